@@ -1,10 +1,16 @@
 //! This module contains the core implementation of the redactor library.
-use std::io::{BufReader, Read};
+use std::io::{Error, ErrorKind, Write};
 use std::sync::{Arc};
 use std::fs::File;
 
 use crate::byte_source::ByteSource;
+use crate::byte_source::file::FileByteSource;
 use crate::byte_source::memory::MemoryByteSource;
+use crate::document::Document;
+use crate::geometry::Rectangle;
+use crate::redaction::{find_detected, find_text, redact_page, Detector, PatternDetector, RedactionReport, TextMatch, TextPattern};
+use crate::structure::xref::{RepairReport, Xref};
+use crate::writer::{RewriteReport, WriteOptions, Writer};
 
 /// A core implementation of this library.
 /// <br>
@@ -15,22 +21,74 @@ use crate::byte_source::memory::MemoryByteSource;
 /// let length: usize = redactor.content_len();
 /// println!("Content length: {}", length);
 /// ```
+///
+/// To get the number and sizes of the pages:
+///
+/// ```rs
+/// println!("{} pages: {:?}", redactor.page_count(), redactor.page_sizes());
+/// ```
+///
+/// To remove everything inside an area of the first page:
+///
+/// ```rs
+/// redactor.mark_area(0, Rectangle::new(100.0, 100.0, 300.0, 130.0))?;
+/// let report = redactor.apply()?;
+/// redactor.write(File::create("redacted.pdf")?)?;
+/// ```
+///
+/// To remove every occurrence of some text, reviewing the matches first:
+///
+/// ```rs
+/// let matches = redactor.mark_text(&TextPattern::regex(r"\d{3}-\d{4}")?)?;
+/// let report = redactor.apply()?;
+/// ```
+///
+/// To remove personal data found by the registered detectors:
+///
+/// ```rs
+/// redactor.add_detector(PatternDetector::new("employee id", r"EMP-\d{5}")?);
+/// let matches = redactor.mark_detected()?;
+/// ```
 #[derive(Debug, Clone)]
 pub struct Redactor {
     /// The original byte source.
     source: Arc<dyn ByteSource>,
+    /// The document read from the source.
+    document: Option<Document>,
+    /// The areas marked for redaction, by page index.
+    marks: Vec<(usize, Rectangle)>,
+    /// The color of the boxes drawn over redacted areas, if any.
+    fill_color: Option<[f64; 3]>,
+    /// The detectors of sensitive data, the built-in ones by default.
+    detectors: Vec<Arc<dyn Detector>>,
+    /// Report of the rewrite made by the last `apply`, if any.
+    rewrite_report: Option<RewriteReport>,
+    /// Options of the file written by `apply`, compact by default.
+    write_options: WriteOptions,
 }
 
 impl Redactor {
 
     /// Reads the content from the given file and creates a new `Redactor` instance.
+    ///
+    /// The file is memory-mapped rather than copied, so it must not be
+    /// changed until `apply` has rewritten the content. A damaged
+    /// cross-reference table is rebuilt by scanning the file; see
+    /// `repair_report`.
     pub fn read(file: &File) -> std::io::Result<Self> {
-        let mut reader = BufReader::new(file);
-        let mut buffer = Vec::new();
-        reader.read_to_end(&mut buffer)?;
-        
+        let source: Arc<dyn ByteSource> = Arc::new(FileByteSource::new(file)?);
+
+        let document = Document::read(&source)
+            .map_err(|message| Error::new(ErrorKind::InvalidData, message))?;
+
         Ok(Self {
-            source: Arc::new(MemoryByteSource::new(buffer)),
+            source,
+            document: Some(document),
+            marks: Vec::new(),
+            fill_color: Some([0.0, 0.0, 0.0]),
+            detectors: built_in_detectors(),
+            rewrite_report: None,
+            write_options: WriteOptions::compact(),
         })
     }
 
@@ -38,6 +96,12 @@ impl Redactor {
     pub fn new() -> Self {
         Self {
             source: Arc::new(MemoryByteSource::new(Vec::new())),
+            document: None,
+            marks: Vec::new(),
+            fill_color: Some([0.0, 0.0, 0.0]),
+            detectors: built_in_detectors(),
+            rewrite_report: None,
+            write_options: WriteOptions::compact(),
         }
     }
     
@@ -46,4 +110,301 @@ impl Redactor {
 
         self.source.len()
     }
+
+    /// Returns the document read from the content, if any.
+    pub fn document(&self) -> Option<&Document> {
+        self.document.as_ref()
+    }
+
+    /// Returns the cross-reference table of the content, if any.
+    pub fn xref(&self) -> Option<&Xref> {
+        self.document.as_ref().map(Document::xref)
+    }
+
+    /// Returns the report of the repair made while reading, if the
+    /// cross-reference table of the file was damaged.
+    pub fn repair_report(&self) -> Option<&RepairReport> {
+        self.document.as_ref().and_then(Document::repair_report)
+    }
+
+    /// Marks an area of a page for redaction.
+    ///
+    /// The area is given in the page's default user space, in points
+    /// from the lower-left corner. Nothing is removed until `apply`.
+    pub fn mark_area(&mut self, page_index: usize, area: Rectangle) -> Result<(), String> {
+
+        if page_index >= self.page_count() {
+            return Err(format!("Page index {} is out of range", page_index));
+        }
+
+        self.marks.push((page_index, area));
+
+        Ok(())
+    }
+
+    /// Finds the matches of a pattern in the text of every page.
+    ///
+    /// Nothing is marked; see `mark_text` and `mark_match`.
+    pub fn find_text(&self, pattern: &TextPattern) -> Result<Vec<TextMatch>, String> {
+
+        let document = self.document.as_ref()
+            .ok_or_else(|| "No document has been read".to_string())?;
+
+        find_text(document, pattern)
+    }
+
+    /// Marks every match of a pattern for redaction and returns the matches.
+    ///
+    /// Text is matched across text operations and lines, and each match
+    /// marks the boxes of its glyphs.
+    pub fn mark_text(&mut self, pattern: &TextPattern) -> Result<Vec<TextMatch>, String> {
+
+        let matches = self.find_text(pattern)?;
+
+        for found in &matches {
+            self.mark_match(found)?;
+        }
+
+        Ok(matches)
+    }
+
+    /// Marks the areas of a match for redaction.
+    pub fn mark_match(&mut self, found: &TextMatch) -> Result<(), String> {
+
+        for area in found.areas() {
+            self.mark_area(found.page(), *area)?;
+        }
+
+        Ok(())
+    }
+
+    /// Registers a detector of sensitive data.
+    pub fn add_detector<D: Detector + 'static>(&mut self, detector: D) {
+        self.detectors.push(Arc::new(detector));
+    }
+
+    /// Removes every registered detector, the built-in ones included.
+    pub fn clear_detectors(&mut self) {
+        self.detectors.clear();
+    }
+
+    /// Returns the registered detectors.
+    pub fn detectors(&self) -> &[Arc<dyn Detector>] {
+        &self.detectors
+    }
+
+    /// Finds the data detected by the registered detectors in the text of every page.
+    pub fn find_detected(&self) -> Result<Vec<TextMatch>, String> {
+
+        let document = self.document.as_ref()
+            .ok_or_else(|| "No document has been read".to_string())?;
+
+        find_detected(document, &self.detectors)
+    }
+
+    /// Marks the data detected by the registered detectors for redaction
+    /// and returns the matches.
+    pub fn mark_detected(&mut self) -> Result<Vec<TextMatch>, String> {
+
+        let matches = self.find_detected()?;
+
+        for found in &matches {
+            self.mark_match(found)?;
+        }
+
+        Ok(matches)
+    }
+
+    /// Returns the areas marked for redaction, by page index.
+    pub fn marked_areas(&self) -> &[(usize, Rectangle)] {
+        &self.marks
+    }
+
+    /// Sets the RGB color of the boxes drawn over redacted areas.
+    ///
+    /// Boxes are black by default; `None` draws no boxes.
+    pub fn set_fill_color(&mut self, color: Option<[f64; 3]>) {
+        self.fill_color = color;
+    }
+
+    /// Sets the options of the file written by `apply`.
+    ///
+    /// Files are compact by default: from PDF 1.5, objects are packed
+    /// into object streams, and streams without a filter are compressed.
+    pub fn set_write_options(&mut self, options: WriteOptions) {
+        self.write_options = options;
+    }
+
+    /// Applies the marked redactions.
+    ///
+    /// Every text glyph and vector path that paints inside a marked area
+    /// is removed from the page content, the pixels of images under it are
+    /// painted black, and a box is drawn over each area. The marks are
+    /// cleared once applied.
+    ///
+    /// The content is then replaced by a new file that holds only the
+    /// objects the redacted document still uses, in a single revision,
+    /// so removed content cannot be recovered; see `write` and
+    /// `rewrite_report`.
+    pub fn apply(&mut self) -> Result<RedactionReport, String> {
+
+        let document = self.document.as_mut()
+            .ok_or_else(|| "No document has been read".to_string())?;
+
+        let mut pages = self.marks.iter().map(|(page, _)| *page).collect::<Vec<_>>();
+        pages.sort_unstable();
+        pages.dedup();
+
+        let mut report = RedactionReport::default();
+
+        for page in pages {
+            let areas = self.marks.iter()
+                .filter(|(index, _)| *index == page)
+                .map(|(_, area)| *area)
+                .collect::<Vec<_>>();
+
+            report.add(&redact_page(document, page, &areas, self.fill_color)?);
+        }
+
+        self.marks.clear();
+
+        let mut writer = Writer::with_options(Vec::new(), self.write_options);
+        let rewrite_report = writer.rewrite_document(document).map_err(|error| error.to_string())?;
+
+        self.rewrite_report = Some(rewrite_report);
+        self.source = Arc::new(MemoryByteSource::new(writer.into_inner()));
+        self.document = Some(Document::read(&self.source)?);
+
+        Ok(report)
+    }
+
+    /// Returns the report of the rewrite made by the last `apply`: the
+    /// objects and bytes dropped from the file.
+    pub fn rewrite_report(&self) -> Option<&RewriteReport> {
+        self.rewrite_report.as_ref()
+    }
+
+    /// Writes the content to the given output.
+    ///
+    /// Once redactions are applied, this is the redacted file.
+    pub fn write<W: Write>(&self, mut output: W) -> std::io::Result<()> {
+
+        if self.document.is_none() {
+            return Err(Error::new(ErrorKind::InvalidInput, "No document has been read"));
+        }
+
+        output.write_all(self.source.slice(0..self.source.len()))
+    }
+
+    /// Returns the number of pages.
+    pub fn page_count(&self) -> usize {
+        self.document.as_ref().map_or(0, |document| document.pages().len())
+    }
+
+    /// Returns the displayed width and height of each page, in points.
+    pub fn page_sizes(&self) -> Vec<(f64, f64)> {
+        self.document.as_ref()
+            .map(|document| document.pages().iter().map(|page| page.size()).collect())
+            .unwrap_or_default()
+    }
+}
+
+impl Default for Redactor {
+
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the built-in detectors of sensitive data.
+fn built_in_detectors() -> Vec<Arc<dyn Detector>> {
+    PatternDetector::built_ins().into_iter().map(|detector| Arc::new(detector) as Arc<dyn Detector>).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::sync::Arc;
+
+    use super::Redactor;
+    use crate::document::Linearization;
+    use crate::geometry::Rectangle;
+    use crate::writer::WriteOptions;
+    use crate::redaction::tests::document;
+
+    /// Returns a redactor of a one-page document, without fill color nor detectors.
+    fn redactor(content: &[u8]) -> Redactor {
+        let document = document(content);
+
+        Redactor {
+            source: Arc::clone(document.source()),
+            document: Some(document),
+            marks: Vec::new(),
+            fill_color: None,
+            detectors: Vec::new(),
+            rewrite_report: None,
+            write_options: WriteOptions::default(),
+        }
+    }
+
+    #[test]
+    fn should_read_mapped_file() {
+        let file = File::open("tests/pdf/test.pdf").unwrap();
+        let redactor = Redactor::read(&file).unwrap();
+
+        assert_eq!(redactor.content_len(), file.metadata().unwrap().len() as usize);
+        assert!(redactor.page_count() > 0);
+    }
+
+    #[test]
+    fn should_write_only_used_objects_after_apply() {
+        let mut redactor = redactor(b"BT /F1 10 Tf 100 100 Td (ABC) Tj ET");
+
+        redactor.mark_area(0, Rectangle::new(95.0, 95.0, 300.0, 120.0)).unwrap();
+        assert_eq!(redactor.apply().unwrap().glyphs(), 3);
+
+        let mut output = Vec::new();
+        redactor.write(&mut output).unwrap();
+
+        assert_eq!(output.len(), redactor.content_len());
+        assert_eq!(String::from_utf8_lossy(&output).matches("startxref").count(), 1);
+        assert!(redactor.repair_report().is_none());
+
+        assert!(!String::from_utf8_lossy(&output).contains("(ABC)"));
+        assert_eq!(redactor.rewrite_report().unwrap().dropped_objects(), 1);
+    }
+
+    #[test]
+    fn should_write_linearized_file_when_asked() {
+        let mut redactor = redactor(b"BT /F1 10 Tf 100 100 Td (ABC) Tj ET");
+
+        redactor.set_write_options(WriteOptions::compact().with_linearization(true));
+        redactor.mark_area(0, Rectangle::new(95.0, 95.0, 300.0, 120.0)).unwrap();
+        redactor.apply().unwrap();
+
+        let document = redactor.document().unwrap();
+        let linearization = Linearization::read(document).unwrap().unwrap();
+
+        assert_eq!(linearization.length(), redactor.content_len());
+        assert!(linearization.check(document).is_empty());
+    }
+
+    #[test]
+    fn should_blank_image_pixels_after_apply() {
+        let mut redactor = redactor(b"q 100 0 0 100 100 100 cm /Im1 Do Q q 100 0 0 100 300 100 cm /Im2 Do Q");
+
+        redactor.mark_area(0, Rectangle::new(120.0, 120.0, 130.0, 130.0)).unwrap();
+        assert_eq!(redactor.apply().unwrap().images(), 1);
+
+        let document = redactor.document().unwrap();
+        let xobjects = document.page(0).unwrap().resources().get(b"/XObject").unwrap().as_dicionary().unwrap().clone();
+        let pixels = |name: &[u8]| {
+            let image = document.resolve(xobjects.get(name).unwrap()).unwrap();
+            image.as_stream().unwrap().decode().unwrap()
+        };
+
+        assert!(xobjects.get(b"/Im1").is_none());
+        assert_eq!(pixels(b"/Redacted1"), vec![0]);
+        assert_eq!(pixels(b"/Im2"), vec![255]);
+    }
 }
\ No newline at end of file
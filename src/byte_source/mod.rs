@@ -11,4 +11,9 @@ pub trait ByteSource: Send + Sync + Debug + 'static {
 
     /// Returns the total length of the byte source.
     fn len(&self) -> usize;
+
+    /// Returns `true` if the byte source contains no bytes.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 }
\ No newline at end of file
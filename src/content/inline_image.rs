@@ -0,0 +1,81 @@
+use crate::object::{Dicionary, Name, Object, Stream};
+
+/// The abbreviated keys of inline image dictionaries, with their full keys.
+const ABBREVIATIONS: [(&[u8], &[u8]); 9] = [
+    (b"/BPC", b"/BitsPerComponent"),
+    (b"/CS", b"/ColorSpace"),
+    (b"/D", b"/Decode"),
+    (b"/DP", b"/DecodeParms"),
+    (b"/F", b"/Filter"),
+    (b"/H", b"/Height"),
+    (b"/IM", b"/ImageMask"),
+    (b"/I", b"/Interpolate"),
+    (b"/W", b"/Width"),
+];
+
+/// An inline image of a content stream (`BI ... ID ... EI`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InlineImage {
+    dicionary: Dicionary,
+    data: Vec<u8>,
+    bytes: Vec<u8>,
+}
+
+impl InlineImage {
+
+    /// Creates a new `InlineImage` from its dictionary and data.
+    pub fn new(dicionary: Dicionary, data: Vec<u8>) -> Self {
+
+        let mut bytes = b"BI".to_vec();
+
+        for (key, value) in dicionary.iter() {
+            bytes.push(b' ');
+            bytes.extend_from_slice(key.as_bytes());
+            bytes.push(b' ');
+            bytes.extend_from_slice(value.as_bytes());
+        }
+
+        // A single whitespace character separates `ID` from the data,
+        // and `EI` must be preceded by whitespace.
+        bytes.extend_from_slice(b" ID ");
+        bytes.extend_from_slice(&data);
+        bytes.extend_from_slice(b"\nEI");
+
+        Self {
+            dicionary,
+            data,
+            bytes,
+        }
+    }
+
+    /// Returns the image data, still encoded by its filters.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Returns the image as an image XObject, with the full keys.
+    ///
+    /// Abbreviated values (i.e `/G` or `/Fl`) are kept, as color spaces
+    /// and filters also accept them.
+    pub fn to_stream(&self) -> Stream {
+
+        let mut dicionary = Dicionary::new(vec![]);
+        dicionary.insert(Name::new(b"/Type").unwrap(), Object::Name(Name::new(b"/XObject").unwrap()));
+        dicionary.insert(Name::new(b"/Subtype").unwrap(), Object::Name(Name::new(b"/Image").unwrap()));
+
+        for (key, value) in self.dicionary.iter() {
+            let key = ABBREVIATIONS.iter()
+                .find(|(abbreviation, _)| *abbreviation == key.as_bytes())
+                .map_or(key.clone(), |(_, full)| Name::new(full).unwrap());
+
+            dicionary.insert(key, value.clone());
+        }
+
+        Stream::new(dicionary, self.data.clone())
+    }
+
+    /// Returns the byte representation of the inline image.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
\ No newline at end of file
@@ -0,0 +1,539 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::content::{parse_operations, Operation, Operator};
+use crate::document::Document;
+use crate::font::Font;
+use crate::geometry::{Matrix, Rectangle};
+use crate::object::{Dicionary, Null, Object, ObjectId, Stream};
+
+/// Maximum nesting of form XObjects.
+const MAX_FORM_DEPTH: usize = 32;
+
+/// The part of the graphics state needed to locate content.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphicsState {
+    /// The current transformation matrix.
+    pub ctm: Matrix,
+    /// The line width (`w`).
+    pub line_width: f64,
+    /// The resource name of the current font (`Tf`).
+    pub font: Option<Vec<u8>>,
+    /// The font size (`Tf`).
+    pub font_size: f64,
+    /// The character spacing (`Tc`).
+    pub character_spacing: f64,
+    /// The word spacing (`Tw`).
+    pub word_spacing: f64,
+    /// The horizontal scaling as a factor (`Tz` divided by 100).
+    pub horizontal_scaling: f64,
+    /// The leading (`TL`).
+    pub leading: f64,
+    /// The text rise (`Ts`).
+    pub rise: f64,
+}
+
+impl Default for GraphicsState {
+
+    fn default() -> Self {
+        Self {
+            ctm: Matrix::IDENTITY,
+            line_width: 1.0,
+            font: None,
+            font_size: 0.0,
+            character_spacing: 0.0,
+            word_spacing: 0.0,
+            horizontal_scaling: 1.0,
+            leading: 0.0,
+            rise: 0.0,
+        }
+    }
+}
+
+/// A glyph shown by a text-showing operation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Glyph {
+    /// The character code.
+    code: u32,
+    /// The length of the character code in bytes.
+    length: usize,
+    /// The text of the character code.
+    text: String,
+    /// Bounding box of the glyph in default user space.
+    bounding_box: Rectangle,
+    /// Horizontal displacement in unscaled text space units, spacing included.
+    advance: f64,
+    /// The font size the glyph was shown with.
+    font_size: f64,
+}
+
+impl Glyph {
+
+    /// Returns the character code.
+    pub fn code(&self) -> u32 {
+        self.code
+    }
+
+    /// Returns the length of the character code in bytes.
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    /// Returns the text of the character code.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the bounding box of the glyph in default user space.
+    pub fn bounding_box(&self) -> Rectangle {
+        self.bounding_box
+    }
+
+    /// Returns the horizontal displacement in unscaled text space units, spacing included.
+    pub fn advance(&self) -> f64 {
+        self.advance
+    }
+
+    /// Returns the font size the glyph was shown with.
+    pub fn font_size(&self) -> f64 {
+        self.font_size
+    }
+}
+
+/// Runs the operations of a content stream, tracking the graphics and
+/// text state to locate the glyphs they show.
+///
+/// Form XObjects painted with `Do` are run recursively, with their own
+/// resources and matrix.
+#[derive(Debug, Clone)]
+pub struct Interpreter<'a> {
+    /// The document the content belongs to.
+    document: &'a Document,
+    /// The resource dictionary of the content.
+    resources: Dicionary,
+    /// The fonts used so far, by resource name.
+    fonts: HashMap<Vec<u8>, Arc<Font>>,
+    /// The current graphics state.
+    state: GraphicsState,
+    /// Graphics states saved by `q`.
+    stack: Vec<GraphicsState>,
+    /// The text matrix.
+    text_matrix: Matrix,
+    /// The text line matrix.
+    line_matrix: Matrix,
+    /// The form XObjects being run, outermost first.
+    forms: Vec<ObjectId>,
+}
+
+impl<'a> Interpreter<'a> {
+
+    /// Creates a new `Interpreter` with the default graphics state.
+    pub fn new(document: &'a Document, resources: &Dicionary) -> Self {
+        Self {
+            document,
+            resources: resources.clone(),
+            fonts: HashMap::new(),
+            state: GraphicsState::default(),
+            stack: Vec::new(),
+            text_matrix: Matrix::IDENTITY,
+            line_matrix: Matrix::IDENTITY,
+            forms: Vec::new(),
+        }
+    }
+
+    /// Returns the document the content belongs to.
+    pub fn document(&self) -> &'a Document {
+        self.document
+    }
+
+    /// Returns the resource dictionary of the content.
+    pub fn resources(&self) -> &Dicionary {
+        &self.resources
+    }
+
+    /// Returns the current graphics state.
+    pub fn state(&self) -> &GraphicsState {
+        &self.state
+    }
+
+    /// Runs operations and returns the glyphs they show.
+    pub fn run(&mut self, operations: &[Operation]) -> Result<Vec<Glyph>, String> {
+
+        let mut glyphs = Vec::new();
+
+        for operation in operations {
+            glyphs.extend(self.execute(operation)?);
+        }
+
+        Ok(glyphs)
+    }
+
+    /// Runs one operation and returns the glyphs it shows.
+    pub fn execute(&mut self, operation: &Operation) -> Result<Vec<Glyph>, String> {
+
+        if *operation.operator() == Operator::PaintXObject {
+            return self.run_form(operation);
+        }
+
+        let Some(elements) = self.begin_show(operation) else {
+            self.update(operation);
+            return Ok(Vec::new());
+        };
+
+        let font = self.font()?;
+        let mut glyphs = Vec::new();
+
+        for element in elements {
+            match element {
+                Ok(text) => glyphs.extend(self.show(&font, &text)),
+                Err(adjust) => self.adjust(&font, adjust),
+            }
+        }
+
+        Ok(glyphs)
+    }
+
+    /// Applies an operation that changes the graphics or text state.
+    ///
+    /// Operations that do not change the state are ignored. The line
+    /// moves of `'` and `"` are applied by `begin_show`.
+    pub fn update(&mut self, operation: &Operation) {
+
+        match operation.operator() {
+            Operator::SaveState => self.stack.push(self.state.clone()),
+            Operator::RestoreState => {
+                if let Some(state) = self.stack.pop() {
+                    self.state = state;
+                }
+            }
+            Operator::ConcatMatrix => {
+                if let Some([a, b, c, d, e, f]) = operation.number_operands(6).as_deref() {
+                    self.state.ctm = Matrix::new(*a, *b, *c, *d, *e, *f).multiply(&self.state.ctm);
+                }
+            }
+            Operator::LineWidth => {
+                if let Some([width]) = operation.number_operands(1).as_deref() {
+                    self.state.line_width = *width;
+                }
+            }
+            Operator::BeginText => {
+                self.text_matrix = Matrix::IDENTITY;
+                self.line_matrix = Matrix::IDENTITY;
+            }
+            Operator::CharacterSpacing | Operator::WordSpacing | Operator::HorizontalScaling | Operator::Leading | Operator::Rise => {
+                if let Some([value]) = operation.number_operands(1).as_deref() {
+                    match operation.operator() {
+                        Operator::CharacterSpacing => self.state.character_spacing = *value,
+                        Operator::WordSpacing => self.state.word_spacing = *value,
+                        Operator::HorizontalScaling => self.state.horizontal_scaling = *value / 100.0,
+                        Operator::Leading => self.state.leading = *value,
+                        _ => self.state.rise = *value,
+                    }
+                }
+            }
+            Operator::Font => {
+                if let (Some(name), Some(size)) = (operation.name_operand(0), operation.operands().get(1).and_then(Object::as_f64)) {
+                    self.state.font = Some(name.as_bytes().to_vec());
+                    self.state.font_size = size;
+                }
+            }
+            Operator::MoveText | Operator::MoveTextSetLeading => {
+                if let Some([x, y]) = operation.number_operands(2).as_deref() {
+                    if *operation.operator() == Operator::MoveTextSetLeading {
+                        self.state.leading = -y;
+                    }
+
+                    self.move_line(*x, *y);
+                }
+            }
+            Operator::TextMatrix => {
+                if let Some([a, b, c, d, e, f]) = operation.number_operands(6).as_deref() {
+                    self.line_matrix = Matrix::new(*a, *b, *c, *d, *e, *f);
+                    self.text_matrix = self.line_matrix;
+                }
+            }
+            Operator::NextLine => self.move_line(0.0, -self.state.leading),
+            _ => {}
+        }
+    }
+
+    /// Applies the state changes of a text-showing operation and returns its strings.
+    ///
+    /// `'` moves to the next line, and `"` also sets the word and
+    /// character spacing. Position adjustments of `TJ` are returned
+    /// as `Err` values in thousandths of text space units.
+    pub(crate) fn begin_show(&mut self, operation: &Operation) -> Option<Vec<Result<Vec<u8>, f64>>> {
+
+        let operands = operation.operands();
+
+        match operation.operator() {
+            Operator::ShowText => operands.first().and_then(Object::as_string_bytes).map(|text| vec![Ok(text)]),
+            Operator::ShowTextArray => operands.first().and_then(Object::as_array).map(|array| {
+                array.as_objects().iter()
+                    .filter_map(|element| match element {
+                        Object::Integer(_) | Object::Real(_) => element.as_f64().map(Err),
+                        _ => element.as_string_bytes().map(Ok),
+                    })
+                    .collect()
+            }),
+            Operator::NextLineShowText => {
+                self.move_line(0.0, -self.state.leading);
+                operands.first().and_then(Object::as_string_bytes).map(|text| vec![Ok(text)])
+            }
+            Operator::NextLineShowTextSpacing => {
+                if let (Some(word), Some(character)) = (operands.first().and_then(Object::as_f64), operands.get(1).and_then(Object::as_f64)) {
+                    self.state.word_spacing = word;
+                    self.state.character_spacing = character;
+                }
+
+                self.move_line(0.0, -self.state.leading);
+                operands.get(2).and_then(Object::as_string_bytes).map(|text| vec![Ok(text)])
+            }
+            _ => None,
+        }
+    }
+
+    /// Moves to the start of the next line, offset from the start of the current line.
+    fn move_line(&mut self, x: f64, y: f64) {
+        self.line_matrix = Matrix::translate(x, y).multiply(&self.line_matrix);
+        self.text_matrix = self.line_matrix;
+    }
+
+    /// Returns the current font.
+    pub(crate) fn font(&mut self) -> Result<Arc<Font>, String> {
+
+        let Some(name) = self.state.font.clone() else {
+            return Ok(Arc::new(Font::default()));
+        };
+
+        if let Some(font) = self.fonts.get(&name) {
+            return Ok(font.clone());
+        }
+
+        let font = match self.resource(b"/Font", &name)? {
+            (_, Object::Dicionary(font)) => Arc::new(Font::read(self.document, &font)?),
+            _ => Arc::new(Font::default()),
+        };
+
+        self.fonts.insert(name, font.clone());
+
+        Ok(font)
+    }
+
+    /// Shows a string with the given font, advancing the text matrix.
+    ///
+    /// Returns the glyphs with their bounding boxes.
+    pub(crate) fn show(&mut self, font: &Font, text: &[u8]) -> Vec<Glyph> {
+
+        let state = &self.state;
+        let mut glyphs = Vec::new();
+
+        let metrics = font.metrics();
+
+        for (code, length) in font.codes(text) {
+
+            let width = font.width(code, length);
+
+            // Glyph boxes are in glyph space, mapped to text space by the font matrix.
+            let rendering = metrics.matrix()
+                .multiply(&Matrix::new(state.font_size * state.horizontal_scaling, 0.0, 0.0, state.font_size, 0.0, state.rise))
+                .multiply(&self.text_matrix)
+                .multiply(&state.ctm);
+
+            let spacing = state.character_spacing
+                + if font.is_word_space(code, length) { state.word_spacing } else { 0.0 };
+
+            // In vertical writing, glyphs hang below their vertical origin
+            // and text advances downwards.
+            let (glyph, advance) = if font.is_vertical() {
+                let (vertical, x, y) = font.vertical_metrics(code, length);
+
+                let glyph = Rectangle::new(-x, metrics.descent() - y, width - x, metrics.ascent() - y);
+                let advance = vertical * metrics.matrix().d * state.font_size + spacing;
+
+                self.text_matrix = Matrix::translate(0.0, advance).multiply(&self.text_matrix);
+
+                (glyph, advance)
+            } else {
+                let glyph = Rectangle::new(0.0, metrics.descent(), width, metrics.ascent());
+                let advance = width * metrics.matrix().a * state.font_size + spacing;
+
+                self.text_matrix = Matrix::translate(advance * state.horizontal_scaling, 0.0).multiply(&self.text_matrix);
+
+                (glyph, advance)
+            };
+
+            glyphs.push(Glyph {
+                code,
+                length,
+                text: font.text(code, length),
+                bounding_box: rendering.transform_rectangle(&glyph),
+                advance,
+                font_size: state.font_size,
+            });
+        }
+
+        glyphs
+    }
+
+    /// Applies a `TJ` position adjustment, in thousandths of text space units.
+    ///
+    /// Adjustments move text vertically in fonts for vertical writing.
+    pub(crate) fn adjust(&mut self, font: &Font, adjust: f64) {
+
+        let shift = -adjust / 1000.0 * self.state.font_size;
+
+        let translation = match font.is_vertical() {
+            true => Matrix::translate(0.0, shift),
+            false => Matrix::translate(shift * self.state.horizontal_scaling, 0.0),
+        };
+
+        self.text_matrix = translation.multiply(&self.text_matrix);
+    }
+
+    /// Returns a named resource of a category (i.e `/XObject`), resolved,
+    /// with its object identifier if it is an indirect object.
+    ///
+    /// A missing resource is the null object.
+    pub fn resource(&self, category: &[u8], name: &[u8]) -> Result<(Option<ObjectId>, Object), String> {
+
+        let Some(resources) = self.resources.get(category) else {
+            return Ok((None, Object::Null(Null::new())));
+        };
+
+        match self.document.resolve(resources)?.as_dicionary().and_then(|resources| resources.get(name).cloned()) {
+            Some(resource) => Ok((resource.as_reference(), self.document.resolve(&resource)?)),
+            None => Ok((None, Object::Null(Null::new()))),
+        }
+    }
+
+    /// Runs the form XObject painted by a `Do` operation and returns its glyphs.
+    ///
+    /// Images and forms already being run (a cycle) show nothing.
+    fn run_form(&mut self, operation: &Operation) -> Result<Vec<Glyph>, String> {
+
+        let Some(name) = operation.name_operand(0) else {
+            return Ok(Vec::new());
+        };
+
+        let (id, Object::Stream(form)) = self.resource(b"/XObject", name.as_bytes())? else {
+            return Ok(Vec::new());
+        };
+
+        let is_form = form.dicionary().get(b"/Subtype").and_then(Object::as_name).is_some_and(|subtype| subtype.as_bytes() == b"/Form");
+
+        if !is_form {
+            return Ok(Vec::new());
+        }
+
+        let Some(mut interpreter) = self.form_interpreter(id, &form)? else {
+            return Ok(Vec::new());
+        };
+
+        interpreter.run(&parse_operations(&form.decode()?)?)
+    }
+
+    /// Returns an interpreter for the content of a form XObject painted
+    /// in the current state, or `None` if the form is already being run
+    /// (a cycle) or forms are nested too deeply.
+    pub(crate) fn form_interpreter(&self, id: Option<ObjectId>, form: &Stream) -> Result<Option<Interpreter<'a>>, String> {
+
+        if id.is_some_and(|id| self.forms.contains(&id)) || self.forms.len() >= MAX_FORM_DEPTH {
+            return Ok(None);
+        }
+
+        let dicionary = form.dicionary();
+
+        let matrix = dicionary.get(b"/Matrix")
+            .map(|matrix| self.document.resolve(matrix))
+            .transpose()?
+            .and_then(|matrix| Matrix::from_object(&matrix))
+            .unwrap_or(Matrix::IDENTITY);
+
+        // A form without resources uses those of the content painting it.
+        let resources = match dicionary.get(b"/Resources").map(|resources| self.document.resolve(resources)).transpose()? {
+            Some(Object::Dicionary(resources)) => resources,
+            _ => self.resources.clone(),
+        };
+
+        let mut interpreter = Interpreter::new(self.document, &resources);
+        interpreter.state = self.state.clone();
+        interpreter.state.ctm = matrix.multiply(&self.state.ctm);
+        interpreter.forms = self.forms.iter().copied().chain(id).collect();
+
+        Ok(Some(interpreter))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Interpreter;
+    use crate::content::parse_operations;
+    use crate::document::Document;
+    use crate::document::tests::build_pdf;
+    use crate::geometry::Rectangle;
+
+    fn glyphs(content: &[u8]) -> Vec<Rectangle> {
+        let source = build_pdf(&[
+            b"<</Type /Catalog /Pages 2 0 R>>",
+            b"<</Type /Pages /Kids [3 0 R] /Count 1>>",
+            b"<</Type /Page /Parent 2 0 R /Resources <</Font <</F1 4 0 R /F2 7 0 R /F3 9 0 R>> /XObject <</Fm1 5 0 R /Fm2 6 0 R>>>>>>",
+            b"<</Type /Font /Subtype /Type1 /BaseFont /Helvetica /FirstChar 65 /Widths [500 600]>>",
+            b"<</Type /XObject /Subtype /Form /BBox [0 0 100 100] /Matrix [1 0 0 1 0 100] /Length 22>>\nstream\nBT /F1 10 Tf (A) Tj ET\nendstream",
+            b"<</Type /XObject /Subtype /Form /BBox [0 0 100 100] /Length 7>>\nstream\n/Fm2 Do\nendstream",
+            b"<</Type /Font /Subtype /Type0 /BaseFont /Mincho /Encoding /Identity-V /DescendantFonts [8 0 R]>>",
+            b"<</Type /Font /Subtype /CIDFontType0 /BaseFont /Mincho /FontDescriptor <</Ascent 750 /Descent -250>> /W [1 [500]] /DW2 [750 -1000] /W2 [1 1 -500 250 750]>>",
+            b"<</Type /Font /Subtype /Type3 /FontMatrix [0.01 0 0 0.01 0 0] /FontBBox [0 -20 100 80] /FirstChar 65 /LastChar 65 /Widths [50] /CharProcs <<>> /Encoding <</Differences [65 /a]>> /Resources <<>>>>",
+        ]);
+
+        let document = Document::read(&source).unwrap();
+        let resources = document.page(0).unwrap().resources().clone();
+
+        Interpreter::new(&document, &resources)
+            .run(&parse_operations(content).unwrap())
+            .unwrap()
+            .iter()
+            .map(|glyph| glyph.bounding_box())
+            .collect()
+    }
+
+    #[test]
+    fn should_place_glyphs_with_text_state() {
+        let boxes = glyphs(b"q 2 0 0 2 0 0 cm BT /F1 10 Tf 50 Tz 1 Tc 5 Ts 10 20 Td (AB) Tj ET Q BT /F1 10 Tf (A) Tj ET");
+
+        assert_eq!(boxes, vec![
+            Rectangle::new(20.0, 45.86, 25.0, 64.36),
+            Rectangle::new(26.0, 45.86, 32.0, 64.36),
+            Rectangle::new(0.0, -2.07, 5.0, 7.18),
+        ]);
+    }
+
+    #[test]
+    fn should_run_forms_recursively() {
+        let boxes = glyphs(b"q 1 0 0 1 10 0 cm /Fm1 Do /Fm2 Do Q");
+
+        assert_eq!(boxes, vec![Rectangle::new(10.0, 97.93, 15.0, 107.18)]);
+    }
+
+    #[test]
+    fn should_scale_type3_glyphs_with_font_matrix() {
+        let boxes = glyphs(b"BT /F3 10 Tf 100 100 Td (AA) Tj ET");
+
+        assert_eq!(boxes, vec![
+            Rectangle::new(100.0, 98.0, 105.0, 108.0),
+            Rectangle::new(105.0, 98.0, 110.0, 108.0),
+        ]);
+    }
+
+    #[test]
+    fn should_place_glyphs_in_vertical_writing() {
+        let boxes = glyphs(b"BT /F2 10 Tf 100 200 Td <00010002> Tj [<0002> 500 <0002>] TJ ET");
+
+        assert_eq!(boxes, vec![
+            Rectangle::new(97.5, 190.0, 102.5, 200.0),
+            Rectangle::new(95.0, 185.0, 105.0, 195.0),
+            Rectangle::new(95.0, 175.0, 105.0, 185.0),
+            Rectangle::new(95.0, 160.0, 105.0, 170.0),
+        ]);
+    }
+}
\ No newline at end of file
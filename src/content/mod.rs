@@ -0,0 +1,12 @@
+//! This module contains the operations of content streams.
+mod inline_image;
+mod interpreter;
+mod operation;
+mod operator;
+mod parser;
+
+pub use crate::content::inline_image::InlineImage;
+pub use crate::content::interpreter::Interpreter;
+pub use crate::content::operation::Operation;
+pub use crate::content::operator::Operator;
+pub use crate::content::parser::parse_operations;
\ No newline at end of file
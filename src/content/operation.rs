@@ -0,0 +1,96 @@
+use crate::content::{InlineImage, Operator};
+use crate::object::{Name, Object};
+
+/// An operation of a content stream: operands followed by an operator.
+///
+/// An inline image is a single `BI` operation without operands.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Operation {
+    operands: Vec<Object>,
+    operator: Operator,
+    inline_image: Option<InlineImage>,
+    bytes: Vec<u8>,
+}
+
+impl Operation {
+
+    /// Creates a new `Operation` from its operator and operands.
+    pub fn new(operator: Operator, operands: Vec<Object>) -> Self {
+
+        let mut bytes = Vec::new();
+
+        for operand in &operands {
+            bytes.extend_from_slice(operand.as_bytes());
+            bytes.push(b' ');
+        }
+
+        bytes.extend_from_slice(operator.as_bytes());
+
+        Self {
+            operands,
+            operator,
+            inline_image: None,
+            bytes,
+        }
+    }
+
+    /// Creates a new `Operation` painting an inline image.
+    pub fn from_inline_image(image: InlineImage) -> Self {
+        Self {
+            operands: Vec::new(),
+            operator: Operator::BeginInlineImage,
+            bytes: image.as_bytes().to_vec(),
+            inline_image: Some(image),
+        }
+    }
+
+    /// Returns the operator.
+    pub fn operator(&self) -> &Operator {
+        &self.operator
+    }
+
+    /// Returns the operands.
+    pub fn operands(&self) -> &[Object] {
+        &self.operands
+    }
+
+    /// Returns the inline image painted by a `BI` operation.
+    pub fn inline_image(&self) -> Option<&InlineImage> {
+        self.inline_image.as_ref()
+    }
+
+    /// Returns the operand at the index, if it is a name.
+    pub fn name_operand(&self, index: usize) -> Option<&Name> {
+        self.operands.get(index).and_then(Object::as_name)
+    }
+
+    /// Returns the numeric operands, if there are exactly `count` of them.
+    pub fn number_operands(&self, count: usize) -> Option<Vec<f64>> {
+
+        let numbers = self.operands.iter().map(Object::as_f64).collect::<Option<Vec<_>>>()?;
+
+        (numbers.len() == count).then_some(numbers)
+    }
+
+    /// Returns the byte representation of the Operation.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Operation;
+    use crate::content::Operator;
+    use crate::object::Object;
+
+    #[test]
+    fn should_serialize_operands_and_operator() {
+        let operation = Operation::new(Operator::Font, vec![Object::parse(b"/F1").unwrap(), Object::parse(b"12").unwrap()]);
+
+        assert_eq!(operation.as_bytes(), b"/F1 12 Tf");
+        assert_eq!(operation.name_operand(0).unwrap().as_bytes(), b"/F1");
+        assert_eq!(operation.number_operands(2), None);
+        assert_eq!(Operation::new(Operator::SaveState, vec![]).as_bytes(), b"q");
+    }
+}
\ No newline at end of file
@@ -0,0 +1,378 @@
+/// PDF content stream operator (ISO 32000-2, Annex A).
+///
+/// Operators not defined by the specification are kept as `Other`,
+/// since they may appear inside compatibility sections (`BX`/`EX`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Operator {
+    /// Sets the line width, `w`.
+    LineWidth,
+    /// Sets the line cap style, `J`.
+    LineCap,
+    /// Sets the line join style, `j`.
+    LineJoin,
+    /// Sets the miter limit, `M`.
+    MiterLimit,
+    /// Sets the line dash pattern, `d`.
+    DashPattern,
+    /// Sets the color rendering intent, `ri`.
+    RenderingIntent,
+    /// Sets the flatness tolerance, `i`.
+    Flatness,
+    /// Sets parameters from a graphics state parameter dictionary, `gs`.
+    GraphicsStateParameters,
+    /// Saves the graphics state, `q`.
+    SaveState,
+    /// Restores the graphics state, `Q`.
+    RestoreState,
+    /// Concatenates a matrix to the current transformation matrix, `cm`.
+    ConcatMatrix,
+    /// Begins a new subpath, `m`.
+    MoveTo,
+    /// Appends a straight line segment, `l`.
+    LineTo,
+    /// Appends a Bézier curve, `c`.
+    CurveTo,
+    /// Appends a Bézier curve using the current point as first control point, `v`.
+    CurveToInitial,
+    /// Appends a Bézier curve using the end point as second control point, `y`.
+    CurveToFinal,
+    /// Closes the current subpath, `h`.
+    ClosePath,
+    /// Appends a rectangle, `re`.
+    Rectangle,
+    /// Strokes the path, `S`.
+    Stroke,
+    /// Closes and strokes the path, `s`.
+    CloseStroke,
+    /// Fills the path with the nonzero winding number rule, `f`.
+    Fill,
+    /// Fills the path, obsolete equivalent of `f`, `F`.
+    FillObsolete,
+    /// Fills the path with the even-odd rule, `f*`.
+    FillEvenOdd,
+    /// Fills and strokes the path, `B`.
+    FillStroke,
+    /// Fills with the even-odd rule and strokes the path, `B*`.
+    FillStrokeEvenOdd,
+    /// Closes, fills and strokes the path, `b`.
+    CloseFillStroke,
+    /// Closes, fills with the even-odd rule and strokes the path, `b*`.
+    CloseFillStrokeEvenOdd,
+    /// Ends the path without painting it, `n`.
+    EndPath,
+    /// Intersects the clipping path with the nonzero winding number rule, `W`.
+    Clip,
+    /// Intersects the clipping path with the even-odd rule, `W*`.
+    ClipEvenOdd,
+    /// Begins a text object, `BT`.
+    BeginText,
+    /// Ends a text object, `ET`.
+    EndText,
+    /// Sets the character spacing, `Tc`.
+    CharacterSpacing,
+    /// Sets the word spacing, `Tw`.
+    WordSpacing,
+    /// Sets the horizontal scaling, `Tz`.
+    HorizontalScaling,
+    /// Sets the leading, `TL`.
+    Leading,
+    /// Sets the font and font size, `Tf`.
+    Font,
+    /// Sets the text rendering mode, `Tr`.
+    RenderingMode,
+    /// Sets the text rise, `Ts`.
+    Rise,
+    /// Moves to the start of the next line, `Td`.
+    MoveText,
+    /// Moves to the start of the next line and sets the leading, `TD`.
+    MoveTextSetLeading,
+    /// Sets the text matrix and the text line matrix, `Tm`.
+    TextMatrix,
+    /// Moves to the start of the next line, `T*`.
+    NextLine,
+    /// Shows a string, `Tj`.
+    ShowText,
+    /// Shows strings with individual glyph positioning, `TJ`.
+    ShowTextArray,
+    /// Moves to the next line and shows a string, `'`.
+    NextLineShowText,
+    /// Sets the spacing, moves to the next line and shows a string, `"`.
+    NextLineShowTextSpacing,
+    /// Sets the glyph width of a Type 3 glyph, `d0`.
+    GlyphWidth,
+    /// Sets the glyph width and bounding box of a Type 3 glyph, `d1`.
+    GlyphWidthBoundingBox,
+    /// Sets the stroking color space, `CS`.
+    StrokeColorSpace,
+    /// Sets the nonstroking color space, `cs`.
+    FillColorSpace,
+    /// Sets the stroking color, `SC`.
+    StrokeColor,
+    /// Sets the stroking color, patterns included, `SCN`.
+    StrokeColorExtended,
+    /// Sets the nonstroking color, `sc`.
+    FillColor,
+    /// Sets the nonstroking color, patterns included, `scn`.
+    FillColorExtended,
+    /// Sets a gray stroking color, `G`.
+    StrokeGray,
+    /// Sets a gray nonstroking color, `g`.
+    FillGray,
+    /// Sets an RGB stroking color, `RG`.
+    StrokeRgb,
+    /// Sets an RGB nonstroking color, `rg`.
+    FillRgb,
+    /// Sets a CMYK stroking color, `K`.
+    StrokeCmyk,
+    /// Sets a CMYK nonstroking color, `k`.
+    FillCmyk,
+    /// Paints a shading, `sh`.
+    Shading,
+    /// Begins an inline image, `BI`.
+    BeginInlineImage,
+    /// Begins the data of an inline image, `ID`.
+    InlineImageData,
+    /// Ends an inline image, `EI`.
+    EndInlineImage,
+    /// Paints an XObject, `Do`.
+    PaintXObject,
+    /// Designates a marked-content point, `MP`.
+    MarkedContentPoint,
+    /// Designates a marked-content point with a property list, `DP`.
+    MarkedContentPointProperties,
+    /// Begins a marked-content sequence, `BMC`.
+    BeginMarkedContent,
+    /// Begins a marked-content sequence with a property list, `BDC`.
+    BeginMarkedContentProperties,
+    /// Ends a marked-content sequence, `EMC`.
+    EndMarkedContent,
+    /// Begins a compatibility section, `BX`.
+    BeginCompatibility,
+    /// Ends a compatibility section, `EX`.
+    EndCompatibility,
+    /// Any other operator.
+    Other(Vec<u8>),
+}
+
+impl Operator {
+
+    /// Creates a new `Operator` from the given bytes.
+    pub fn new(bytes: &[u8]) -> Self {
+        match bytes {
+            b"w" => Operator::LineWidth,
+            b"J" => Operator::LineCap,
+            b"j" => Operator::LineJoin,
+            b"M" => Operator::MiterLimit,
+            b"d" => Operator::DashPattern,
+            b"ri" => Operator::RenderingIntent,
+            b"i" => Operator::Flatness,
+            b"gs" => Operator::GraphicsStateParameters,
+            b"q" => Operator::SaveState,
+            b"Q" => Operator::RestoreState,
+            b"cm" => Operator::ConcatMatrix,
+            b"m" => Operator::MoveTo,
+            b"l" => Operator::LineTo,
+            b"c" => Operator::CurveTo,
+            b"v" => Operator::CurveToInitial,
+            b"y" => Operator::CurveToFinal,
+            b"h" => Operator::ClosePath,
+            b"re" => Operator::Rectangle,
+            b"S" => Operator::Stroke,
+            b"s" => Operator::CloseStroke,
+            b"f" => Operator::Fill,
+            b"F" => Operator::FillObsolete,
+            b"f*" => Operator::FillEvenOdd,
+            b"B" => Operator::FillStroke,
+            b"B*" => Operator::FillStrokeEvenOdd,
+            b"b" => Operator::CloseFillStroke,
+            b"b*" => Operator::CloseFillStrokeEvenOdd,
+            b"n" => Operator::EndPath,
+            b"W" => Operator::Clip,
+            b"W*" => Operator::ClipEvenOdd,
+            b"BT" => Operator::BeginText,
+            b"ET" => Operator::EndText,
+            b"Tc" => Operator::CharacterSpacing,
+            b"Tw" => Operator::WordSpacing,
+            b"Tz" => Operator::HorizontalScaling,
+            b"TL" => Operator::Leading,
+            b"Tf" => Operator::Font,
+            b"Tr" => Operator::RenderingMode,
+            b"Ts" => Operator::Rise,
+            b"Td" => Operator::MoveText,
+            b"TD" => Operator::MoveTextSetLeading,
+            b"Tm" => Operator::TextMatrix,
+            b"T*" => Operator::NextLine,
+            b"Tj" => Operator::ShowText,
+            b"TJ" => Operator::ShowTextArray,
+            b"'" => Operator::NextLineShowText,
+            b"\"" => Operator::NextLineShowTextSpacing,
+            b"d0" => Operator::GlyphWidth,
+            b"d1" => Operator::GlyphWidthBoundingBox,
+            b"CS" => Operator::StrokeColorSpace,
+            b"cs" => Operator::FillColorSpace,
+            b"SC" => Operator::StrokeColor,
+            b"SCN" => Operator::StrokeColorExtended,
+            b"sc" => Operator::FillColor,
+            b"scn" => Operator::FillColorExtended,
+            b"G" => Operator::StrokeGray,
+            b"g" => Operator::FillGray,
+            b"RG" => Operator::StrokeRgb,
+            b"rg" => Operator::FillRgb,
+            b"K" => Operator::StrokeCmyk,
+            b"k" => Operator::FillCmyk,
+            b"sh" => Operator::Shading,
+            b"BI" => Operator::BeginInlineImage,
+            b"ID" => Operator::InlineImageData,
+            b"EI" => Operator::EndInlineImage,
+            b"Do" => Operator::PaintXObject,
+            b"MP" => Operator::MarkedContentPoint,
+            b"DP" => Operator::MarkedContentPointProperties,
+            b"BMC" => Operator::BeginMarkedContent,
+            b"BDC" => Operator::BeginMarkedContentProperties,
+            b"EMC" => Operator::EndMarkedContent,
+            b"BX" => Operator::BeginCompatibility,
+            b"EX" => Operator::EndCompatibility,
+            _ => Operator::Other(bytes.to_vec()),
+        }
+    }
+
+    /// Returns the byte representation of the Operator.
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            Operator::LineWidth => b"w",
+            Operator::LineCap => b"J",
+            Operator::LineJoin => b"j",
+            Operator::MiterLimit => b"M",
+            Operator::DashPattern => b"d",
+            Operator::RenderingIntent => b"ri",
+            Operator::Flatness => b"i",
+            Operator::GraphicsStateParameters => b"gs",
+            Operator::SaveState => b"q",
+            Operator::RestoreState => b"Q",
+            Operator::ConcatMatrix => b"cm",
+            Operator::MoveTo => b"m",
+            Operator::LineTo => b"l",
+            Operator::CurveTo => b"c",
+            Operator::CurveToInitial => b"v",
+            Operator::CurveToFinal => b"y",
+            Operator::ClosePath => b"h",
+            Operator::Rectangle => b"re",
+            Operator::Stroke => b"S",
+            Operator::CloseStroke => b"s",
+            Operator::Fill => b"f",
+            Operator::FillObsolete => b"F",
+            Operator::FillEvenOdd => b"f*",
+            Operator::FillStroke => b"B",
+            Operator::FillStrokeEvenOdd => b"B*",
+            Operator::CloseFillStroke => b"b",
+            Operator::CloseFillStrokeEvenOdd => b"b*",
+            Operator::EndPath => b"n",
+            Operator::Clip => b"W",
+            Operator::ClipEvenOdd => b"W*",
+            Operator::BeginText => b"BT",
+            Operator::EndText => b"ET",
+            Operator::CharacterSpacing => b"Tc",
+            Operator::WordSpacing => b"Tw",
+            Operator::HorizontalScaling => b"Tz",
+            Operator::Leading => b"TL",
+            Operator::Font => b"Tf",
+            Operator::RenderingMode => b"Tr",
+            Operator::Rise => b"Ts",
+            Operator::MoveText => b"Td",
+            Operator::MoveTextSetLeading => b"TD",
+            Operator::TextMatrix => b"Tm",
+            Operator::NextLine => b"T*",
+            Operator::ShowText => b"Tj",
+            Operator::ShowTextArray => b"TJ",
+            Operator::NextLineShowText => b"'",
+            Operator::NextLineShowTextSpacing => b"\"",
+            Operator::GlyphWidth => b"d0",
+            Operator::GlyphWidthBoundingBox => b"d1",
+            Operator::StrokeColorSpace => b"CS",
+            Operator::FillColorSpace => b"cs",
+            Operator::StrokeColor => b"SC",
+            Operator::StrokeColorExtended => b"SCN",
+            Operator::FillColor => b"sc",
+            Operator::FillColorExtended => b"scn",
+            Operator::StrokeGray => b"G",
+            Operator::FillGray => b"g",
+            Operator::StrokeRgb => b"RG",
+            Operator::FillRgb => b"rg",
+            Operator::StrokeCmyk => b"K",
+            Operator::FillCmyk => b"k",
+            Operator::Shading => b"sh",
+            Operator::BeginInlineImage => b"BI",
+            Operator::InlineImageData => b"ID",
+            Operator::EndInlineImage => b"EI",
+            Operator::PaintXObject => b"Do",
+            Operator::MarkedContentPoint => b"MP",
+            Operator::MarkedContentPointProperties => b"DP",
+            Operator::BeginMarkedContent => b"BMC",
+            Operator::BeginMarkedContentProperties => b"BDC",
+            Operator::EndMarkedContent => b"EMC",
+            Operator::BeginCompatibility => b"BX",
+            Operator::EndCompatibility => b"EX",
+            Operator::Other(bytes) => bytes,
+        }
+    }
+
+    /// Returns `true` if the operator shows text (`Tj`, `TJ`, `'` and `"`).
+    pub fn shows_text(&self) -> bool {
+        matches!(self, Operator::ShowText | Operator::ShowTextArray | Operator::NextLineShowText | Operator::NextLineShowTextSpacing)
+    }
+
+    /// Returns `true` if the operator constructs a path (`m`, `l`, `c`, `v`, `y`, `h` and `re`).
+    pub fn constructs_path(&self) -> bool {
+        matches!(
+            self,
+            Operator::MoveTo | Operator::LineTo | Operator::CurveTo | Operator::CurveToInitial
+                | Operator::CurveToFinal | Operator::ClosePath | Operator::Rectangle
+        )
+    }
+
+    /// Returns `true` if the operator paints or ends a path (`S`, `f`, `B`, `n` and others).
+    pub fn paints_path(&self) -> bool {
+        matches!(
+            self,
+            Operator::Stroke | Operator::CloseStroke | Operator::Fill | Operator::FillObsolete
+                | Operator::FillEvenOdd | Operator::FillStroke | Operator::FillStrokeEvenOdd
+                | Operator::CloseFillStroke | Operator::CloseFillStrokeEvenOdd | Operator::EndPath
+        )
+    }
+
+    /// Returns `true` if the operator strokes the path.
+    pub fn strokes_path(&self) -> bool {
+        matches!(
+            self,
+            Operator::Stroke | Operator::CloseStroke | Operator::FillStroke | Operator::FillStrokeEvenOdd
+                | Operator::CloseFillStroke | Operator::CloseFillStrokeEvenOdd
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Operator;
+
+    #[test]
+    fn should_create_operator_from_bytes() {
+        let operators: &[&[u8]] = &[
+            b"w", b"J", b"j", b"M", b"d", b"ri", b"i", b"gs", b"q", b"Q", b"cm", b"m", b"l", b"c",
+            b"v", b"y", b"h", b"re", b"S", b"s", b"f", b"F", b"f*", b"B", b"B*", b"b", b"b*", b"n",
+            b"W", b"W*", b"BT", b"ET", b"Tc", b"Tw", b"Tz", b"TL", b"Tf", b"Tr", b"Ts", b"Td",
+            b"TD", b"Tm", b"T*", b"Tj", b"TJ", b"'", b"\"", b"d0", b"d1", b"CS", b"cs", b"SC",
+            b"SCN", b"sc", b"scn", b"G", b"g", b"RG", b"rg", b"K", b"k", b"sh", b"BI", b"ID",
+            b"EI", b"Do", b"MP", b"DP", b"BMC", b"BDC", b"EMC", b"BX", b"EX",
+        ];
+
+        for bytes in operators {
+            let operator = Operator::new(bytes);
+
+            assert!(!matches!(operator, Operator::Other(_)));
+            assert_eq!(operator.as_bytes(), *bytes);
+        }
+
+        assert_eq!(Operator::new(b"foo"), Operator::Other(b"foo".to_vec()));
+        assert_eq!(Operator::new(b"foo").as_bytes(), b"foo");
+    }
+}
\ No newline at end of file
@@ -0,0 +1,240 @@
+use crate::content::{InlineImage, Operation, Operator};
+use crate::object::{Dicionary, DicionaryEntry, Object};
+use crate::parser::ObjectParser;
+use crate::parser::search::find;
+use crate::token::{Keyword, TokenKind, Whitespace};
+
+/// Parses a decoded content stream into operations.
+///
+/// Operands are parsed as objects, and any keyword other than `true`,
+/// `false` and `null` is an operator. An inline image (`BI ... ID ... EI`)
+/// is a single `BI` operation holding the image.
+pub fn parse_operations(bytes: &[u8]) -> Result<Vec<Operation>, String> {
+
+    let mut parser = ObjectParser::new(bytes);
+    let mut operations = Vec::new();
+    let mut operands = Vec::new();
+
+    loop {
+        let mut lookahead = parser.clone();
+
+        let Some(token) = lookahead.next_token()? else {
+            break;
+        };
+
+        match token.kind() {
+            TokenKind::Keyword(Keyword::True | Keyword::False | Keyword::Null) => {
+                operands.push(parser.parse_object()?);
+            }
+            TokenKind::Keyword(keyword) => {
+                parser = lookahead;
+
+                let operator = Operator::new(keyword.as_bytes());
+
+                if operator == Operator::BeginInlineImage {
+                    operands.clear();
+                    operations.push(Operation::from_inline_image(parse_inline_image(bytes, &mut parser)?));
+                } else {
+                    operations.push(Operation::new(operator, std::mem::take(&mut operands)));
+                }
+            }
+            _ => operands.push(parser.parse_object()?),
+        }
+    }
+
+    Ok(operations)
+}
+
+/// Parses an inline image after its `BI` operator, up to its `EI` operator.
+///
+/// The data ends at the `/L` (or `/Length`) length when it is followed
+/// by `EI`, then at the length of the samples of an unfiltered image,
+/// and otherwise at the first `EI` surrounded by whitespace.
+fn parse_inline_image(bytes: &[u8], parser: &mut ObjectParser) -> Result<InlineImage, String> {
+
+    let mut entries = Vec::new();
+
+    loop {
+        let mut lookahead = parser.clone();
+
+        match lookahead.next_token()? {
+            Some(token) if token.as_bytes() == b"ID" => {
+                *parser = lookahead;
+                break;
+            }
+            Some(_) => {
+                let Object::Name(key) = parser.parse_object()? else {
+                    return Err(format!("Expected a name in inline image at offset {}", parser.position()));
+                };
+
+                let value = parser.parse_object()?;
+
+                entries.push(DicionaryEntry { key, value });
+            }
+            None => return Err("Unterminated inline image".to_string()),
+        }
+    }
+
+    let dicionary = Dicionary::new(entries);
+
+    // A single whitespace character separates `ID` from the data.
+    let mut start = parser.position();
+
+    if bytes.get(start).is_some_and(|byte| Whitespace::from_byte(*byte).is_some()) {
+        start += 1;
+    }
+
+    let length = entry(&dicionary, b"/L", b"/Length")
+        .and_then(Object::as_i64)
+        .and_then(|length| usize::try_from(length).ok());
+
+    let found = [length, unfiltered_length(&dicionary)].into_iter().flatten().find_map(|length| {
+        let data_end = start.checked_add(length)?;
+        Some((data_end, ends_with_ei(bytes, data_end)?))
+    });
+
+    let (data_end, end) = match found {
+        Some(found) => found,
+        None => {
+            let ei = find_ei(bytes, start).ok_or_else(|| format!("Unterminated inline image data at offset {}", start))?;
+            (ei - 1, ei + 2)
+        }
+    };
+
+    parser.seek(end);
+
+    Ok(InlineImage::new(dicionary, bytes[start..data_end].to_vec()))
+}
+
+/// Returns the value of an inline image entry, by its abbreviated or full key.
+fn entry<'a>(dicionary: &'a Dicionary, abbreviation: &[u8], key: &[u8]) -> Option<&'a Object> {
+    dicionary.get(abbreviation).or_else(|| dicionary.get(key))
+}
+
+/// Returns the length of the samples of an unfiltered inline image, or
+/// `None` if it is filtered or its color space is a named resource.
+fn unfiltered_length(dicionary: &Dicionary) -> Option<usize> {
+
+    if entry(dicionary, b"/F", b"/Filter").is_some_and(|filter| filter.as_array().is_none_or(|filters| !filters.as_objects().is_empty())) {
+        return None;
+    }
+
+    let dimension = |abbreviation: &[u8], key: &[u8]| entry(dicionary, abbreviation, key)
+        .and_then(Object::as_i64)
+        .and_then(|value| usize::try_from(value).ok());
+
+    let mask = matches!(entry(dicionary, b"/IM", b"/ImageMask"), Some(Object::Boolean(mask)) if mask.as_bool());
+
+    let (components, bits) = if mask {
+        (1, 1)
+    } else {
+        let color_space = entry(dicionary, b"/CS", b"/ColorSpace")?;
+        let family = color_space.as_array().and_then(|array| array.as_objects().first()).unwrap_or(color_space);
+
+        let components = match family.as_bytes() {
+            b"/G" | b"/DeviceGray" | b"/I" | b"/Indexed" => 1,
+            b"/RGB" | b"/DeviceRGB" => 3,
+            b"/CMYK" | b"/DeviceCMYK" => 4,
+            _ => return None,
+        };
+
+        (components, dimension(b"/BPC", b"/BitsPerComponent")?)
+    };
+
+    let row = dimension(b"/W", b"/Width")?.checked_mul(components)?.checked_mul(bits)?.div_ceil(8);
+
+    row.checked_mul(dimension(b"/H", b"/Height")?)
+}
+
+/// Returns the end of an `EI` operator following the position after optional whitespace.
+fn ends_with_ei(bytes: &[u8], mut position: usize) -> Option<usize> {
+
+    while bytes.get(position).is_some_and(|byte| Whitespace::from_byte(*byte).is_some()) {
+        position += 1;
+    }
+
+    (bytes.get(position..position + 2) == Some(b"EI") && is_operator_end(bytes, position + 2)).then_some(position + 2)
+}
+
+/// Returns the position of the first `EI` operator preceded by whitespace after the position.
+fn find_ei(bytes: &[u8], start: usize) -> Option<usize> {
+
+    let mut position = start;
+
+    while let Some(found) = find(&bytes[position..], b"EI") {
+
+        let ei = position + found;
+        position = ei + 2;
+
+        let preceded = ei > start && Whitespace::from_byte(bytes[ei - 1]).is_some();
+
+        if preceded && is_operator_end(bytes, ei + 2) {
+            return Some(ei);
+        }
+    }
+
+    None
+}
+
+/// Returns `true` if an operator can end at the position.
+fn is_operator_end(bytes: &[u8], position: usize) -> bool {
+
+    bytes.get(position).is_none_or(|byte| Whitespace::from_byte(*byte).is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_operations;
+    use crate::content::Operator;
+
+    #[test]
+    fn should_parse_operations() {
+        let content = b"q 1 0 0 1 10 20 cm\nBT /F1 12 Tf [(A) -120 (B)] TJ ET Q";
+        let operations = parse_operations(content).unwrap();
+
+        let operators = operations.iter().map(|operation| operation.operator().clone()).collect::<Vec<_>>();
+        assert_eq!(operators, vec![
+            Operator::SaveState, Operator::ConcatMatrix, Operator::BeginText, Operator::Font,
+            Operator::ShowTextArray, Operator::EndText, Operator::RestoreState,
+        ]);
+        assert_eq!(operations[1].number_operands(6), Some(vec![1.0, 0.0, 0.0, 1.0, 10.0, 20.0]));
+        assert_eq!(operations[4].as_bytes(), b"[(A) -120 (B)] TJ");
+    }
+
+    #[test]
+    fn should_parse_inline_image_data() {
+        let content = b"q BI /W 2 /H 1 /BPC 8 /CS /G /L 2 ID \xffEI EI Q BI /W 1 /H 1 ID\nEI\nEI Q";
+        let operations = parse_operations(content).unwrap();
+
+        let operators = operations.iter().map(|operation| operation.operator().as_bytes()).collect::<Vec<_>>();
+        assert_eq!(operators, vec![b"q".as_slice(), b"BI", b"Q", b"BI", b"Q"]);
+
+        let image = operations[1].inline_image().unwrap();
+        assert_eq!(image.to_stream().dicionary().get(b"/Width").unwrap().as_i64(), Some(2));
+        assert_eq!(image.data(), b"\xffEI");
+        assert_eq!(operations[3].inline_image().unwrap().data(), b"EI");
+        assert!(parse_operations(b"BI /W 1 ID abc").is_err());
+    }
+
+    #[test]
+    fn should_parse_inline_image_data_of_computed_length() {
+        let operations = parse_operations(b"BI /W 6 /H 1 /BPC 8 /CS /G ID A\nEI B\nEI Q").unwrap();
+
+        assert_eq!(operations.len(), 2);
+        assert_eq!(operations[0].inline_image().unwrap().data(), b"A\nEI B");
+        assert_eq!(operations[1].operator(), &Operator::RestoreState);
+
+        let operations = parse_operations(b"BI /Width 8 /Height 3 /ImageMask true ID \nEI\nEI Q").unwrap();
+        assert_eq!(operations[0].inline_image().unwrap().data(), b"\nEI");
+    }
+
+    #[test]
+    fn should_serialize_operations() {
+        let content = b"q BI /W 1 /H 1 /L 3 ID a\nb\nEI 0 0 10 10 re f Q";
+        let operations = parse_operations(content).unwrap();
+        let serialized = operations.iter().flat_map(|operation| [operation.as_bytes(), b"\n"].concat()).collect::<Vec<_>>();
+
+        assert_eq!(serialized, b"q\nBI /W 1 /H 1 /L 3 ID a\nb\nEI\n0 0 10 10 re\nf\nQ\n");
+        assert_eq!(parse_operations(&serialized).unwrap(), operations);
+    }
+}
\ No newline at end of file
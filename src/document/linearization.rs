@@ -0,0 +1,292 @@
+use crate::document::Document;
+use crate::object::{Object, ObjectId};
+use crate::structure::HintTables;
+
+/// Size of the start of a file that holds the linearization parameter dictionary.
+const LINEARIZATION_SEARCH_LENGTH: usize = 1024;
+
+/// The linearization parameters of a file ("Fast Web View").
+///
+/// A linearized file stays valid only as long as it is not changed:
+/// an incremental update appends objects the parameters and hint
+/// tables know nothing about.
+///
+/// ```rs
+/// if let Some(linearization) = Linearization::read(&document)? {
+///     for problem in linearization.check(&document) {
+///         println!("{}", problem);
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Linearization {
+    /// Length of the file (`/L`).
+    length: usize,
+    /// Offset and length of the primary hint stream, and of the overflow one if any (`/H`).
+    hint_streams: Vec<(usize, usize)>,
+    /// Object number of the first page (`/O`).
+    first_page: u32,
+    /// Offset of the end of the first page (`/E`).
+    first_page_end: usize,
+    /// Number of pages (`/N`).
+    page_count: usize,
+    /// Offset of the main cross-reference table (`/T`).
+    main_xref: usize,
+}
+
+impl Linearization {
+
+    /// Reads the linearization parameter dictionary of a document's file.
+    ///
+    /// Returns `None` if the first object of the file is not a
+    /// linearization parameter dictionary.
+    pub fn read(document: &Document) -> Result<Option<Self>, String> {
+
+        let xref = document.xref();
+
+        let Some((id, offset)) = xref.index().iter().min_by_key(|(_, offset)| **offset) else {
+            return Ok(None);
+        };
+
+        if offset.saturating_sub(xref.base()) > LINEARIZATION_SEARCH_LENGTH {
+            return Ok(None);
+        }
+
+        let object = xref.read_object(document.source(), *id)?;
+
+        let Some(dicionary) = object.as_dicionary().filter(|dicionary| dicionary.contains_key(b"/Linearized")) else {
+            return Ok(None);
+        };
+
+        let integer = |key: &[u8]| dicionary.get(key)
+            .and_then(Object::as_i64)
+            .and_then(|value| usize::try_from(value).ok())
+            .ok_or_else(|| format!("Invalid {} entry in the linearization parameter dictionary", String::from_utf8_lossy(key)));
+
+        let hints = dicionary.get(b"/H")
+            .and_then(Object::as_array)
+            .map(|array| array.as_objects().iter().map(|object| object.as_i64().and_then(|value| usize::try_from(value).ok())).collect::<Option<Vec<_>>>())
+            .and_then(|hints| hints.filter(|hints| hints.len() == 2 || hints.len() == 4))
+            .ok_or_else(|| "Invalid /H entry in the linearization parameter dictionary".to_string())?;
+
+        Ok(Some(Self {
+            length: integer(b"/L")?,
+            hint_streams: hints.chunks(2).map(|pair| (pair[0], pair[1])).collect(),
+            first_page: u32::try_from(integer(b"/O")?).map_err(|_| "Invalid /O entry in the linearization parameter dictionary".to_string())?,
+            first_page_end: integer(b"/E")?,
+            page_count: integer(b"/N")?,
+            main_xref: integer(b"/T")?,
+        }))
+    }
+
+    /// Returns the length of the file when it was linearized.
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    /// Returns the offset and length of the primary hint stream, and of
+    /// the overflow hint stream if any.
+    pub fn hint_streams(&self) -> &[(usize, usize)] {
+        &self.hint_streams
+    }
+
+    /// Returns the object number of the first page.
+    pub fn first_page(&self) -> u32 {
+        self.first_page
+    }
+
+    /// Returns the offset of the end of the first page.
+    pub fn first_page_end(&self) -> usize {
+        self.first_page_end
+    }
+
+    /// Returns the number of pages.
+    pub fn page_count(&self) -> usize {
+        self.page_count
+    }
+
+    /// Returns the offset of the main cross-reference table.
+    pub fn main_xref(&self) -> usize {
+        self.main_xref
+    }
+
+    /// Checks that the parameters and the hint tables still describe the
+    /// document's file, and returns what does not match; nothing if the
+    /// linearization data is valid.
+    pub fn check(&self, document: &Document) -> Vec<String> {
+
+        let source = document.source();
+        let base = document.xref().base();
+        let length = source.len() - base;
+
+        let mut problems = Vec::new();
+
+        if self.length != length {
+            problems.push(format!("The file is {} bytes long, but was {} bytes when linearized", length, self.length));
+        }
+
+        if self.page_count != document.pages().len() {
+            problems.push(format!("The document has {} pages, but had {} when linearized", document.pages().len(), self.page_count));
+        }
+
+        if document.page(0).map(|page| page.id().number()) != Some(self.first_page) {
+            problems.push(format!("The first page is not object {}", self.first_page));
+        }
+
+        if self.first_page_end > length {
+            problems.push(format!("The end of the first page, at offset {}, is beyond the end of the file", self.first_page_end));
+        }
+
+        if !self.has_main_xref(document) {
+            problems.push(format!("No main cross-reference section at offset {}", self.main_xref));
+        }
+
+        if let Err(problem) = self.check_hint_tables(document) {
+            problems.push(problem);
+        }
+
+        problems
+    }
+
+    /// Returns `true` if the main cross-reference table starts at `/T`:
+    /// the white-space before its first entry, or the start of a
+    /// cross-reference stream.
+    fn has_main_xref(&self, document: &Document) -> bool {
+
+        let source = document.source();
+        let position = document.xref().base() + self.main_xref;
+        let bytes = source.slice(position.min(source.len())..(position + 32).min(source.len()));
+
+        if let [first, rest @ ..] = bytes && first.is_ascii_whitespace() {
+
+            let entry = rest.trim_ascii_start();
+
+            if entry.len() >= 18
+                && entry[..10].iter().all(u8::is_ascii_digit)
+                && entry[10] == b' '
+                && entry[11..16].iter().all(u8::is_ascii_digit)
+                && entry[16..18] == *b" f"
+            {
+                return true;
+            }
+        }
+
+        self.object_at(document, self.main_xref)
+            .and_then(|id| document.xref().read_object(source, id).ok())
+            .and_then(|object| object.as_stream().and_then(|stream| stream.dicionary().get(b"/Type")).map(|kind| kind.as_bytes() == b"/XRef"))
+            .unwrap_or(false)
+    }
+
+    /// Checks the hint tables against the offsets of the pages.
+    fn check_hint_tables(&self, document: &Document) -> Result<(), String> {
+
+        let mut data = Vec::new();
+        let mut shared_offset = None;
+
+        for (offset, _) in &self.hint_streams {
+
+            let object = self.object_at(document, *offset)
+                .and_then(|id| document.xref().read_object(document.source(), id).ok())
+                .ok_or_else(|| format!("No hint stream at offset {}", offset))?;
+
+            let stream = object.as_stream().ok_or_else(|| format!("No hint stream at offset {}", offset))?;
+
+            shared_offset = shared_offset.or_else(|| stream.dicionary().get(b"/S").and_then(Object::as_i64));
+            data.extend(stream.decode()?);
+        }
+
+        let shared_offset = shared_offset
+            .and_then(|offset| usize::try_from(offset).ok())
+            .ok_or_else(|| "The primary hint stream has no /S entry".to_string())?;
+
+        let tables = HintTables::parse(&data, self.page_count.min(document.pages().len()), shared_offset)?;
+
+        let mut start = tables.first_page_offset as usize;
+
+        for (index, (page, hint)) in document.pages().iter().zip(&tables.pages).enumerate() {
+
+            let offset = document.xref().offset(page.id()).map(|offset| self.hinted_offset(offset - document.xref().base()));
+
+            if !offset.is_some_and(|offset| offset >= start && offset < start + hint.length as usize) {
+                return Err(format!("The hint tables do not give the location of page {}", index + 1));
+            }
+
+            start += hint.length as usize;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the number of the object at the given offset from the header.
+    fn object_at(&self, document: &Document, offset: usize) -> Option<ObjectId> {
+
+        let position = document.xref().base() + offset;
+
+        document.xref().index().iter().find(|(_, offset)| **offset == position).map(|(id, _)| *id)
+    }
+
+    /// Returns an offset as hint tables give it: as if the hint streams
+    /// were not in the file.
+    fn hinted_offset(&self, offset: usize) -> usize {
+
+        let hints = self.hint_streams.iter()
+            .filter(|(start, _)| *start < offset)
+            .map(|(_, length)| *length)
+            .sum::<usize>();
+
+        offset.saturating_sub(hints)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::Linearization;
+    use crate::byte_source::ByteSource;
+    use crate::byte_source::memory::MemoryByteSource;
+    use crate::document::Document;
+    use crate::object::{Name, Object};
+    use crate::writer::linearize::tests::{linearized, pages_pdf};
+    use crate::writer::{WriteOptions, Writer, XrefStyle};
+
+    #[test]
+    fn should_not_find_linearization_in_plain_files() {
+        assert_eq!(Linearization::read(&Document::read(&pages_pdf()).unwrap()).unwrap(), None);
+    }
+
+    #[test]
+    fn should_report_changes_made_after_linearization() {
+        let mut document = linearized(&pages_pdf(), WriteOptions::new());
+
+        let mut catalog = document.catalog().clone();
+        catalog.insert(Name::new(b"/PageMode").unwrap(), Object::parse(b"/UseOutlines").unwrap());
+        let root = document.trailer().get(b"/Root").and_then(Object::as_reference).unwrap();
+        document.set_object(root, Object::Dicionary(catalog));
+
+        let mut writer = Writer::new(Vec::new());
+        writer.update_document(&document, XrefStyle::of(&document)).unwrap();
+
+        let source: Arc<dyn ByteSource> = Arc::new(MemoryByteSource::new(writer.into_inner()));
+        let updated = Document::read(&source).unwrap();
+
+        let linearization = Linearization::read(&updated).unwrap().unwrap();
+        let problems = linearization.check(&updated);
+
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains(&format!("was {} bytes when linearized", document.source().len())));
+    }
+
+    #[test]
+    fn should_report_hint_tables_that_do_not_match() {
+        let document = linearized(&pages_pdf(), WriteOptions::new());
+        let linearization = Linearization::read(&document).unwrap().unwrap();
+
+        let (offset, length) = linearization.hint_streams()[0];
+        let moved = Linearization { hint_streams: vec![(offset, length + 1000)], ..linearization.clone() };
+        assert_eq!(moved.check(&document), vec!["The hint tables do not give the location of page 1".to_string()]);
+
+        let shifted = Linearization { main_xref: linearization.main_xref() + 1, first_page: 1, ..linearization };
+        assert_eq!(shifted.check(&document).len(), 2);
+    }
+}
\ No newline at end of file
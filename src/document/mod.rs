@@ -0,0 +1,606 @@
+//! This module contains the document model built on the cross-reference table.
+mod linearization;
+mod page;
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+
+use crate::byte_source::ByteSource;
+use crate::geometry::Rectangle;
+use crate::object::{Array, Dicionary, Name, Null, Object, ObjectId, Reference, Stream};
+use crate::structure::{ObjectStream, Version};
+use crate::structure::xref::{RepairReport, Xref, XrefEntry};
+
+pub use crate::document::linearization::Linearization;
+pub use crate::document::page::Page;
+
+/// Maximum depth of the page tree.
+const MAX_PAGE_TREE_DEPTH: usize = 256;
+
+/// Maximum number of references followed when resolving an object.
+const MAX_REFERENCE_CHAIN: usize = 32;
+
+
+/// A PDF document: the catalog and the pages of a file.
+///
+/// ```rs
+/// let document = Document::read(&source)?;
+/// for page in document.pages() {
+///     println!("{:?}", page.size());
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Document {
+    /// The byte source of the file.
+    source: Arc<dyn ByteSource>,
+    /// The cross-reference table of the file.
+    xref: Xref,
+    /// Report of the repair of a damaged cross-reference table.
+    repair_report: Option<RepairReport>,
+    /// The document catalog (`/Root`).
+    catalog: Dicionary,
+    /// The pages in document order.
+    pages: Vec<Page>,
+    /// Objects added or replaced since the file was read.
+    changes: BTreeMap<ObjectId, Object>,
+    /// Object streams decoded so far, by object number.
+    object_streams: Arc<Mutex<HashMap<u32, Arc<ObjectStream>>>>,
+}
+
+impl Document {
+
+    /// Reads the document of the given source.
+    ///
+    /// A damaged cross-reference table is rebuilt; see `repair_report`.
+    pub fn read(source: &Arc<dyn ByteSource>) -> Result<Self, String> {
+
+        let (xref, repair_report) = Xref::read_or_repair(source)?;
+
+        Self::new(Arc::clone(source), xref, repair_report)
+    }
+
+    /// Creates a new `Document` from a source and its cross-reference table.
+    ///
+    /// The catalog is resolved and the page tree is walked.
+    pub fn new(source: Arc<dyn ByteSource>, xref: Xref, repair_report: Option<RepairReport>) -> Result<Self, String> {
+
+        let mut document = Self {
+            source,
+            xref,
+            repair_report,
+            catalog: Dicionary::new(vec![]),
+            pages: Vec::new(),
+            changes: BTreeMap::new(),
+            object_streams: Arc::new(Mutex::new(HashMap::new())),
+        };
+
+        let root = document.xref.trailer().get(b"/Root")
+            .cloned()
+            .ok_or_else(|| "The trailer has no /Root entry".to_string())?;
+
+        document.catalog = document.resolve(&root)?
+            .as_dicionary()
+            .cloned()
+            .ok_or_else(|| "The document catalog must be a dictionary".to_string())?;
+
+        let pages = document.catalog.get(b"/Pages")
+            .and_then(Object::as_reference)
+            .ok_or_else(|| "The document catalog has no /Pages reference".to_string())?;
+
+        let mut collector = PageCollector { document: &document, pages: Vec::new(), visited: HashSet::new() };
+        collector.collect(pages, Inherited::default(), 0)?;
+
+        document.pages = collector.pages;
+
+        Ok(document)
+    }
+
+    /// Returns the byte source of the file.
+    pub fn source(&self) -> &Arc<dyn ByteSource> {
+        &self.source
+    }
+
+    /// Returns the cross-reference table of the file.
+    pub fn xref(&self) -> &Xref {
+        &self.xref
+    }
+
+    /// Returns the report of the repair of a damaged cross-reference table, if any.
+    pub fn repair_report(&self) -> Option<&RepairReport> {
+        self.repair_report.as_ref()
+    }
+
+    /// Returns the trailer dictionary.
+    pub fn trailer(&self) -> &Dicionary {
+        self.xref.trailer()
+    }
+
+    /// Returns the PDF version of the document.
+    ///
+    /// The `/Version` of the catalog overrides the header when later.
+    /// A missing or invalid header gives version 1.7.
+    pub fn version(&self) -> Version {
+
+        let base = self.xref.base();
+        let header = self.source.slice(base.min(self.source.len())..(base + 8).min(self.source.len()));
+
+        let version = header.strip_prefix(b"%PDF-")
+            .and_then(Version::parse)
+            .unwrap_or_else(|| Version::new(1, 7));
+
+        match self.catalog.get(b"/Version").and_then(Object::as_name).and_then(|name| Version::parse(&name.as_bytes()[1..])) {
+            Some(catalog) if catalog.is_later_than(&version) => catalog,
+            _ => version,
+        }
+    }
+
+    /// Returns the document catalog.
+    pub fn catalog(&self) -> &Dicionary {
+        &self.catalog
+    }
+
+    /// Returns the pages in document order.
+    pub fn pages(&self) -> &[Page] {
+        &self.pages
+    }
+
+    /// Returns the page at the given zero-based index.
+    pub fn page(&self, index: usize) -> Option<&Page> {
+        self.pages.get(index)
+    }
+
+    /// Returns the objects added or replaced since the file was read.
+    pub fn changes(&self) -> &BTreeMap<ObjectId, Object> {
+        &self.changes
+    }
+
+    /// Replaces the given object.
+    pub fn set_object(&mut self, id: ObjectId, object: Object) {
+        self.changes.insert(id, object);
+    }
+
+    /// Adds a new object and returns its identifier.
+    pub fn add_object(&mut self, object: Object) -> ObjectId {
+
+        let id = ObjectId::new(self.next_object_number(), 0);
+        self.changes.insert(id, object);
+
+        id
+    }
+
+    /// Returns the number of the next object added.
+    pub(crate) fn next_object_number(&self) -> u32 {
+
+        self.xref.entries().keys().next_back().copied()
+            .max(self.changes.keys().next_back().map(|id| id.number()))
+            .unwrap_or(0) + 1
+    }
+
+    /// Replaces the dictionary of the page at the given index.
+    ///
+    /// The page's `/Resources` are taken from the new dictionary when
+    /// it sets them; other inherited attributes are kept.
+    pub fn set_page(&mut self, index: usize, dicionary: Dicionary) -> Result<(), String> {
+
+        let page = self.pages.get(index)
+            .ok_or_else(|| format!("Page index {} is out of range", index))?;
+
+        let resources = match dicionary.get(b"/Resources") {
+            Some(resources) => self.resolve(resources)?.as_dicionary().cloned().unwrap_or_else(|| Dicionary::new(vec![])),
+            None => page.resources().clone(),
+        };
+
+        let page = Page::new(page.id(), dicionary.clone(), resources, page.media_box(), page.crop_box(), page.rotate());
+
+        self.set_object(page.id(), Object::Dicionary(dicionary));
+        self.pages[index] = page;
+
+        Ok(())
+    }
+
+    /// Returns the decoded content of the page at the given index.
+    ///
+    /// The streams of a `/Contents` array are joined with a newline.
+    pub fn page_content(&self, index: usize) -> Result<Vec<u8>, String> {
+
+        let page = self.pages.get(index)
+            .ok_or_else(|| format!("Page index {} is out of range", index))?;
+
+        let contents = match page.dicionary().get(b"/Contents") {
+            Some(contents) => self.resolve(contents)?,
+            None => return Ok(Vec::new()),
+        };
+
+        let streams = match &contents {
+            Object::Array(array) => array.as_objects().to_vec(),
+            _ => vec![contents],
+        };
+
+        let mut content = Vec::new();
+
+        for stream in streams {
+            if let Object::Stream(stream) = self.resolve(&stream)? {
+                if !content.is_empty() {
+                    content.push(b'\n');
+                }
+
+                content.extend_from_slice(&stream.decode()?);
+            }
+        }
+
+        Ok(content)
+    }
+
+    /// Reads the given object.
+    ///
+    /// Decoded object streams are cached, so that reading many
+    /// compressed objects decodes each stream once. The indirect
+    /// `/Filter` and `/DecodeParms` of a stream are resolved, so that
+    /// the stream can be decoded on its own.
+    pub fn get(&self, id: ObjectId) -> Result<Object, String> {
+
+        if let Some(object) = self.changes.get(&id) {
+            return Ok(object.clone());
+        }
+
+        let Some(XrefEntry::Compressed { stream, index }) = self.xref.entry(id.number()) else {
+            return self.resolve_filters(self.xref.read_object(&self.source, id)?);
+        };
+
+        if id.generation() != 0 {
+            return Err(format!("Object {} {} is not in the cross-reference table", id.number(), id.generation()));
+        }
+
+        self.resolve_filters(self.object_stream(*stream)?.get(id.number(), *index as usize)?)
+    }
+
+    /// Resolves an object, following indirect references.
+    ///
+    /// A reference to an object that is not in use resolves to `null`.
+    pub fn resolve(&self, object: &Object) -> Result<Object, String> {
+
+        let mut object = object.clone();
+
+        for _ in 0..MAX_REFERENCE_CHAIN {
+
+            let Some(id) = object.as_reference() else {
+                return Ok(object);
+            };
+
+            if let Some(changed) = self.changes.get(&id) {
+                object = changed.clone();
+                continue;
+            }
+
+            match self.xref.entry(id.number()) {
+                Some(entry) if entry.is_in_use() && entry.generation() == id.generation() => {
+                    object = self.get(id)?;
+                }
+                _ => return Ok(Object::Null(Null::new())),
+            }
+        }
+
+        Err("Too many nested references".to_string())
+    }
+
+    /// Resolves the `/Filter` and `/DecodeParms` entries of a stream,
+    /// and the elements of their arrays.
+    fn resolve_filters(&self, object: Object) -> Result<Object, String> {
+
+        let Object::Stream(stream) = object else {
+            return Ok(object);
+        };
+
+        let mut dicionary = stream.dicionary().clone();
+
+        for key in [b"/Filter".as_slice(), b"/DecodeParms"] {
+
+            let Some(value) = dicionary.get(key).filter(|value| value.as_reference().is_some() || value.as_array().is_some()) else {
+                continue;
+            };
+
+            let value = match self.resolve(value)? {
+                Object::Array(array) => Object::Array(Array::new(
+                    array.as_objects().iter().map(|element| self.resolve(element)).collect::<Result<_, _>>()?,
+                )),
+                value => value,
+            };
+
+            dicionary.insert(Name::new(key).unwrap(), value);
+        }
+
+        Ok(Object::Stream(Stream::with_data(dicionary, stream.data().clone())))
+    }
+
+    /// Returns the decoded object stream with the given object number.
+    fn object_stream(&self, number: u32) -> Result<Arc<ObjectStream>, String> {
+
+        if let Some(object_stream) = self.object_streams.lock().unwrap().get(&number) {
+            return Ok(Arc::clone(object_stream));
+        }
+
+        let object_stream = Arc::new(self.xref.read_object_stream(&self.source, number)?);
+
+        self.object_streams.lock().unwrap().insert(number, Arc::clone(&object_stream));
+
+        Ok(object_stream)
+    }
+}
+
+/// Page attributes inherited from the ancestors of a page tree node.
+#[derive(Debug, Clone, Default)]
+struct Inherited {
+    /// The inherited `/Resources`.
+    resources: Option<Object>,
+    /// The inherited `/MediaBox`.
+    media_box: Option<Object>,
+    /// The inherited `/CropBox`.
+    crop_box: Option<Object>,
+    /// The inherited `/Rotate`.
+    rotate: Option<Object>,
+}
+
+impl Inherited {
+
+    /// Returns the attributes inherited by the children of the given node.
+    fn apply(&self, node: &Dicionary) -> Self {
+        Self {
+            resources: node.get(b"/Resources").cloned().or_else(|| self.resources.clone()),
+            media_box: node.get(b"/MediaBox").cloned().or_else(|| self.media_box.clone()),
+            crop_box: node.get(b"/CropBox").cloned().or_else(|| self.crop_box.clone()),
+            rotate: node.get(b"/Rotate").cloned().or_else(|| self.rotate.clone()),
+        }
+    }
+}
+
+/// Walks the page tree and collects its pages.
+struct PageCollector<'a> {
+    /// The document whose objects are read.
+    document: &'a Document,
+    /// The pages collected so far.
+    pages: Vec<Page>,
+    /// The page tree nodes visited so far, to break cycles.
+    visited: HashSet<ObjectId>,
+}
+
+impl PageCollector<'_> {
+
+    /// Collects the pages below the given page tree node.
+    fn collect(&mut self, id: ObjectId, inherited: Inherited, depth: usize) -> Result<(), String> {
+
+        if depth > MAX_PAGE_TREE_DEPTH {
+            return Err("The page tree is too deep".to_string());
+        }
+
+        // A node reached twice would duplicate pages or loop forever.
+        if !self.visited.insert(id) {
+            return Ok(());
+        }
+
+        let node = match self.document.resolve(&Object::Reference(Reference::new(id)))? {
+            Object::Dicionary(node) => node,
+            Object::Stream(stream) => stream.dicionary().clone(),
+            _ => return Ok(()),
+        };
+
+        let inherited = inherited.apply(&node);
+
+        let is_node = node.get(b"/Type").and_then(Object::as_name).map(|name| name.as_bytes()) == Some(b"/Pages")
+            || (node.get(b"/Type").is_none() && node.contains_key(b"/Kids"));
+
+        if !is_node {
+            self.pages.push(self.page(id, node, inherited)?);
+            return Ok(());
+        }
+
+        let kids = match node.get(b"/Kids") {
+            Some(kids) => self.document.resolve(kids)?,
+            None => return Ok(()),
+        };
+
+        for kid in kids.as_array().map(|kids| kids.as_objects()).unwrap_or_default() {
+            if let Some(kid) = kid.as_reference() {
+                self.collect(kid, inherited.clone(), depth + 1)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Creates a page from a leaf of the page tree.
+    fn page(&self, id: ObjectId, dicionary: Dicionary, inherited: Inherited) -> Result<Page, String> {
+
+        let resolve_rectangle = |object: &Option<Object>| -> Result<Option<Rectangle>, String> {
+            match object {
+                Some(object) => Ok(Rectangle::from_object(&self.document.resolve(object)?)),
+                None => Ok(None),
+            }
+        };
+
+        let media_box = resolve_rectangle(&inherited.media_box)?.unwrap_or_else(default_media_box);
+        let crop_box = resolve_rectangle(&inherited.crop_box)?.unwrap_or(media_box);
+
+        let resources = match &inherited.resources {
+            Some(resources) => self.document.resolve(resources)?.as_dicionary().cloned(),
+            None => None,
+        };
+
+        let rotate = match &inherited.rotate {
+            Some(rotate) => self.document.resolve(rotate)?.as_i64().unwrap_or(0),
+            None => 0,
+        };
+
+        Ok(Page::new(
+            id,
+            dicionary,
+            resources.unwrap_or_else(|| Dicionary::new(vec![])),
+            media_box,
+            crop_box,
+            rotate,
+        ))
+    }
+}
+
+/// Returns the media box used when no page tree node sets one (US Letter).
+fn default_media_box() -> Rectangle {
+    Rectangle::new(0.0, 0.0, 612.0, 792.0)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::io::Write;
+    use std::sync::Arc;
+
+    use flate2::Compression;
+    use flate2::write::ZlibEncoder;
+
+    use super::Document;
+    use crate::byte_source::ByteSource;
+    use crate::byte_source::memory::MemoryByteSource;
+    use crate::geometry::Rectangle;
+    use crate::object::{Dicionary, Object, ObjectId};
+    use crate::structure::Version;
+
+    /// Builds a PDF file from objects numbered from 1, with a classic
+    /// cross-reference table and object 1 as the catalog.
+    pub(crate) fn build_pdf(objects: &[&[u8]]) -> Arc<dyn ByteSource> {
+        let mut pdf = b"%PDF-1.7\n".to_vec();
+        let mut offsets = Vec::new();
+
+        for (index, object) in objects.iter().enumerate() {
+            offsets.push(pdf.len());
+            pdf.extend_from_slice(format!("{} 0 obj\n", index + 1).as_bytes());
+            pdf.extend_from_slice(object);
+            pdf.extend_from_slice(b"\nendobj\n");
+        }
+
+        let startxref = pdf.len();
+        pdf.extend_from_slice(format!("xref\n0 {}\n0000000000 65535 f\r\n", objects.len() + 1).as_bytes());
+
+        for offset in offsets {
+            pdf.extend_from_slice(format!("{:010} 00000 n\r\n", offset).as_bytes());
+        }
+
+        pdf.extend_from_slice(format!(
+            "trailer\n<</Size {} /Root 1 0 R>>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1, startxref
+        ).as_bytes());
+
+        Arc::new(MemoryByteSource::new(pdf))
+    }
+
+    #[test]
+    fn should_read_catalog_and_pages() {
+        let source: Arc<dyn ByteSource> = Arc::new(MemoryByteSource::new(include_bytes!("../../tests/pdf/test.pdf").to_vec()));
+        let document = Document::read(&source).unwrap();
+
+        assert_eq!(document.catalog().get(b"/Type").unwrap().as_bytes(), b"/Catalog");
+        assert_eq!(document.pages().len(), 1);
+
+        let page = document.page(0).unwrap();
+        assert_eq!(page.id(), ObjectId::new(4, 0));
+        assert_eq!(page.size(), (612.0, 396.0));
+        assert!(page.resources().get(b"/Font").is_some());
+        assert!(document.repair_report().is_none());
+    }
+
+    #[test]
+    fn should_read_version_from_header_and_catalog() {
+        let header = build_pdf(&[b"<</Type /Catalog /Pages 2 0 R /Version /1.4>>", b"<</Type /Pages /Kids [] /Count 0>>"]);
+        assert_eq!(Document::read(&header).unwrap().version(), Version::new(1, 7));
+
+        let catalog = build_pdf(&[b"<</Type /Catalog /Pages 2 0 R /Version /2.0>>", b"<</Type /Pages /Kids [] /Count 0>>"]);
+        assert_eq!(Document::read(&catalog).unwrap().version(), Version::new(2, 0));
+    }
+
+    #[test]
+    fn should_apply_inherited_attributes() {
+        let source = build_pdf(&[
+            b"<</Type /Catalog /Pages 2 0 R>>",
+            b"<</Type /Pages /Kids [3 0 R 5 0 R] /Count 3 /MediaBox [0 0 500 800] /Resources 6 0 R /Rotate 90>>",
+            b"<</Type /Pages /Parent 2 0 R /Kids [4 0 R 2 0 R] /Count 1 /CropBox [10 10 400 700]>>",
+            b"<</Type /Page /Parent 3 0 R>>",
+            b"<</Type /Page /Parent 2 0 R /MediaBox [0 0 200 300] /Rotate 0 /Resources <<>>>>",
+            b"<</Font <</F1 7 0 R>>>>",
+            b"<</Type /Font /Subtype /Type1 /BaseFont /Helvetica>>",
+        ]);
+        let document = Document::read(&source).unwrap();
+
+        // The loop back to the root node is ignored.
+        assert_eq!(document.pages().len(), 2);
+
+        let first = document.page(0).unwrap();
+        assert_eq!(first.media_box(), Rectangle::new(0.0, 0.0, 500.0, 800.0));
+        assert_eq!(first.crop_box(), Rectangle::new(10.0, 10.0, 400.0, 700.0));
+        assert_eq!(first.rotate(), 90);
+        assert_eq!(first.size(), (690.0, 390.0));
+        assert!(first.resources().get(b"/Font").is_some());
+
+        let second = document.page(1).unwrap();
+        assert_eq!(second.id(), ObjectId::new(5, 0));
+        assert_eq!(second.size(), (200.0, 300.0));
+        assert!(second.resources().is_empty());
+    }
+
+    #[test]
+    fn should_resolve_indirect_filters_of_streams() {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&[2, 1, 2, 2, 1, 1]).unwrap();
+        let data = encoder.finish().unwrap();
+
+        let mut content = format!("<</Length {} /Filter 5 0 R /DecodeParms [6 0 R]>>\nstream\n", data.len()).into_bytes();
+        content.extend_from_slice(&data);
+        content.extend_from_slice(b"\nendstream");
+
+        let source = build_pdf(&[
+            b"<</Type /Catalog /Pages 2 0 R>>",
+            b"<</Type /Pages /Kids [3 0 R] /Count 1>>",
+            b"<</Type /Page /Contents 4 0 R>>",
+            &content,
+            b"/FlateDecode",
+            b"<</Predictor 12 /Columns 2>>",
+        ]);
+        let document = Document::read(&source).unwrap();
+
+        assert_eq!(document.page_content(0).unwrap(), vec![1, 2, 2, 3]);
+    }
+
+    #[test]
+    fn should_add_and_replace_objects() {
+        let source = build_pdf(&[
+            b"<</Type /Catalog /Pages 2 0 R>>",
+            b"<</Type /Pages /Kids [3 0 R] /Count 1 /Resources <</Font <<>>>>>>",
+            b"<</Type /Page /Contents [4 0 R 4 0 R]>>",
+            b"<</Length 5>>\nstream\nBT ET\nendstream",
+        ]);
+        let mut document = Document::read(&source).unwrap();
+
+        assert_eq!(document.page_content(0).unwrap(), b"BT ET\nBT ET");
+
+        let id = document.add_object(Object::parse(b"<</Length 1>>\nstream\nq\nendstream").unwrap());
+        assert_eq!(id, ObjectId::new(5, 0));
+
+        let page = Object::parse(b"<</Type /Page /Contents 5 0 R /Resources <<>>>>").unwrap();
+        document.set_page(0, page.as_dicionary().unwrap().clone()).unwrap();
+
+        assert_eq!(document.page_content(0).unwrap(), b"q");
+        assert!(document.page(0).unwrap().resources().is_empty());
+        assert_eq!(document.get(ObjectId::new(3, 0)).unwrap(), page);
+        assert_eq!(document.changes().len(), 2);
+        assert!(document.set_page(1, Dicionary::new(vec![])).is_err());
+    }
+
+    #[test]
+    fn should_resolve_missing_objects_to_null() {
+        let source = build_pdf(&[
+            b"<</Type /Catalog /Pages 2 0 R /Missing 9 0 R>>",
+            b"<</Type /Pages /Kids [3 0 R] /Count 1>>",
+            b"<</Type /Page>>",
+        ]);
+        let document = Document::read(&source).unwrap();
+
+        let missing = document.resolve(document.catalog().get(b"/Missing").unwrap()).unwrap();
+        assert_eq!(missing.as_bytes(), b"null");
+        assert_eq!(document.page(0).unwrap().media_box(), Rectangle::new(0.0, 0.0, 612.0, 792.0));
+    }
+}
\ No newline at end of file
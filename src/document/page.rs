@@ -0,0 +1,115 @@
+use crate::geometry::Rectangle;
+use crate::object::{Dicionary, ObjectId};
+
+/// A page of a document with its inherited attributes applied.
+///
+/// `/Resources`, `/MediaBox`, `/CropBox` and `/Rotate` are taken from
+/// the nearest ancestor in the page tree when the page does not set them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Page {
+    /// Identifier of the page object.
+    id: ObjectId,
+    /// The page dictionary as stored in the file.
+    dicionary: Dicionary,
+    /// The resource dictionary, resolved.
+    resources: Dicionary,
+    /// The boundaries of the physical medium.
+    media_box: Rectangle,
+    /// The visible region, clipped to the media box.
+    crop_box: Rectangle,
+    /// The clockwise rotation in degrees: 0, 90, 180 or 270.
+    rotate: i64,
+}
+
+impl Page {
+
+    /// Creates a new `Page`.
+    ///
+    /// The crop box is clipped to the media box, and the rotation
+    /// is normalized to a multiple of 90 degrees in `0..360`.
+    pub fn new(
+        id: ObjectId,
+        dicionary: Dicionary,
+        resources: Dicionary,
+        media_box: Rectangle,
+        crop_box: Rectangle,
+        rotate: i64,
+    ) -> Self {
+
+        let crop_box = crop_box.intersection(&media_box).unwrap_or(media_box);
+
+        let rotate = match rotate.rem_euclid(360) {
+            rotate if rotate % 90 == 0 => rotate,
+            _ => 0,
+        };
+
+        Self { id, dicionary, resources, media_box, crop_box, rotate }
+    }
+
+    /// Returns the identifier of the page object.
+    pub fn id(&self) -> ObjectId {
+        self.id
+    }
+
+    /// Returns the page dictionary as stored in the file.
+    pub fn dicionary(&self) -> &Dicionary {
+        &self.dicionary
+    }
+
+    /// Returns the resource dictionary of the page.
+    pub fn resources(&self) -> &Dicionary {
+        &self.resources
+    }
+
+    /// Returns the media box of the page.
+    pub fn media_box(&self) -> Rectangle {
+        self.media_box
+    }
+
+    /// Returns the crop box of the page.
+    pub fn crop_box(&self) -> Rectangle {
+        self.crop_box
+    }
+
+    /// Returns the clockwise rotation of the page in degrees.
+    pub fn rotate(&self) -> i64 {
+        self.rotate
+    }
+
+    /// Returns the displayed width and height of the page.
+    ///
+    /// This is the size of the crop box, swapped for pages rotated
+    /// by 90 or 270 degrees.
+    pub fn size(&self) -> (f64, f64) {
+
+        let (width, height) = (self.crop_box.width(), self.crop_box.height());
+
+        match self.rotate {
+            90 | 270 => (height, width),
+            _ => (width, height),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Page;
+    use crate::geometry::Rectangle;
+    use crate::object::{Dicionary, ObjectId};
+
+    #[test]
+    fn should_clip_crop_box_and_normalize_rotation() {
+        let page = Page::new(
+            ObjectId::new(3, 0),
+            Dicionary::new(vec![]),
+            Dicionary::new(vec![]),
+            Rectangle::new(0.0, 0.0, 600.0, 400.0),
+            Rectangle::new(100.0, -50.0, 700.0, 300.0),
+            -90,
+        );
+
+        assert_eq!(page.crop_box(), Rectangle::new(100.0, 0.0, 600.0, 300.0));
+        assert_eq!(page.rotate(), 270);
+        assert_eq!(page.size(), (300.0, 500.0));
+    }
+}
\ No newline at end of file
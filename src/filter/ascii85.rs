@@ -0,0 +1,99 @@
+use crate::token::Whitespace;
+
+/// Decodes data encoded in base-85 (`/ASCII85Decode`).
+///
+/// Whitespace is ignored, `~>` ends the data, and `z` stands for four
+/// zero bytes. A final partial group gives one byte less than its digits.
+pub fn decode_ascii85(data: &[u8]) -> Result<Vec<u8>, String> {
+
+    let mut output = Vec::with_capacity(data.len() * 4 / 5);
+    let mut group = Vec::with_capacity(5);
+
+    for byte in data.iter().copied().filter(|byte| Whitespace::from_byte(*byte).is_none()) {
+        match byte {
+            b'~' => break,
+            b'z' if group.is_empty() => output.extend_from_slice(&[0; 4]),
+            b'!'..=b'u' => {
+                group.push(byte - b'!');
+
+                if group.len() == 5 {
+                    output.extend_from_slice(&decode_group(&group)?);
+                    group.clear();
+                }
+            }
+            _ => return Err(format!("Failed to decode ASCII85Decode stream: invalid byte {}", byte)),
+        }
+    }
+
+    match group.len() {
+        0 => {}
+        1 => return Err("Failed to decode ASCII85Decode stream: final group has a single digit".to_string()),
+        length => {
+            group.resize(5, b'u' - b'!');
+            output.extend_from_slice(&decode_group(&group)?[..length - 1]);
+        }
+    }
+
+    Ok(output)
+}
+
+/// Decodes a group of five base-85 digits into four bytes.
+fn decode_group(group: &[u8]) -> Result<[u8; 4], String> {
+
+    let value = group.iter().fold(0u64, |value, digit| value * 85 + *digit as u64);
+
+    u32::try_from(value)
+        .map(u32::to_be_bytes)
+        .map_err(|_| "Failed to decode ASCII85Decode stream: group value out of range".to_string())
+}
+
+/// Encodes data in base-85 (`/ASCII85Decode`), ending with `~>`.
+pub fn encode_ascii85(data: &[u8]) -> Vec<u8> {
+
+    let mut output = Vec::with_capacity(data.len() * 5 / 4 + 7);
+
+    for chunk in data.chunks(4) {
+
+        let mut bytes = [0u8; 4];
+        bytes[..chunk.len()].copy_from_slice(chunk);
+
+        let mut value = u32::from_be_bytes(bytes);
+
+        if value == 0 && chunk.len() == 4 {
+            output.push(b'z');
+            continue;
+        }
+
+        let mut digits = [0u8; 5];
+
+        for digit in digits.iter_mut().rev() {
+            *digit = (value % 85) as u8 + b'!';
+            value /= 85;
+        }
+
+        output.extend_from_slice(&digits[..chunk.len() + 1]);
+    }
+
+    output.extend_from_slice(b"~>");
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_ascii85, encode_ascii85};
+
+    #[test]
+    fn should_decode_and_encode_base85_data() {
+        assert_eq!(decode_ascii85(b"87cURD]i,\"Ebo80~>").unwrap(), b"Hello World!");
+        assert_eq!(decode_ascii85(b"z 87cU\nRD]~>").unwrap(), b"\0\0\0\0Hello");
+        assert_eq!(decode_ascii85(b"87cURD]").unwrap(), b"Hello");
+        assert!(decode_ascii85(b"8~>").is_err());
+        assert!(decode_ascii85(b"uuuuu~>").is_err());
+        assert!(decode_ascii85(b"87{~>").is_err());
+
+        assert_eq!(encode_ascii85(b"Hello World!"), b"87cURD]i,\"Ebo80~>");
+
+        let data = (0..=255u8).chain([0, 0, 0, 0, 7]).collect::<Vec<_>>();
+        assert_eq!(decode_ascii85(&encode_ascii85(&data)).unwrap(), data);
+    }
+}
\ No newline at end of file
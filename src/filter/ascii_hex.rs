@@ -0,0 +1,54 @@
+use crate::specification::value::hexadecimal_char::validate_hexadecimal_char;
+use crate::token::Whitespace;
+use crate::value::HexadecimalChar;
+
+/// Decodes data encoded as hexadecimal digits (`/ASCIIHexDecode`).
+///
+/// Whitespace is ignored, `>` ends the data, and a final odd digit is
+/// followed by `0`.
+pub fn decode_ascii_hex(data: &[u8]) -> Result<Vec<u8>, String> {
+
+    let end = data.iter().position(|byte| *byte == b'>').unwrap_or(data.len());
+
+    let digits = data[..end].iter()
+        .copied()
+        .filter(|byte| Whitespace::from_byte(*byte).is_none())
+        .collect::<Vec<_>>();
+
+    digits.chunks(2)
+        .map(|pair| {
+            let pair = match pair {
+                [high, low] => [*high, *low],
+                _ => [pair[0], b'0'],
+            };
+
+            validate_hexadecimal_char(&pair)
+                .map(|_| HexadecimalChar::new(&pair).as_byte())
+                .map_err(|error| format!("Failed to decode ASCIIHexDecode stream: {}", error))
+        })
+        .collect()
+}
+
+/// Encodes data as hexadecimal digits (`/ASCIIHexDecode`), ending with `>`.
+pub fn encode_ascii_hex(data: &[u8]) -> Vec<u8> {
+
+    let mut output = data.iter().flat_map(|byte| format!("{:02X}", byte).into_bytes()).collect::<Vec<_>>();
+    output.push(b'>');
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_ascii_hex, encode_ascii_hex};
+
+    #[test]
+    fn should_decode_and_encode_hexadecimal_data() {
+        assert_eq!(decode_ascii_hex(b"48 65\n6c6C 6f>").unwrap(), b"Hello");
+        assert_eq!(decode_ascii_hex(b"414>ignored").unwrap(), b"A@");
+        assert_eq!(decode_ascii_hex(b"4142").unwrap(), b"AB");
+        assert!(decode_ascii_hex(b"4G>").is_err());
+
+        assert_eq!(encode_ascii_hex(b"\x00\xffA"), b"00FF41>");
+    }
+}
\ No newline at end of file
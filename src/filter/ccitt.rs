@@ -0,0 +1,415 @@
+use std::collections::HashMap;
+
+/// Parameters of the CCITT facsimile filter (`/DecodeParms`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CcittParams {
+    /// The coding scheme (`/K`): negative for Group 4, 0 for Group 3
+    /// one-dimensional, positive for Group 3 two-dimensional coding.
+    pub k: i64,
+    /// Width of the image in pixels (`/Columns`).
+    pub columns: usize,
+    /// Height of the image in pixels (`/Rows`), or 0 if unknown.
+    pub rows: usize,
+    /// Whether each line begins on a byte boundary (`/EncodedByteAlign`).
+    pub encoded_byte_align: bool,
+    /// Whether 1 bits are black pixels (`/BlackIs1`).
+    pub black_is_1: bool,
+}
+
+impl Default for CcittParams {
+
+    fn default() -> Self {
+        Self { k: 0, columns: 1728, rows: 0, encoded_byte_align: false, black_is_1: false }
+    }
+}
+
+/// Codes of the runs of white pixels (ITU-T T.4, tables 2 and 3).
+const WHITE_CODES: &[(&str, u16)] = &[
+    ("00110101", 0), ("000111", 1), ("0111", 2), ("1000", 3), ("1011", 4), ("1100", 5), ("1110", 6), ("1111", 7),
+    ("10011", 8), ("10100", 9), ("00111", 10), ("01000", 11), ("001000", 12), ("000011", 13), ("110100", 14), ("110101", 15),
+    ("101010", 16), ("101011", 17), ("0100111", 18), ("0001100", 19), ("0001000", 20), ("0010111", 21), ("0000011", 22), ("0000100", 23),
+    ("0101000", 24), ("0101011", 25), ("0010011", 26), ("0100100", 27), ("0011000", 28), ("00000010", 29), ("00000011", 30), ("00011010", 31),
+    ("00011011", 32), ("00010010", 33), ("00010011", 34), ("00010100", 35), ("00010101", 36), ("00010110", 37), ("00010111", 38), ("00101000", 39),
+    ("00101001", 40), ("00101010", 41), ("00101011", 42), ("00101100", 43), ("00101101", 44), ("00000100", 45), ("00000101", 46), ("00001010", 47),
+    ("00001011", 48), ("01010010", 49), ("01010011", 50), ("01010100", 51), ("01010101", 52), ("00100100", 53), ("00100101", 54), ("01011000", 55),
+    ("01011001", 56), ("01011010", 57), ("01011011", 58), ("01001010", 59), ("01001011", 60), ("00110010", 61), ("00110011", 62), ("00110100", 63),
+    ("11011", 64), ("10010", 128), ("010111", 192), ("0110111", 256), ("00110110", 320), ("00110111", 384), ("01100100", 448), ("01100101", 512),
+    ("01101000", 576), ("01100111", 640), ("011001100", 704), ("011001101", 768), ("011010010", 832), ("011010011", 896), ("011010100", 960), ("011010101", 1024),
+    ("011010110", 1088), ("011010111", 1152), ("011011000", 1216), ("011011001", 1280), ("011011010", 1344), ("011011011", 1408), ("010011000", 1472), ("010011001", 1536),
+    ("010011010", 1600), ("011000", 1664), ("010011011", 1728),
+];
+
+/// Codes of the runs of black pixels (ITU-T T.4, tables 2 and 3).
+const BLACK_CODES: &[(&str, u16)] = &[
+    ("0000110111", 0), ("010", 1), ("11", 2), ("10", 3), ("011", 4), ("0011", 5), ("0010", 6), ("00011", 7),
+    ("000101", 8), ("000100", 9), ("0000100", 10), ("0000101", 11), ("0000111", 12), ("00000100", 13), ("00000111", 14), ("000011000", 15),
+    ("0000010111", 16), ("0000011000", 17), ("0000001000", 18), ("00001100111", 19), ("00001101000", 20), ("00001101100", 21), ("00000110111", 22), ("00000101000", 23),
+    ("00000010111", 24), ("00000011000", 25), ("000011001010", 26), ("000011001011", 27), ("000011001100", 28), ("000011001101", 29), ("000001101000", 30), ("000001101001", 31),
+    ("000001101010", 32), ("000001101011", 33), ("000011010010", 34), ("000011010011", 35), ("000011010100", 36), ("000011010101", 37), ("000011010110", 38), ("000011010111", 39),
+    ("000001101100", 40), ("000001101101", 41), ("000011011010", 42), ("000011011011", 43), ("000001010100", 44), ("000001010101", 45), ("000001010110", 46), ("000001010111", 47),
+    ("000001100100", 48), ("000001100101", 49), ("000001010010", 50), ("000001010011", 51), ("000000100100", 52), ("000000110111", 53), ("000000111000", 54), ("000000100111", 55),
+    ("000000101000", 56), ("000001011000", 57), ("000001011001", 58), ("000000101011", 59), ("000000101100", 60), ("000001011010", 61), ("000001100110", 62), ("000001100111", 63),
+    ("0000001111", 64), ("000011001000", 128), ("000011001001", 192), ("000001011011", 256), ("000000110011", 320), ("000000110100", 384), ("000000110101", 448), ("0000001101100", 512),
+    ("0000001101101", 576), ("0000001001010", 640), ("0000001001011", 704), ("0000001001100", 768), ("0000001001101", 832), ("0000001110010", 896), ("0000001110011", 960), ("0000001110100", 1024),
+    ("0000001110101", 1088), ("0000001110110", 1152), ("0000001110111", 1216), ("0000001010010", 1280), ("0000001010011", 1344), ("0000001010100", 1408), ("0000001010101", 1472), ("0000001011010", 1536),
+    ("0000001011011", 1600), ("0000001100100", 1664), ("0000001100101", 1728),
+];
+
+/// Make-up codes of long runs of either color (ITU-T T.4, table 4).
+const EXTENDED_CODES: &[(&str, u16)] = &[
+    ("00000001000", 1792), ("00000001100", 1856), ("00000001101", 1920), ("000000010010", 1984), ("000000010011", 2048), ("000000010100", 2112), ("000000010101", 2176),
+    ("000000010110", 2240), ("000000010111", 2304), ("000000011100", 2368), ("000000011101", 2432), ("000000011110", 2496), ("000000011111", 2560),
+];
+
+/// The end-of-line code: eleven zero bits and a one bit.
+const END_OF_LINE: u32 = 1;
+
+/// Modes of two-dimensional coding (ITU-T T.4, 4.2.1.3).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Pass,
+    Horizontal,
+    Vertical(isize),
+    EndOfLine,
+}
+
+/// Decodes data compressed with CCITT Group 3 or Group 4 facsimile
+/// coding (`/CCITTFaxDecode`) into rows of one bit per pixel.
+///
+/// Decoding stops at the end of the data or of the block; rows that
+/// cannot be decoded end the image, which is an error only when no
+/// row is decoded.
+pub fn decode_ccitt(data: &[u8], params: &CcittParams) -> Result<Vec<u8>, String> {
+
+    let columns = params.columns;
+
+    if columns == 0 {
+        return Err("Failed to decode CCITTFaxDecode stream: /Columns must be positive".to_string());
+    }
+
+    let white = table(&[WHITE_CODES, EXTENDED_CODES]);
+    let black = table(&[BLACK_CODES, EXTENDED_CODES]);
+
+    let mut reader = BitReader { data, position: 0 };
+    let mut output = Vec::new();
+    let mut reference = vec![columns, columns];
+    let mut rows = 0;
+
+    let mut two_dimensional = params.k < 0;
+
+    if params.k >= 0 {
+        reader.skip_end_of_line();
+
+        if params.k > 0 {
+            two_dimensional = !reader.bit().unwrap_or(true);
+        }
+    }
+
+    while params.rows == 0 || rows < params.rows {
+
+        if reader.is_empty() {
+            break;
+        }
+
+        let changes = match two_dimensional {
+            true => decode_2d(&mut reader, &reference, columns, (&white, &black)),
+            false => decode_1d(&mut reader, columns, (&white, &black)),
+        };
+
+        let Some(changes) = changes else {
+            break;
+        };
+
+        output.extend(pack_row(&changes, columns, params.black_is_1));
+        rows += 1;
+
+        reference = changes;
+        reference.extend([columns, columns]);
+
+        if params.encoded_byte_align {
+            reader.align();
+        }
+
+        if params.k >= 0 {
+            // Six end-of-line codes in a row end the data.
+            if reader.skip_end_of_line() && reader.skip_end_of_line() {
+                break;
+            }
+
+            if params.k > 0 {
+                match reader.bit() {
+                    Some(bit) => two_dimensional = !bit,
+                    None => break,
+                }
+            }
+        }
+    }
+
+    if rows == 0 {
+        return Err("Failed to decode CCITTFaxDecode stream: no row could be decoded".to_string());
+    }
+
+    Ok(output)
+}
+
+/// Run lengths by code length and code.
+type RunTable = HashMap<(u32, u32), u16>;
+
+/// Builds the table of run lengths by code length and code.
+fn table(codes: &[&[(&str, u16)]]) -> RunTable {
+
+    codes.iter()
+        .flat_map(|codes| codes.iter())
+        .map(|(code, run)| ((code.len() as u32, u32::from_str_radix(code, 2).unwrap()), *run))
+        .collect()
+}
+
+/// Decodes a one-dimensional (Modified Huffman) row into its changing elements.
+fn decode_1d(reader: &mut BitReader, columns: usize, tables: (&RunTable, &RunTable)) -> Option<Vec<usize>> {
+
+    let mut changes = Vec::new();
+    let mut position = 0;
+    let mut color = 0;
+
+    while position < columns {
+        position = (position + reader.run(if color == 0 { tables.0 } else { tables.1 })?).min(columns);
+        changes.push(position);
+        color ^= 1;
+    }
+
+    Some(trim(changes, columns))
+}
+
+/// Decodes a two-dimensional row, coded relative to the changing
+/// elements of the reference row, into its changing elements.
+fn decode_2d(
+    reader: &mut BitReader,
+    reference: &[usize],
+    columns: usize,
+    tables: (&RunTable, &RunTable),
+) -> Option<Vec<usize>> {
+
+    let mut changes: Vec<usize> = Vec::new();
+    let mut a0: isize = -1;
+    let mut color = 0;
+    let mut start = 0;
+
+    while a0 < columns as isize {
+
+        // b1 is the first changing element of the reference row right of
+        // a0 and of the opposite color; the changes of even index are
+        // changes to black.
+        while start > 0 && reference[start - 1] as isize > a0 {
+            start -= 1;
+        }
+
+        let mut index = start;
+
+        while index < reference.len() - 2 && (reference[index] as isize <= a0 || index % 2 != color) {
+            index += 1;
+        }
+
+        start = index;
+
+        let b1 = reference[index];
+        let b2 = reference[(index + 1).min(reference.len() - 1)];
+
+        match reader.mode()? {
+            Mode::Pass => a0 = b2 as isize,
+            Mode::Horizontal => {
+                let origin = a0.max(0) as usize;
+                let first = reader.run(if color == 0 { tables.0 } else { tables.1 })?;
+                let second = reader.run(if color == 0 { tables.1 } else { tables.0 })?;
+
+                let a1 = (origin + first).min(columns);
+                let a2 = (a1 + second).min(columns);
+
+                changes.extend([a1, a2]);
+                a0 = a2 as isize;
+            }
+            Mode::Vertical(offset) => {
+                let a1 = (b1 as isize + offset).clamp(0, columns as isize);
+
+                if changes.last().is_some_and(|last| *last as isize > a1) {
+                    return None;
+                }
+
+                changes.push(a1 as usize);
+                a0 = a1;
+                color ^= 1;
+            }
+            Mode::EndOfLine => return None,
+        }
+    }
+
+    Some(trim(changes, columns))
+}
+
+/// Removes the changing elements at the end of a row.
+fn trim(mut changes: Vec<usize>, columns: usize) -> Vec<usize> {
+
+    while changes.last().is_some_and(|last| *last >= columns) {
+        changes.pop();
+    }
+
+    changes
+}
+
+/// Packs a row from its changing elements, the first of which is a change to black.
+fn pack_row(changes: &[usize], columns: usize, black_is_1: bool) -> Vec<u8> {
+
+    let mut row = vec![if black_is_1 { 0x00 } else { 0xFF }; columns.div_ceil(8)];
+
+    for span in changes.chunks(2) {
+        let end = span.get(1).copied().unwrap_or(columns);
+
+        for x in span[0]..end {
+            row[x / 8] ^= 0x80 >> (x % 8);
+        }
+    }
+
+    row
+}
+
+/// Reads the bits of coded data, from the most significant bit first.
+struct BitReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl BitReader<'_> {
+
+    /// Returns `true` if every bit has been read.
+    fn is_empty(&self) -> bool {
+        self.position >= self.data.len() * 8
+    }
+
+    /// Reads a bit, if any is left.
+    fn bit(&mut self) -> Option<bool> {
+
+        let byte = self.data.get(self.position / 8)?;
+        let bit = (byte >> (7 - self.position % 8)) & 1 == 1;
+        self.position += 1;
+
+        Some(bit)
+    }
+
+    /// Returns the next bits without reading them, padded with zero bits.
+    fn peek(&self, count: u32) -> u32 {
+
+        (0..count as usize).fold(0, |value, offset| {
+            let position = self.position + offset;
+            let bit = self.data.get(position / 8).map(|byte| (byte >> (7 - position % 8)) & 1).unwrap_or(0);
+            (value << 1) | bit as u32
+        })
+    }
+
+    /// Skips to the next byte boundary.
+    fn align(&mut self) {
+        self.position = self.position.next_multiple_of(8);
+    }
+
+    /// Skips fill bits and an end-of-line code, if one follows.
+    /// Returns `true` if one was skipped.
+    fn skip_end_of_line(&mut self) -> bool {
+
+        let mut position = self.position;
+
+        while position < self.data.len() * 8 {
+            let reader = BitReader { data: self.data, position };
+
+            match reader.peek(12) {
+                0 => position += 1,
+                END_OF_LINE => {
+                    self.position = position + 12;
+                    return true;
+                }
+                _ => break,
+            }
+        }
+
+        false
+    }
+
+    /// Reads the code of a two-dimensional coding mode.
+    fn mode(&mut self) -> Option<Mode> {
+
+        let mode = match self.peek(7) {
+            code if code >> 6 == 0b1 => (1, Mode::Vertical(0)),
+            code if code >> 4 == 0b011 => (3, Mode::Vertical(1)),
+            code if code >> 4 == 0b010 => (3, Mode::Vertical(-1)),
+            code if code >> 4 == 0b001 => (3, Mode::Horizontal),
+            code if code >> 3 == 0b0001 => (4, Mode::Pass),
+            code if code >> 1 == 0b000011 => (6, Mode::Vertical(2)),
+            code if code >> 1 == 0b000010 => (6, Mode::Vertical(-2)),
+            0b0000011 => (7, Mode::Vertical(3)),
+            0b0000010 => (7, Mode::Vertical(-3)),
+            _ if self.peek(12) == END_OF_LINE => (12, Mode::EndOfLine),
+            // Uncompressed mode extensions are not supported.
+            _ => return None,
+        };
+
+        if self.position + mode.0 > self.data.len() * 8 {
+            return None;
+        }
+
+        self.position += mode.0;
+        Some(mode.1)
+    }
+
+    /// Reads a run length: make-up codes followed by a terminating code.
+    fn run(&mut self, table: &RunTable) -> Option<usize> {
+
+        let mut total = 0;
+
+        loop {
+            let (length, run) = (2..=13).find_map(|length| table.get(&(length, self.peek(length))).map(|run| (length, *run)))?;
+
+            if self.position + length as usize > self.data.len() * 8 {
+                return None;
+            }
+
+            self.position += length as usize;
+            total += run as usize;
+
+            if run < 64 {
+                return Some(total);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_ccitt, CcittParams};
+
+    const BITMAP: &[u8] = include_bytes!("../../tests/image/fax.raw");
+
+    fn params(k: i64, black_is_1: bool) -> CcittParams {
+        CcittParams { k, columns: 2600, rows: 24, encoded_byte_align: false, black_is_1 }
+    }
+
+    #[test]
+    fn should_decode_group_4() {
+        assert_eq!(decode_ccitt(include_bytes!("../../tests/image/g4.fax"), &params(-1, true)).unwrap(), BITMAP);
+
+        let inverted = BITMAP.iter().map(|byte| !byte).collect::<Vec<_>>();
+        assert_eq!(decode_ccitt(include_bytes!("../../tests/image/g4.fax"), &params(-1, false)).unwrap(), inverted);
+    }
+
+    #[test]
+    fn should_decode_group_3() {
+        assert_eq!(decode_ccitt(include_bytes!("../../tests/image/g3.fax"), &params(0, true)).unwrap(), BITMAP);
+        assert_eq!(decode_ccitt(include_bytes!("../../tests/image/g3-2d.fax"), &params(4, true)).unwrap(), BITMAP);
+    }
+
+    #[test]
+    fn should_stop_at_truncated_rows() {
+        let data = include_bytes!("../../tests/image/g4.fax");
+        let decoded = decode_ccitt(&data[..data.len() / 2], &CcittParams { rows: 0, ..params(-1, true) }).unwrap();
+
+        assert!(!decoded.is_empty() && decoded.len() < BITMAP.len());
+        assert_eq!(decoded, BITMAP[..decoded.len()]);
+        assert!(decode_ccitt(&[0x02, 0x00, 0x00], &params(-1, true)).is_err());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,849 @@
+/// Order of the coefficients of a block in the entropy-coded data.
+const ZIGZAG: [usize; 64] = [
+    0, 1, 8, 16, 9, 2, 3, 10,
+    17, 24, 32, 25, 18, 11, 4, 5,
+    12, 19, 26, 33, 40, 48, 41, 34,
+    27, 20, 13, 6, 7, 14, 21, 28,
+    35, 42, 49, 56, 57, 50, 43, 36,
+    29, 22, 15, 23, 30, 37, 44, 51,
+    58, 59, 52, 45, 38, 31, 39, 46,
+    53, 60, 61, 54, 47, 55, 62, 63,
+];
+
+/// Largest number of samples a frame may declare, components included.
+const MAX_FRAME_SAMPLES: usize = 1 << 28;
+
+/// Decodes JPEG data (`/DCTDecode`) into interleaved 8-bit samples.
+///
+/// Baseline, extended and progressive Huffman-coded JPEG is supported.
+/// `color_transform` is the `/ColorTransform` parameter: when absent,
+/// the Adobe marker decides, and three components are YCbCr by default.
+/// Truncated data decodes as far as it goes.
+pub fn decode_dct(data: &[u8], color_transform: Option<bool>) -> Result<Vec<u8>, String> {
+
+    let mut decoder = Decoder::default();
+    decoder.read(data)?;
+
+    let frame = decoder.frame.as_ref().ok_or_else(|| "Failed to decode DCTDecode stream: no frame".to_string())?;
+
+    let transform = color_transform
+        .or(decoder.adobe_transform.map(|transform| transform != 0))
+        .unwrap_or(frame.components.len() == 3);
+
+    frame.output(&decoder.quantization, transform)
+}
+
+/// A Huffman table, decoded with the code ranges of each length.
+#[derive(Debug, Clone, Default)]
+struct HuffmanTable {
+    /// Largest code of each length, or -1 if there is none.
+    max_code: [i32; 17],
+    /// Index in `values` of the first code of each length, less that code.
+    offset: [i32; 17],
+    /// The symbols, in code order.
+    values: Vec<u8>,
+}
+
+impl HuffmanTable {
+
+    /// Builds a table from the number of codes of each length and the symbols.
+    fn new(counts: &[u8], values: &[u8]) -> Self {
+
+        let mut table = Self { max_code: [-1; 17], offset: [0; 17], values: values.to_vec() };
+        let mut code = 0i32;
+        let mut index = 0i32;
+
+        for length in 1..=16 {
+            let count = counts[length - 1] as i32;
+
+            if count > 0 {
+                table.offset[length] = index - code;
+                code += count;
+                index += count;
+                table.max_code[length] = code - 1;
+            }
+
+            code <<= 1;
+        }
+
+        table
+    }
+}
+
+/// A component of a frame and its decoded coefficients.
+#[derive(Debug, Clone)]
+struct Component {
+    /// The component identifier.
+    id: u8,
+    /// Horizontal sampling factor.
+    horizontal: usize,
+    /// Vertical sampling factor.
+    vertical: usize,
+    /// Index of the quantization table.
+    quantization: usize,
+    /// Number of blocks per line, padded to whole MCUs.
+    blocks_per_line: usize,
+    /// Number of block lines, padded to whole MCUs.
+    blocks_per_column: usize,
+    /// Width of the component in samples, without padding.
+    width: usize,
+    /// Height of the component in samples, without padding.
+    height: usize,
+    /// Coefficients of every block, in natural order.
+    coefficients: Vec<i32>,
+    /// Index of the DC Huffman table of the current scan.
+    dc_table: usize,
+    /// Index of the AC Huffman table of the current scan.
+    ac_table: usize,
+    /// DC prediction of the current scan.
+    prediction: i32,
+}
+
+impl Component {
+
+    /// Returns the coefficients of the block at the given block line and column.
+    fn block(&mut self, line: usize, column: usize) -> &mut [i32] {
+
+        let start = (line * self.blocks_per_line + column) * 64;
+        &mut self.coefficients[start..start + 64]
+    }
+}
+
+/// The frame of a JPEG image.
+#[derive(Debug, Clone)]
+struct Frame {
+    /// Whether the frame is progressive.
+    progressive: bool,
+    /// Width of the image.
+    width: usize,
+    /// Height of the image.
+    height: usize,
+    /// Largest horizontal sampling factor.
+    max_horizontal: usize,
+    /// Largest vertical sampling factor.
+    max_vertical: usize,
+    /// Number of MCUs per line.
+    mcus_per_line: usize,
+    /// Number of MCU lines.
+    mcus_per_column: usize,
+    /// The components.
+    components: Vec<Component>,
+}
+
+/// Parameters of a scan.
+#[derive(Debug, Clone, Copy)]
+struct Scan {
+    /// First coefficient of the spectral selection.
+    start: usize,
+    /// Last coefficient of the spectral selection.
+    end: usize,
+    /// Bit position of the previous scan of the coefficients, or 0.
+    high: u32,
+    /// Bit position of this scan.
+    low: u32,
+}
+
+/// Reads the markers of JPEG data and decodes its scans.
+#[derive(Debug)]
+struct Decoder {
+    /// Quantization tables, in natural order.
+    quantization: [[i32; 64]; 4],
+    /// DC Huffman tables.
+    dc_tables: [HuffmanTable; 4],
+    /// AC Huffman tables.
+    ac_tables: [HuffmanTable; 4],
+    /// Number of MCUs between restart markers, or 0.
+    restart_interval: usize,
+    /// Color transform of the Adobe marker, if any.
+    adobe_transform: Option<u8>,
+    /// The frame, once read.
+    frame: Option<Frame>,
+}
+
+impl Default for Decoder {
+
+    fn default() -> Self {
+        Self {
+            quantization: [[1; 64]; 4],
+            dc_tables: Default::default(),
+            ac_tables: Default::default(),
+            restart_interval: 0,
+            adobe_transform: None,
+            frame: None,
+        }
+    }
+}
+
+impl Decoder {
+
+    /// Reads the markers of the data, decoding every scan.
+    fn read(&mut self, data: &[u8]) -> Result<(), String> {
+
+        if !data.starts_with(&[0xFF, 0xD8]) {
+            return Err("Failed to decode DCTDecode stream: missing start of image".to_string());
+        }
+
+        let mut position = 2;
+
+        while position + 4 <= data.len() {
+
+            if data[position] != 0xFF {
+                position += 1;
+                continue;
+            }
+
+            let marker = data[position + 1];
+
+            // Fill bytes and markers without a segment.
+            if marker == 0xFF || (0xD0..=0xD8).contains(&marker) || marker == 0x01 {
+                position += if marker == 0xFF { 1 } else { 2 };
+                continue;
+            }
+
+            if marker == 0xD9 {
+                break;
+            }
+
+            let length = u16::from_be_bytes([data[position + 2], data[position + 3]]) as usize;
+            let end = (position + 2 + length).min(data.len());
+            let segment = &data[(position + 4).min(end)..end];
+
+            position = end;
+
+            match marker {
+                0xC0..=0xC2 => self.read_frame(segment, marker == 0xC2)?,
+                0xC3 | 0xC5..=0xC7 | 0xC9..=0xCB | 0xCD..=0xCF => {
+                    return Err("Failed to decode DCTDecode stream: lossless, hierarchical and arithmetic coding are not supported".to_string());
+                }
+                0xC4 => self.read_huffman_tables(segment)?,
+                0xDB => self.read_quantization_tables(segment)?,
+                0xDD if segment.len() >= 2 => self.restart_interval = u16::from_be_bytes([segment[0], segment[1]]) as usize,
+                0xEE if segment.starts_with(b"Adobe") && segment.len() >= 12 => self.adobe_transform = Some(segment[11]),
+                0xDA => position = self.read_scan(segment, data, position)?,
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reads a start of frame segment.
+    fn read_frame(&mut self, segment: &[u8], progressive: bool) -> Result<(), String> {
+
+        if segment.len() < 6 {
+            return Err("Failed to decode DCTDecode stream: truncated frame header".to_string());
+        }
+
+        if segment[0] != 8 {
+            return Err(format!("Failed to decode DCTDecode stream: unsupported precision {}", segment[0]));
+        }
+
+        let height = u16::from_be_bytes([segment[1], segment[2]]) as usize;
+        let width = u16::from_be_bytes([segment[3], segment[4]]) as usize;
+        let count = segment[5] as usize;
+
+        if width == 0 || height == 0 || count == 0 || segment.len() < 6 + count * 3 {
+            return Err("Failed to decode DCTDecode stream: invalid frame header".to_string());
+        }
+
+        if samples(&[width, height, count]).is_none_or(|samples| samples > MAX_FRAME_SAMPLES) {
+            return Err(format!("Failed to decode DCTDecode stream: the {}x{} frame is too large", width, height));
+        }
+
+        let mut components = segment[6..6 + count * 3].chunks_exact(3)
+            .map(|component| Component {
+                id: component[0],
+                horizontal: (component[1] >> 4).clamp(1, 4) as usize,
+                vertical: (component[1] & 15).clamp(1, 4) as usize,
+                quantization: (component[2] & 3) as usize,
+                blocks_per_line: 0,
+                blocks_per_column: 0,
+                width: 0,
+                height: 0,
+                coefficients: Vec::new(),
+                dc_table: 0,
+                ac_table: 0,
+                prediction: 0,
+            })
+            .collect::<Vec<_>>();
+
+        let max_horizontal = components.iter().map(|component| component.horizontal).max().unwrap_or(1);
+        let max_vertical = components.iter().map(|component| component.vertical).max().unwrap_or(1);
+        let mcus_per_line = width.div_ceil(8 * max_horizontal);
+        let mcus_per_column = height.div_ceil(8 * max_vertical);
+
+        for component in &mut components {
+            component.width = (width * component.horizontal).div_ceil(max_horizontal);
+            component.height = (height * component.vertical).div_ceil(max_vertical);
+            component.blocks_per_line = mcus_per_line * component.horizontal;
+            component.blocks_per_column = mcus_per_column * component.vertical;
+            component.coefficients = allocate(samples(&[component.blocks_per_line, component.blocks_per_column, 64]), 0)?;
+        }
+
+        self.frame = Some(Frame { progressive, width, height, max_horizontal, max_vertical, mcus_per_line, mcus_per_column, components });
+
+        Ok(())
+    }
+
+    /// Reads a Huffman table segment.
+    fn read_huffman_tables(&mut self, mut segment: &[u8]) -> Result<(), String> {
+
+        while segment.len() >= 17 {
+
+            let class = segment[0] >> 4;
+            let index = (segment[0] & 3) as usize;
+            let counts = &segment[1..17];
+            let total = counts.iter().map(|count| *count as usize).sum::<usize>();
+
+            if segment.len() < 17 + total {
+                return Err("Failed to decode DCTDecode stream: truncated Huffman table".to_string());
+            }
+
+            let table = HuffmanTable::new(counts, &segment[17..17 + total]);
+
+            match class {
+                0 => self.dc_tables[index] = table,
+                _ => self.ac_tables[index] = table,
+            }
+
+            segment = &segment[17 + total..];
+        }
+
+        Ok(())
+    }
+
+    /// Reads a quantization table segment.
+    fn read_quantization_tables(&mut self, mut segment: &[u8]) -> Result<(), String> {
+
+        while !segment.is_empty() {
+
+            let wide = segment[0] >> 4 != 0;
+            let index = (segment[0] & 3) as usize;
+            let length = if wide { 129 } else { 65 };
+
+            if segment.len() < length {
+                return Err("Failed to decode DCTDecode stream: truncated quantization table".to_string());
+            }
+
+            for (position, natural) in ZIGZAG.iter().enumerate() {
+                self.quantization[index][*natural] = match wide {
+                    true => u16::from_be_bytes([segment[1 + position * 2], segment[2 + position * 2]]) as i32,
+                    false => segment[1 + position] as i32,
+                };
+            }
+
+            segment = &segment[length..];
+        }
+
+        Ok(())
+    }
+
+    /// Reads a start of scan segment and decodes the entropy-coded data
+    /// that follows it. Returns the position of the next marker.
+    fn read_scan(&mut self, segment: &[u8], data: &[u8], position: usize) -> Result<usize, String> {
+
+        let frame = self.frame.as_mut().ok_or_else(|| "Failed to decode DCTDecode stream: scan before frame".to_string())?;
+
+        let count = *segment.first().unwrap_or(&0) as usize;
+
+        if count == 0 || segment.len() < 4 + count * 2 {
+            return Err("Failed to decode DCTDecode stream: invalid scan header".to_string());
+        }
+
+        let mut indices = Vec::with_capacity(count);
+
+        for selector in segment[1..1 + count * 2].chunks_exact(2) {
+            let index = frame.components.iter().position(|component| component.id == selector[0])
+                .ok_or_else(|| format!("Failed to decode DCTDecode stream: unknown component {}", selector[0]))?;
+
+            frame.components[index].dc_table = (selector[1] >> 4 & 3) as usize;
+            frame.components[index].ac_table = (selector[1] & 3) as usize;
+            frame.components[index].prediction = 0;
+            indices.push(index);
+        }
+
+        let parameters = &segment[1 + count * 2..];
+
+        let scan = match frame.progressive {
+            true => Scan {
+                start: (parameters[0] as usize).min(63),
+                end: (parameters[1] as usize).min(63),
+                high: (parameters[2] >> 4) as u32,
+                low: (parameters[2] & 15) as u32,
+            },
+            false => Scan { start: 0, end: 63, high: 0, low: 0 },
+        };
+
+        let mut reader = BitReader::new(data, position);
+        let tables = (&self.dc_tables, &self.ac_tables);
+
+        decode_scan(frame, &indices, scan, tables, self.restart_interval, &mut reader);
+
+        // The scan ends at the first marker that is not a restart marker.
+        let mut next = reader.position;
+
+        while next + 1 < data.len() && !(data[next] == 0xFF && data[next + 1] != 0 && !(0xD0..=0xD7).contains(&data[next + 1])) {
+            next += 1;
+        }
+
+        Ok(next)
+    }
+}
+
+/// Decodes the entropy-coded data of a scan into the coefficients of its components.
+fn decode_scan(
+    frame: &mut Frame,
+    indices: &[usize],
+    scan: Scan,
+    (dc_tables, ac_tables): (&[HuffmanTable; 4], &[HuffmanTable; 4]),
+    restart_interval: usize,
+    reader: &mut BitReader,
+) {
+
+    let mut end_of_band = 0u32;
+
+    // A scan of a single component codes its blocks one by one, without
+    // the padding of the MCUs.
+    let (units_per_line, total) = match indices {
+        [index] => {
+            let component = &frame.components[*index];
+            let per_line = component.width.div_ceil(8);
+            (per_line, per_line * component.height.div_ceil(8))
+        }
+        _ => (frame.mcus_per_line, frame.mcus_per_line * frame.mcus_per_column),
+    };
+
+    for unit in 0..total {
+
+        if restart_interval > 0 && unit > 0 && unit % restart_interval == 0 {
+            reader.restart();
+            end_of_band = 0;
+
+            for index in indices {
+                frame.components[*index].prediction = 0;
+            }
+        }
+
+        let (line, column) = (unit / units_per_line, unit % units_per_line);
+
+        for index in indices {
+
+            let component = &mut frame.components[*index];
+            let (dc, ac) = (&dc_tables[component.dc_table], &ac_tables[component.ac_table]);
+
+            let blocks = match indices.len() {
+                1 => vec![(line, column)],
+                _ => (0..component.vertical)
+                    .flat_map(|v| (0..component.horizontal).map(move |h| (v, h)))
+                    .map(|(v, h)| (line * component.vertical + v, column * component.horizontal + h))
+                    .collect(),
+            };
+
+            for (block_line, block_column) in blocks {
+
+                let mut prediction = component.prediction;
+                let block = component.block(block_line, block_column);
+
+                match (scan.start, scan.high) {
+                    (0, 0) => {
+                        let size = reader.decode(dc);
+                        prediction += reader.extend(size);
+                        block[0] = prediction * (1 << scan.low);
+
+                        if scan.end > 0 {
+                            decode_ac_first(block, ac, scan, reader, &mut end_of_band);
+                        }
+                    }
+                    (0, _) => {
+                        if reader.bit() {
+                            block[0] |= 1 << scan.low;
+                        }
+                    }
+                    (_, 0) => decode_ac_first(block, ac, scan, reader, &mut end_of_band),
+                    _ => decode_ac_refine(block, ac, scan, reader, &mut end_of_band),
+                }
+
+                component.prediction = prediction;
+            }
+        }
+    }
+}
+
+/// Decodes the AC coefficients of a sequential scan, or the first scan
+/// of a spectral band of a progressive image.
+fn decode_ac_first(block: &mut [i32], table: &HuffmanTable, scan: Scan, reader: &mut BitReader, end_of_band: &mut u32) {
+
+    if *end_of_band > 0 {
+        *end_of_band -= 1;
+        return;
+    }
+
+    let mut k = scan.start.max(1);
+
+    while k <= scan.end {
+
+        let symbol = reader.decode(table);
+        let (run, size) = ((symbol >> 4) as usize, symbol & 15);
+
+        if size == 0 {
+            if run < 15 {
+                *end_of_band = (1 << run) - 1;
+
+                if run > 0 {
+                    *end_of_band += reader.bits(run as u32);
+                }
+
+                break;
+            }
+
+            k += 16;
+            continue;
+        }
+
+        k += run;
+
+        if k > 63 {
+            break;
+        }
+
+        block[ZIGZAG[k]] = reader.extend(size) * (1 << scan.low);
+        k += 1;
+    }
+}
+
+/// Decodes a refinement scan of a spectral band of a progressive image.
+fn decode_ac_refine(block: &mut [i32], table: &HuffmanTable, scan: Scan, reader: &mut BitReader, end_of_band: &mut u32) {
+
+    let positive = 1 << scan.low;
+    let negative = -1 << scan.low;
+    let mut k = scan.start;
+
+    let refine = |coefficient: &mut i32, reader: &mut BitReader| {
+        if reader.bit() && *coefficient & positive == 0 {
+            *coefficient += if *coefficient >= 0 { positive } else { negative };
+        }
+    };
+
+    if *end_of_band == 0 {
+        while k <= scan.end {
+
+            let symbol = reader.decode(table);
+            let (mut run, size) = ((symbol >> 4) as i32, symbol & 15);
+            let mut value = 0;
+
+            if size != 0 {
+                value = if reader.bit() { positive } else { negative };
+            } else if run != 15 {
+                *end_of_band = 1 << run;
+
+                if run > 0 {
+                    *end_of_band += reader.bits(run as u32);
+                }
+
+                break;
+            }
+
+            // Skips the zero coefficients of the run, refining the non-zero ones.
+            while k <= scan.end {
+                let coefficient = &mut block[ZIGZAG[k]];
+
+                if *coefficient != 0 {
+                    refine(coefficient, reader);
+                } else {
+                    run -= 1;
+
+                    if run < 0 {
+                        break;
+                    }
+                }
+
+                k += 1;
+            }
+
+            if value != 0 && k <= scan.end {
+                block[ZIGZAG[k]] = value;
+            }
+
+            k += 1;
+        }
+    }
+
+    if *end_of_band > 0 {
+        while k <= scan.end {
+            let coefficient = &mut block[ZIGZAG[k]];
+
+            if *coefficient != 0 {
+                refine(coefficient, reader);
+            }
+
+            k += 1;
+        }
+
+        *end_of_band -= 1;
+    }
+}
+
+impl Frame {
+
+    /// Returns the interleaved samples of the frame.
+    fn output(&self, quantization: &[[i32; 64]; 4], transform: bool) -> Result<Vec<u8>, String> {
+
+        let planes = self.components.iter()
+            .map(|component| inverse_transform(component, &quantization[component.quantization]))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let count = self.components.len();
+        let mut output = allocate(samples(&[self.width, self.height, count]), 0u8)?;
+        let mut pixel = vec![0.0f32; count];
+        let mut position = 0;
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+
+                // Subsampled components are upsampled by replication.
+                for (index, component) in self.components.iter().enumerate() {
+                    let sx = x * component.horizontal / self.max_horizontal;
+                    let sy = y * component.vertical / self.max_vertical;
+                    pixel[index] = planes[index][sy * component.blocks_per_line * 8 + sx];
+                }
+
+                if transform && count >= 3 {
+                    let (luma, blue, red) = (pixel[0], pixel[1] - 128.0, pixel[2] - 128.0);
+
+                    pixel[0] = luma + 1.402 * red;
+                    pixel[1] = luma - 0.344136 * blue - 0.714136 * red;
+                    pixel[2] = luma + 1.772 * blue;
+
+                    // YCCK holds the complement of CMY.
+                    if count == 4 {
+                        for value in &mut pixel[..3] {
+                            *value = 255.0 - *value;
+                        }
+                    }
+                }
+
+                for (sample, value) in output[position..position + count].iter_mut().zip(&pixel) {
+                    *sample = value.round().clamp(0.0, 255.0) as u8;
+                }
+
+                position += count;
+            }
+        }
+
+        Ok(output)
+    }
+}
+
+/// Returns the samples of a component, dequantized and transformed, in
+/// rows of the padded block width.
+fn inverse_transform(component: &Component, quantization: &[i32; 64]) -> Result<Vec<f32>, String> {
+
+    let cosines = cosine_table();
+    let stride = component.blocks_per_line * 8;
+    let mut plane = allocate(samples(&[stride, component.blocks_per_column, 8]), 0.0f32)?;
+    let mut temporary = [0.0f32; 64];
+
+    for (index, block) in component.coefficients.chunks_exact(64).enumerate() {
+
+        let (line, column) = (index / component.blocks_per_line, index % component.blocks_per_line);
+
+        // Rows, then columns.
+        for v in 0..8 {
+            for x in 0..8 {
+                temporary[v * 8 + x] = (0..8).map(|u| cosines[x][u] * (block[v * 8 + u] * quantization[v * 8 + u]) as f32).sum();
+            }
+        }
+
+        for y in 0..8 {
+            for x in 0..8 {
+                let value = (0..8).map(|v| cosines[y][v] * temporary[v * 8 + x]).sum::<f32>();
+                plane[(line * 8 + y) * stride + column * 8 + x] = value + 128.0;
+            }
+        }
+    }
+
+    Ok(plane)
+}
+
+/// Returns the product of the dimensions of a buffer, or `None` on overflow.
+fn samples(dimensions: &[usize]) -> Option<usize> {
+    dimensions.iter().try_fold(1usize, |product, dimension| product.checked_mul(*dimension))
+}
+
+/// Returns a buffer of `length` copies of `value`, failing rather than
+/// aborting when it cannot be allocated.
+fn allocate<T: Clone>(length: Option<usize>, value: T) -> Result<Vec<T>, String> {
+
+    let length = length.ok_or_else(|| "Failed to decode DCTDecode stream: the frame is too large".to_string())?;
+    let mut buffer = Vec::new();
+
+    buffer.try_reserve_exact(length).map_err(|_| format!("Failed to decode DCTDecode stream: cannot allocate {} samples", length))?;
+    buffer.resize(length, value);
+
+    Ok(buffer)
+}
+
+/// Returns the factors of the inverse DCT: `C(u) / 2 * cos((2x + 1)uπ / 16)`.
+fn cosine_table() -> [[f32; 8]; 8] {
+
+    let mut table = [[0.0f32; 8]; 8];
+
+    for (x, row) in table.iter_mut().enumerate() {
+        for (u, value) in row.iter_mut().enumerate() {
+            let scale = if u == 0 { std::f32::consts::FRAC_1_SQRT_2 } else { 1.0 };
+            *value = scale / 2.0 * ((2 * x + 1) as f32 * u as f32 * std::f32::consts::PI / 16.0).cos();
+        }
+    }
+
+    table
+}
+
+/// Reads the bits of entropy-coded data, removing stuffed zero bytes.
+///
+/// Bits past a marker or the end of the data read as zero.
+struct BitReader<'a> {
+    data: &'a [u8],
+    position: usize,
+    buffer: u32,
+    bits: u32,
+}
+
+impl<'a> BitReader<'a> {
+
+    /// Creates a reader of the data from the given position.
+    fn new(data: &'a [u8], position: usize) -> Self {
+        Self { data, position, buffer: 0, bits: 0 }
+    }
+
+    /// Reads a single bit.
+    fn bit(&mut self) -> bool {
+
+        if self.bits == 0 {
+            self.buffer = match self.data.get(self.position) {
+                Some(0xFF) if self.data.get(self.position + 1) == Some(&0) => {
+                    self.position += 2;
+                    0xFF
+                }
+                Some(0xFF) | None => 0,
+                Some(byte) => {
+                    self.position += 1;
+                    *byte as u32
+                }
+            };
+
+            self.bits = 8;
+        }
+
+        self.bits -= 1;
+        (self.buffer >> self.bits) & 1 == 1
+    }
+
+    /// Reads an unsigned number of the given number of bits.
+    fn bits(&mut self, count: u32) -> u32 {
+        (0..count).fold(0, |value, _| (value << 1) | self.bit() as u32)
+    }
+
+    /// Reads a number of the given size and extends its sign.
+    fn extend(&mut self, size: u8) -> i32 {
+
+        if size == 0 || size > 16 {
+            return 0;
+        }
+
+        let value = self.bits(size as u32) as i32;
+
+        match value < 1 << (size - 1) {
+            true => value - (1 << size) + 1,
+            false => value,
+        }
+    }
+
+    /// Decodes a Huffman-coded symbol. Invalid codes decode as zero.
+    fn decode(&mut self, table: &HuffmanTable) -> u8 {
+
+        let mut code = 0i32;
+
+        for length in 1..=16 {
+            code = (code << 1) | self.bit() as i32;
+
+            if code <= table.max_code[length] {
+                return table.values.get((table.offset[length] + code) as usize).copied().unwrap_or(0);
+            }
+        }
+
+        0
+    }
+
+    /// Skips to the byte after the next restart marker.
+    fn restart(&mut self) {
+
+        self.bits = 0;
+
+        while self.position + 1 < self.data.len() {
+            let (byte, marker) = (self.data[self.position], self.data[self.position + 1]);
+            self.position += 1;
+
+            if byte == 0xFF && (0xD0..=0xD7).contains(&marker) {
+                self.position += 1;
+                break;
+            }
+
+            if byte == 0xFF && marker != 0 {
+                self.position -= 1;
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::decode_dct;
+
+    fn assert_close(decoded: &[u8], expected: &[u8]) {
+        assert_eq!(decoded.len(), expected.len());
+
+        let difference = decoded.iter().zip(expected).map(|(a, b)| (*a as i32 - *b as i32).abs()).max().unwrap();
+        assert!(difference <= 2, "largest difference {}", difference);
+    }
+
+    #[test]
+    fn should_decode_baseline_jpeg_with_restarts() {
+        let decoded = decode_dct(include_bytes!("../../tests/image/baseline.jpg"), None).unwrap();
+        assert_close(&decoded, include_bytes!("../../tests/image/baseline.raw"));
+    }
+
+    #[test]
+    fn should_decode_progressive_jpeg() {
+        let decoded = decode_dct(include_bytes!("../../tests/image/progressive.jpg"), None).unwrap();
+        assert_close(&decoded, include_bytes!("../../tests/image/progressive.raw"));
+
+        let decoded = decode_dct(include_bytes!("../../tests/image/gray.jpg"), None).unwrap();
+        assert_close(&decoded, include_bytes!("../../tests/image/gray.raw"));
+    }
+
+    #[test]
+    fn should_decode_ycck_jpeg() {
+        let decoded = decode_dct(include_bytes!("../../tests/image/cmyk.jpg"), None).unwrap();
+        assert_close(&decoded, include_bytes!("../../tests/image/cmyk.raw"));
+    }
+
+    #[test]
+    fn should_tolerate_truncated_jpeg() {
+        let data = include_bytes!("../../tests/image/baseline.jpg");
+
+        assert_eq!(decode_dct(&data[..data.len() - 100], None).unwrap().len(), 19 * 13 * 3);
+        assert!(decode_dct(b"not a jpeg", None).is_err());
+    }
+
+    #[test]
+    fn should_reject_frames_too_large_to_allocate() {
+        // Start of image, then a frame of 65535x65535 samples of three components.
+        let data = [
+            0xFF, 0xD8, 0xFF, 0xC0, 0x00, 0x11, 0x08, 0xFF, 0xFF, 0xFF, 0xFF, 0x03,
+            0x01, 0x44, 0x00, 0x02, 0x44, 0x00, 0x03, 0x44, 0x00, 0xFF, 0xD9,
+        ];
+
+        assert!(decode_dct(&data, None).unwrap_err().contains("too large"));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,84 @@
+use std::io::{Read, Write};
+
+use flate2::Compression;
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+
+/// Decodes data compressed with the zlib/deflate method (`/FlateDecode`).
+///
+/// Truncated or corrupt data is decoded as far as it goes: it is an
+/// error only when nothing at all can be decoded.
+pub fn decode_flate(data: &[u8]) -> Result<Vec<u8>, String> {
+
+    let mut output = Vec::new();
+
+    // The bytes decoded before an error stay in the output.
+    match ZlibDecoder::new(data).read_to_end(&mut output) {
+        Ok(_) => Ok(output),
+        Err(_) if !output.is_empty() => Ok(output),
+        Err(error) => Err(format!("Failed to decode FlateDecode stream: {}", error)),
+    }
+}
+
+/// Compresses data with the zlib/deflate method (`/FlateDecode`).
+pub fn encode_flate(data: &[u8]) -> Result<Vec<u8>, String> {
+
+    encode_flate_with_level(data, Compression::default().level())
+}
+
+/// Compresses data with the zlib/deflate method at the given level,
+/// from 0 (stored) to 9 (smallest).
+pub fn encode_flate_with_level(data: &[u8], level: u32) -> Result<Vec<u8>, String> {
+
+    let mut encoder = ZlibEncoder::new(Vec::with_capacity(data.len() / 2), Compression::new(level.min(9)));
+
+    encoder.write_all(data)
+        .and_then(|_| encoder.finish())
+        .map_err(|error| format!("Failed to encode FlateDecode stream: {}", error))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::Compression;
+    use flate2::write::ZlibEncoder;
+
+    use super::{decode_flate, encode_flate, encode_flate_with_level};
+
+    #[test]
+    fn should_decode_flate_data() {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"BT /F1 12 Tf ET").unwrap();
+
+        assert_eq!(decode_flate(&encoder.finish().unwrap()).unwrap(), b"BT /F1 12 Tf ET");
+        assert!(decode_flate(b"not compressed").is_err());
+    }
+
+    #[test]
+    fn should_encode_and_tolerate_truncated_data() {
+        let data = (0..4000).map(|index| format!("{} 0 Td (line) Tj\n", index)).collect::<String>();
+        let encoded = encode_flate(data.as_bytes()).unwrap();
+
+        assert_eq!(decode_flate(&encoded).unwrap(), data.as_bytes());
+
+        // Without the checksum, and without the end of the data.
+        assert_eq!(decode_flate(&encoded[..encoded.len() - 4]).unwrap(), data.as_bytes());
+
+        let truncated = decode_flate(&encoded[..encoded.len() / 2]).unwrap();
+        assert!(!truncated.is_empty() && data.as_bytes().starts_with(&truncated));
+    }
+
+    #[test]
+    fn should_encode_at_every_level() {
+        let data = (0..4000).map(|index| format!("{} 0 Td (line) Tj\n", index)).collect::<String>();
+
+        let stored = encode_flate_with_level(data.as_bytes(), 0).unwrap();
+        let smallest = encode_flate_with_level(data.as_bytes(), 9).unwrap();
+
+        assert!(stored.len() > data.len());
+        assert!(smallest.len() < data.len() / 4);
+        assert_eq!(decode_flate(&stored).unwrap(), data.as_bytes());
+        assert_eq!(decode_flate(&smallest).unwrap(), data.as_bytes());
+    }
+}
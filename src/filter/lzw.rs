@@ -0,0 +1,213 @@
+use std::collections::HashMap;
+
+/// Code that resets the code table.
+const CLEAR_TABLE: u16 = 256;
+
+/// Code that ends the data.
+const END_OF_DATA: u16 = 257;
+
+/// Size of the code table after a reset: the bytes and the two control codes.
+const FIRST_CODE: usize = 258;
+
+/// Maximum width of a code, in bits.
+const MAX_WIDTH: u32 = 12;
+
+/// Decodes data compressed with the LZW method (`/LZWDecode`).
+///
+/// With `early_change`, codes widen one code earlier, as in most PDF
+/// files (`/EarlyChange 1`). Truncated data is decoded as far as it goes.
+pub fn decode_lzw(data: &[u8], early_change: bool) -> Result<Vec<u8>, String> {
+
+    let early = early_change as usize;
+
+    let mut output = Vec::with_capacity(data.len() * 3);
+    let mut table: Vec<Vec<u8>> = (0..=255).map(|byte| vec![byte]).chain([vec![], vec![]]).collect();
+    let mut previous: Option<Vec<u8>> = None;
+    let mut width = 9;
+    let mut reader = BitReader { data, position: 0 };
+
+    while let Some(code) = reader.read(width) {
+
+        let code = code as usize;
+
+        match code as u16 {
+            CLEAR_TABLE => {
+                table.truncate(FIRST_CODE);
+                previous = None;
+                width = 9;
+                continue;
+            }
+            END_OF_DATA => break,
+            _ => {}
+        }
+
+        let entry = match (table.get(code), &previous) {
+            (Some(entry), _) if !(256..FIRST_CODE).contains(&code) => entry.clone(),
+            (None, Some(previous)) if code == table.len() => [previous.as_slice(), &previous[..1]].concat(),
+            _ => return Err(format!("Failed to decode LZWDecode stream: invalid code {}", code)),
+        };
+
+        output.extend_from_slice(&entry);
+
+        if let Some(previous) = previous
+            && table.len() < 1 << MAX_WIDTH
+        {
+            table.push([previous.as_slice(), &entry[..1]].concat());
+        }
+
+        if table.len() + early >= 1 << width && width < MAX_WIDTH {
+            width += 1;
+        }
+
+        previous = Some(entry);
+    }
+
+    Ok(output)
+}
+
+/// Compresses data with the LZW method (`/LZWDecode`).
+///
+/// The table is reset before it is full, so that codes never exceed
+/// twelve bits.
+pub fn encode_lzw(data: &[u8], early_change: bool) -> Vec<u8> {
+
+    let early = early_change as usize;
+
+    let mut writer = BitWriter::default();
+    let mut table: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut width = 9;
+    let mut current: Option<u16> = None;
+
+    writer.write(CLEAR_TABLE, width);
+
+    for byte in data.iter().copied() {
+
+        let Some(code) = current else {
+            current = Some(byte as u16);
+            continue;
+        };
+
+        if let Some(next) = table.get(&(code, byte)) {
+            current = Some(*next);
+            continue;
+        }
+
+        writer.write(code, width);
+
+        // The decoder adds each entry one code later, so its table is
+        // one entry smaller than this one when it reads the next code.
+        let size = FIRST_CODE + table.len();
+        table.insert((code, byte), size as u16);
+
+        if size + early >= 1 << width && width < MAX_WIDTH {
+            width += 1;
+        }
+
+        if size + 1 >= (1 << MAX_WIDTH) - 1 {
+            writer.write(CLEAR_TABLE, width);
+            table.clear();
+            width = 9;
+        }
+
+        current = Some(byte as u16);
+    }
+
+    if let Some(code) = current {
+        writer.write(code, width);
+
+        if FIRST_CODE + table.len() + early >= 1 << width && width < MAX_WIDTH {
+            width += 1;
+        }
+    }
+
+    writer.write(END_OF_DATA, width);
+    writer.finish()
+}
+
+/// Reads codes from the most significant bit first.
+struct BitReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl BitReader<'_> {
+
+    /// Reads a code of the given width, if enough bits are left.
+    fn read(&mut self, width: u32) -> Option<u16> {
+
+        if self.position + width as usize > self.data.len() * 8 {
+            return None;
+        }
+
+        let mut code = 0u16;
+
+        for _ in 0..width {
+            let bit = (self.data[self.position / 8] >> (7 - self.position % 8)) & 1;
+            code = (code << 1) | bit as u16;
+            self.position += 1;
+        }
+
+        Some(code)
+    }
+}
+
+/// Writes codes from the most significant bit first.
+#[derive(Default)]
+struct BitWriter {
+    output: Vec<u8>,
+    buffer: u32,
+    bits: u32,
+}
+
+impl BitWriter {
+
+    /// Writes a code of the given width.
+    fn write(&mut self, code: u16, width: u32) {
+
+        self.buffer = (self.buffer << width) | code as u32;
+        self.bits += width;
+
+        while self.bits >= 8 {
+            self.bits -= 8;
+            self.output.push((self.buffer >> self.bits) as u8);
+        }
+
+        self.buffer &= (1 << self.bits) - 1;
+    }
+
+    /// Returns the written bytes, padding the last one with zero bits.
+    fn finish(mut self) -> Vec<u8> {
+
+        if self.bits > 0 {
+            self.output.push((self.buffer << (8 - self.bits)) as u8);
+        }
+
+        self.output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_lzw, encode_lzw};
+
+    #[test]
+    fn should_decode_lzw_data() {
+        // The example of ISO 32000-2, 7.4.4.2.
+        let data = [0x80, 0x0B, 0x60, 0x50, 0x22, 0x0C, 0x0C, 0x85, 0x01];
+
+        assert_eq!(decode_lzw(&data, true).unwrap(), vec![45, 45, 45, 45, 45, 65, 45, 45, 45, 66]);
+        assert_eq!(decode_lzw(&data[..5], true).unwrap(), vec![45, 45, 45, 45, 45]);
+    }
+
+    #[test]
+    fn should_encode_lzw_data_reversibly() {
+        let data = (0..20000u32).map(|index| ((index * index) % 251 % 7) as u8 + b'a').collect::<Vec<_>>();
+
+        for early_change in [true, false] {
+            assert_eq!(decode_lzw(&encode_lzw(&data, early_change), early_change).unwrap(), data);
+        }
+
+        assert_eq!(decode_lzw(&encode_lzw(b"", true), true).unwrap(), b"");
+        assert_eq!(encode_lzw(&[45, 45, 45, 45, 45, 65, 45, 45, 45, 66], true), vec![0x80, 0x0B, 0x60, 0x50, 0x22, 0x0C, 0x0C, 0x85, 0x01]);
+    }
+}
\ No newline at end of file
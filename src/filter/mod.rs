@@ -0,0 +1,292 @@
+//! This module contains the stream filters used to decode stream payloads.
+mod ascii85;
+mod ascii_hex;
+mod ccitt;
+mod dct;
+mod flate;
+mod lzw;
+mod predictor;
+mod run_length;
+
+use crate::object::{Dicionary, Object, Stream};
+
+pub use crate::filter::ascii85::decode_ascii85;
+pub use crate::filter::ascii85::encode_ascii85;
+pub use crate::filter::ascii_hex::decode_ascii_hex;
+pub use crate::filter::ascii_hex::encode_ascii_hex;
+pub use crate::filter::ccitt::decode_ccitt;
+pub use crate::filter::ccitt::CcittParams;
+pub use crate::filter::dct::decode_dct;
+pub use crate::filter::flate::decode_flate;
+pub use crate::filter::flate::encode_flate;
+pub use crate::filter::flate::encode_flate_with_level;
+pub use crate::filter::lzw::decode_lzw;
+pub use crate::filter::lzw::encode_lzw;
+pub use crate::filter::predictor::{decode_predictor, encode_predictor, PredictorParams};
+pub use crate::filter::run_length::decode_run_length;
+pub use crate::filter::run_length::encode_run_length;
+
+/// Decodes the payload of the given stream.
+///
+/// The filters named by `/Filter` are applied in order, each with
+/// the matching `/DecodeParms` dictionary.
+pub fn decode_stream(stream: &Stream) -> Result<Vec<u8>, String> {
+
+    let mut data = stream.data().as_bytes().to_vec();
+
+    for (filter, params) in filter_chain(stream.dicionary())? {
+        data = decode(filter, &data, params)?;
+    }
+
+    Ok(data)
+}
+
+/// Creates a stream whose payload is the given data encoded through the
+/// filters of the dictionary.
+///
+/// The filters named by `/Filter` are applied in reverse order, so that
+/// decoding the stream gives back the data.
+pub fn encode_stream(dicionary: Dicionary, data: &[u8]) -> Result<Stream, String> {
+
+    let mut data = data.to_vec();
+
+    for (filter, params) in filter_chain(&dicionary)?.into_iter().rev() {
+        data = encode(filter, &data, params)?;
+    }
+
+    Ok(Stream::new(dicionary, data))
+}
+
+/// A filter name with its `/DecodeParms` dictionary, if any.
+type Filter<'a> = (&'a [u8], Option<&'a Dicionary>);
+
+/// Returns the filters named by `/Filter`, each with the matching
+/// `/DecodeParms` dictionary.
+///
+/// Parameters that are neither dictionaries nor null, such as indirect
+/// references left unresolved, are an error rather than being skipped.
+fn filter_chain<'a>(dicionary: &'a Dicionary) -> Result<Vec<Filter<'a>>, String> {
+
+    let filters = match dicionary.get(b"/Filter") {
+        None | Some(Object::Null(_)) => vec![],
+        Some(Object::Name(name)) => vec![name.as_bytes()],
+        Some(Object::Array(array)) => array.as_objects().iter()
+            .map(|filter| filter.as_name()
+                .map(|name| name.as_bytes())
+                .ok_or_else(|| "Filter names must be names".to_string()))
+            .collect::<Result<_, _>>()?,
+        Some(_) => return Err("Filters must be names or arrays of names".to_string()),
+    };
+
+    let params = |params: &'a Object| match params {
+        Object::Dicionary(params) => Ok(Some(params)),
+        Object::Null(_) => Ok(None),
+        _ => Err("Filter parameters must be dictionaries or null".to_string()),
+    };
+
+    let params = match dicionary.get(b"/DecodeParms") {
+        Some(Object::Array(array)) => array.as_objects().iter().map(params).collect::<Result<_, _>>()?,
+        Some(value) => vec![params(value)?],
+        None => vec![],
+    };
+
+    Ok(filters.into_iter().enumerate().map(|(index, filter)| (filter, params.get(index).copied().flatten())).collect())
+}
+
+/// Applies the named filter to the data.
+pub fn decode(filter: &[u8], data: &[u8], params: Option<&Dicionary>) -> Result<Vec<u8>, String> {
+
+    match filter {
+        b"/FlateDecode" | b"/Fl" => decode_predicted(decode_flate(data)?, params),
+        b"/LZWDecode" | b"/LZW" => decode_predicted(decode_lzw(data, early_change(params)?)?, params),
+        b"/ASCIIHexDecode" | b"/AHx" => decode_ascii_hex(data),
+        b"/ASCII85Decode" | b"/A85" => decode_ascii85(data),
+        b"/RunLengthDecode" | b"/RL" => decode_run_length(data),
+        b"/DCTDecode" | b"/DCT" => decode_dct(data, color_transform(params)?),
+        b"/CCITTFaxDecode" | b"/CCF" => decode_ccitt(data, &ccitt_params(params)?),
+        _ => Err(format!("Unsupported filter: {}", String::from_utf8_lossy(filter))),
+    }
+}
+
+/// Encodes the data with the named filter.
+pub fn encode(filter: &[u8], data: &[u8], params: Option<&Dicionary>) -> Result<Vec<u8>, String> {
+
+    match filter {
+        b"/FlateDecode" | b"/Fl" => encode_flate(&encode_predicted(data, params)?),
+        b"/LZWDecode" | b"/LZW" => Ok(encode_lzw(&encode_predicted(data, params)?, early_change(params)?)),
+        b"/ASCIIHexDecode" | b"/AHx" => Ok(encode_ascii_hex(data)),
+        b"/ASCII85Decode" | b"/A85" => Ok(encode_ascii85(data)),
+        b"/RunLengthDecode" | b"/RL" => Ok(encode_run_length(data)),
+        b"/DCTDecode" | b"/DCT" | b"/CCITTFaxDecode" | b"/CCF" => {
+            Err(format!("Encoding with {} is not supported", String::from_utf8_lossy(filter)))
+        }
+        _ => Err(format!("Unsupported filter: {}", String::from_utf8_lossy(filter))),
+    }
+}
+
+/// Reverses the predictor of the `/DecodeParms` dictionary, if any.
+fn decode_predicted(data: Vec<u8>, params: Option<&Dicionary>) -> Result<Vec<u8>, String> {
+
+    match params {
+        Some(params) => decode_predictor(data, &predictor_params(params)?),
+        None => Ok(data),
+    }
+}
+
+/// Applies the predictor of the `/DecodeParms` dictionary, if any.
+fn encode_predicted(data: &[u8], params: Option<&Dicionary>) -> Result<Vec<u8>, String> {
+
+    match params {
+        Some(params) => encode_predictor(data.to_vec(), &predictor_params(params)?),
+        None => Ok(data.to_vec()),
+    }
+}
+
+/// Reads the `/EarlyChange` entry of LZW parameters, which defaults to 1.
+fn early_change(params: Option<&Dicionary>) -> Result<bool, String> {
+
+    match params.and_then(|params| params.get(b"/EarlyChange")) {
+        Some(value) => value.as_i64().map(|value| value != 0).ok_or_else(|| "/EarlyChange must be an integer".to_string()),
+        None => Ok(true),
+    }
+}
+
+/// Reads the `/ColorTransform` entry of DCT parameters, if any.
+fn color_transform(params: Option<&Dicionary>) -> Result<Option<bool>, String> {
+
+    match params.and_then(|params| params.get(b"/ColorTransform")) {
+        Some(value) => value.as_i64().map(|value| Some(value != 0)).ok_or_else(|| "/ColorTransform must be an integer".to_string()),
+        None => Ok(None),
+    }
+}
+
+/// Reads the CCITT facsimile parameters of a `/DecodeParms` dictionary.
+fn ccitt_params(params: Option<&Dicionary>) -> Result<CcittParams, String> {
+
+    let defaults = CcittParams::default();
+
+    let Some(params) = params else {
+        return Ok(defaults);
+    };
+
+    let integer = |key: &[u8], default: i64| match params.get(key) {
+        Some(value) => value.as_i64().ok_or_else(|| format!("{} must be an integer", String::from_utf8_lossy(key))),
+        None => Ok(default),
+    };
+
+    let flag = |key: &[u8], default: bool| match params.get(key) {
+        Some(Object::Boolean(value)) => Ok(value.as_bool()),
+        Some(_) => Err(format!("{} must be a boolean", String::from_utf8_lossy(key))),
+        None => Ok(default),
+    };
+
+    let size = |key: &[u8], default: usize| {
+        usize::try_from(integer(key, default as i64)?).map_err(|_| format!("{} must not be negative", String::from_utf8_lossy(key)))
+    };
+
+    Ok(CcittParams {
+        k: integer(b"/K", defaults.k)?,
+        columns: size(b"/Columns", defaults.columns)?,
+        rows: size(b"/Rows", defaults.rows)?,
+        encoded_byte_align: flag(b"/EncodedByteAlign", defaults.encoded_byte_align)?,
+        black_is_1: flag(b"/BlackIs1", defaults.black_is_1)?,
+    })
+}
+
+/// Reads the predictor parameters of a `/DecodeParms` dictionary.
+fn predictor_params(params: &Dicionary) -> Result<PredictorParams, String> {
+
+    let defaults = PredictorParams::default();
+
+    let get = |key: &[u8], default: i64| match params.get(key) {
+        Some(value) => value.as_i64().ok_or_else(|| format!("{} must be an integer", String::from_utf8_lossy(key))),
+        None => Ok(default),
+    };
+
+    let positive = |key: &[u8], default: usize| {
+        let value = get(key, default as i64)?;
+
+        usize::try_from(value)
+            .ok()
+            .filter(|value| *value > 0)
+            .ok_or_else(|| format!("{} must be positive", String::from_utf8_lossy(key)))
+    };
+
+    let bits_per_component = positive(b"/BitsPerComponent", defaults.bits_per_component)?;
+
+    if !matches!(bits_per_component, 1 | 2 | 4 | 8 | 16) {
+        return Err(format!("/BitsPerComponent must be 1, 2, 4, 8 or 16, not {}", bits_per_component));
+    }
+
+    Ok(PredictorParams {
+        predictor: get(b"/Predictor", defaults.predictor)?,
+        colors: positive(b"/Colors", defaults.colors)?,
+        bits_per_component,
+        columns: positive(b"/Columns", defaults.columns)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::Compression;
+    use flate2::write::ZlibEncoder;
+
+    use super::{decode_stream, encode_stream};
+    use crate::object::{Object, Stream};
+
+    #[test]
+    fn should_decode_filter_chain_with_params() {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&[2, 1, 2, 2, 1, 1]).unwrap();
+
+        let dicionary = Object::parse(b"<</Filter [/FlateDecode] /DecodeParms [<</Predictor 12 /Columns 2>>]>>").unwrap();
+        let stream = Stream::new(dicionary.as_dicionary().unwrap().clone(), encoder.finish().unwrap());
+
+        assert_eq!(decode_stream(&stream).unwrap(), vec![1, 2, 2, 3]);
+    }
+
+    #[test]
+    fn should_encode_filter_chain_with_params() {
+        let dicionary = Object::parse(b"<</Filter /FlateDecode /DecodeParms <</Predictor 2 /Colors 3 /Columns 2>>>>").unwrap();
+        let stream = encode_stream(dicionary.as_dicionary().unwrap().clone(), &[10, 20, 30, 11, 22, 33, 0, 0, 0, 255, 255, 255]).unwrap();
+
+        assert_eq!(stream.dicionary().get(b"/Length").unwrap().as_i64(), Some(stream.data().len() as i64));
+        assert_eq!(decode_stream(&stream).unwrap(), vec![10, 20, 30, 11, 22, 33, 0, 0, 0, 255, 255, 255]);
+    }
+
+    #[test]
+    fn should_apply_legacy_filters_in_order() {
+        let dicionary = Object::parse(b"<</Filter [/A85 /LZW /RunLengthDecode /AHx] /DecodeParms [null <</EarlyChange 0>>]>>").unwrap();
+        let data = b"BT /F1 12 Tf 0 0 Td (legacy legacy legacy) Tj ET".repeat(20);
+        let stream = encode_stream(dicionary.as_dicionary().unwrap().clone(), &data).unwrap();
+
+        assert!(stream.data().as_bytes().ends_with(b"~>"));
+        assert_eq!(decode_stream(&stream).unwrap(), data);
+    }
+
+    #[test]
+    fn should_pass_through_unfiltered_and_reject_unknown() {
+        let stream = Stream::new(Object::parse(b"<<>>").unwrap().as_dicionary().unwrap().clone(), b"raw".to_vec());
+        assert_eq!(decode_stream(&stream).unwrap(), b"raw");
+
+        let stream = Stream::new(Object::parse(b"<</Filter /Unknown>>").unwrap().as_dicionary().unwrap().clone(), b"raw".to_vec());
+        assert!(decode_stream(&stream).is_err());
+
+        let stream = Stream::new(Object::parse(b"<</Filter /FlateDecode /DecodeParms <</Predictor 2 /BitsPerComponent 64>>>>").unwrap().as_dicionary().unwrap().clone(), b"raw".to_vec());
+        assert!(decode_stream(&stream).is_err());
+    }
+
+    #[test]
+    fn should_reject_unresolved_filters_and_params() {
+        let stream = Stream::new(Object::parse(b"<</Filter /FlateDecode /DecodeParms 5 0 R>>").unwrap().as_dicionary().unwrap().clone(), b"raw".to_vec());
+        assert!(decode_stream(&stream).is_err());
+
+        let stream = Stream::new(Object::parse(b"<</Filter [/FlateDecode] /DecodeParms [5 0 R]>>").unwrap().as_dicionary().unwrap().clone(), b"raw".to_vec());
+        assert!(decode_stream(&stream).is_err());
+
+        let stream = Stream::new(Object::parse(b"<</Filter 5 0 R>>").unwrap().as_dicionary().unwrap().clone(), b"raw".to_vec());
+        assert!(decode_stream(&stream).is_err());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,316 @@
+/// Parameters of a predictor function (`/DecodeParms`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PredictorParams {
+    /// The predictor algorithm (`/Predictor`).
+    pub predictor: i64,
+    /// Number of color components per sample (`/Colors`).
+    pub colors: usize,
+    /// Number of bits per color component (`/BitsPerComponent`).
+    pub bits_per_component: usize,
+    /// Number of samples in a row (`/Columns`).
+    pub columns: usize,
+}
+
+impl Default for PredictorParams {
+
+    fn default() -> Self {
+        Self { predictor: 1, colors: 1, bits_per_component: 8, columns: 1 }
+    }
+}
+
+impl PredictorParams {
+
+    /// Returns the number of bytes in a row of samples.
+    ///
+    /// Fails if the number of bits per component is not 1, 2, 4, 8 or
+    /// 16, or if the row is empty or too long to be addressed.
+    pub fn row_length(&self) -> Result<usize, String> {
+
+        if !matches!(self.bits_per_component, 1 | 2 | 4 | 8 | 16) {
+            return Err(format!("Unsupported bits per component: {}", self.bits_per_component));
+        }
+
+        let bits = self.colors.checked_mul(self.bits_per_component)
+            .and_then(|bits| bits.checked_mul(self.columns))
+            .ok_or_else(|| "Predictor row length is too large".to_string())?;
+
+        match bits.div_ceil(8) {
+            0 => Err("Predictor row length must not be zero".to_string()),
+            length => Ok(length),
+        }
+    }
+
+    /// Returns the number of bytes per complete pixel, at least one.
+    pub fn pixel_length(&self) -> usize {
+        (self.colors * self.bits_per_component).div_ceil(8).max(1)
+    }
+}
+
+/// Reverses the predictor applied to the given data.
+pub fn decode_predictor(data: Vec<u8>, params: &PredictorParams) -> Result<Vec<u8>, String> {
+
+    match params.predictor {
+        1 => Ok(data),
+        2 => decode_tiff(data, params),
+        10..=15 => decode_png(&data, params),
+        predictor => Err(format!("Unsupported predictor: {}", predictor)),
+    }
+}
+
+/// Applies a predictor to the given data.
+///
+/// PNG predictors 10 to 14 use the matching filter type on every row;
+/// predictor 15 chooses the filter type of each row.
+pub fn encode_predictor(data: Vec<u8>, params: &PredictorParams) -> Result<Vec<u8>, String> {
+
+    match params.predictor {
+        1 => Ok(data),
+        2 => encode_tiff(data, params),
+        10..=15 => encode_png(&data, params),
+        predictor => Err(format!("Unsupported predictor: {}", predictor)),
+    }
+}
+
+/// Reverses TIFF prediction, where each sample is stored as the
+/// difference from the same component of the previous pixel in the row.
+fn decode_tiff(mut data: Vec<u8>, params: &PredictorParams) -> Result<Vec<u8>, String> {
+
+    let row_length = params.row_length()?;
+
+    let bits = params.bits_per_component;
+    let samples = params.colors * params.columns;
+
+    // A truncated last row is decoded as far as it goes.
+    for row in data.chunks_mut(row_length) {
+        for index in params.colors..samples.min(row.len() * 8 / bits) {
+            let value = read_sample(row, index - params.colors, bits).wrapping_add(read_sample(row, index, bits));
+            write_sample(row, index, bits, value);
+        }
+    }
+
+    Ok(data)
+}
+
+/// Applies TIFF prediction.
+fn encode_tiff(mut data: Vec<u8>, params: &PredictorParams) -> Result<Vec<u8>, String> {
+
+    let row_length = params.row_length()?;
+
+    let bits = params.bits_per_component;
+    let samples = params.colors * params.columns;
+
+    for row in data.chunks_mut(row_length) {
+        // Samples are replaced from the end, so that each difference is
+        // taken with the original previous sample.
+        for index in (params.colors..samples.min(row.len() * 8 / bits)).rev() {
+            let value = read_sample(row, index, bits).wrapping_sub(read_sample(row, index - params.colors, bits));
+            write_sample(row, index, bits, value);
+        }
+    }
+
+    Ok(data)
+}
+
+/// Reads the sample at the given index of a row, for 1 to 16 bits per component.
+fn read_sample(row: &[u8], index: usize, bits: usize) -> u16 {
+
+    match bits {
+        8 => row[index] as u16,
+        16 => u16::from_be_bytes([row[index * 2], row[index * 2 + 1]]),
+        _ => {
+            let position = index * bits;
+            let shift = 8 - bits - position % 8;
+            ((row[position / 8] >> shift) as u16) & ((1 << bits) - 1)
+        }
+    }
+}
+
+/// Writes the sample at the given index of a row, keeping its low bits.
+fn write_sample(row: &mut [u8], index: usize, bits: usize, value: u16) {
+
+    match bits {
+        8 => row[index] = value as u8,
+        16 => row[index * 2..index * 2 + 2].copy_from_slice(&value.to_be_bytes()),
+        _ => {
+            let position = index * bits;
+            let shift = 8 - bits - position % 8;
+            let mask = (((1u16 << bits) - 1) as u8) << shift;
+            row[position / 8] = (row[position / 8] & !mask) | (((value as u8) << shift) & mask);
+        }
+    }
+}
+
+/// Reverses PNG prediction, where every row starts with its own filter type byte.
+fn decode_png(data: &[u8], params: &PredictorParams) -> Result<Vec<u8>, String> {
+
+    let row_length = params.row_length()?;
+    let pixel_length = params.pixel_length();
+
+    let mut output = Vec::with_capacity(data.len());
+    // Rows are never longer than the data.
+    let mut previous = vec![0u8; row_length.min(data.len())];
+
+    // A truncated last row is decoded as far as it goes.
+    for chunk in data.chunks(row_length + 1) {
+
+        let filter_type = chunk[0];
+        let mut row = chunk[1..].to_vec();
+
+        for index in 0..row.len() {
+
+            let left = if index >= pixel_length { row[index - pixel_length] } else { 0 };
+            let up = previous[index];
+            let up_left = if index >= pixel_length { previous[index - pixel_length] } else { 0 };
+
+            row[index] = match filter_type {
+                0 => row[index],
+                1 => row[index].wrapping_add(left),
+                2 => row[index].wrapping_add(up),
+                3 => row[index].wrapping_add(((left as u16 + up as u16) / 2) as u8),
+                4 => row[index].wrapping_add(paeth(left, up, up_left)),
+                _ => return Err(format!("Invalid PNG filter type: {}", filter_type)),
+            };
+        }
+
+        output.extend_from_slice(&row);
+        previous[..row.len()].copy_from_slice(&row);
+    }
+
+    Ok(output)
+}
+
+/// Applies PNG prediction, prefixing every row with its filter type byte.
+fn encode_png(data: &[u8], params: &PredictorParams) -> Result<Vec<u8>, String> {
+
+    let row_length = params.row_length()?;
+    let pixel_length = params.pixel_length();
+
+    let mut output = Vec::with_capacity(data.len() + data.len() / row_length + 1);
+    let mut previous: &[u8] = &vec![0u8; row_length.min(data.len())];
+
+    for row in data.chunks(row_length) {
+
+        let filter = |filter_type: u8| -> Vec<u8> {
+            (0..row.len()).map(|index| {
+                let left = if index >= pixel_length { row[index - pixel_length] } else { 0 };
+                let up = previous[index];
+                let up_left = if index >= pixel_length { previous[index - pixel_length] } else { 0 };
+
+                match filter_type {
+                    1 => row[index].wrapping_sub(left),
+                    2 => row[index].wrapping_sub(up),
+                    3 => row[index].wrapping_sub(((left as u16 + up as u16) / 2) as u8),
+                    4 => row[index].wrapping_sub(paeth(left, up, up_left)),
+                    _ => row[index],
+                }
+            }).collect()
+        };
+
+        // The optimum filter type is guessed with the usual heuristic:
+        // the smallest sum of the differences read as signed bytes.
+        let (filter_type, filtered) = match params.predictor {
+            15 => (0..=4)
+                .map(|filter_type| (filter_type, filter(filter_type)))
+                .min_by_key(|(_, filtered)| filtered.iter().map(|byte| (*byte as i8).unsigned_abs() as u64).sum::<u64>())
+                .unwrap(),
+            predictor => ((predictor - 10) as u8, filter((predictor - 10) as u8)),
+        };
+
+        output.push(filter_type);
+        output.extend_from_slice(&filtered);
+        previous = row;
+    }
+
+    Ok(output)
+}
+
+/// The Paeth predictor function of the PNG specification.
+fn paeth(left: u8, up: u8, up_left: u8) -> u8 {
+
+    let estimate = left as i16 + up as i16 - up_left as i16;
+
+    let distance_left = (estimate - left as i16).abs();
+    let distance_up = (estimate - up as i16).abs();
+    let distance_up_left = (estimate - up_left as i16).abs();
+
+    if distance_left <= distance_up && distance_left <= distance_up_left {
+        left
+    } else if distance_up <= distance_up_left {
+        up
+    } else {
+        up_left
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_predictor, encode_predictor, PredictorParams};
+
+    #[test]
+    fn should_decode_png_predictors() {
+        let params = PredictorParams { predictor: 12, colors: 1, bits_per_component: 8, columns: 3 };
+
+        // Rows: None, Sub, Up, Average, Paeth.
+        let data = vec![
+            0, 1, 2, 3,
+            1, 1, 1, 1,
+            2, 1, 1, 1,
+            3, 2, 2, 2,
+            4, 1, 1, 1,
+        ];
+
+        assert_eq!(
+            decode_predictor(data, &params).unwrap(),
+            vec![
+                1, 2, 3,
+                1, 2, 3,
+                2, 3, 4,
+                3, 5, 6,
+                4, 6, 7,
+            ]
+        );
+    }
+
+    #[test]
+    fn should_decode_tiff_predictors() {
+        let params = PredictorParams { predictor: 2, colors: 2, bits_per_component: 8, columns: 3 };
+        assert_eq!(decode_predictor(vec![1, 2, 1, 1, 255, 0, 5], &params).unwrap(), vec![1, 2, 2, 3, 1, 3, 5]);
+
+        let params = PredictorParams { predictor: 2, colors: 1, bits_per_component: 4, columns: 4 };
+        assert_eq!(decode_predictor(vec![0x31, 0xF2], &params).unwrap(), vec![0x34, 0x35]);
+
+        let params = PredictorParams { predictor: 2, colors: 1, bits_per_component: 16, columns: 2 };
+        assert_eq!(decode_predictor(vec![0x01, 0xFF, 0x00, 0x02], &params).unwrap(), vec![0x01, 0xFF, 0x02, 0x01]);
+    }
+
+    #[test]
+    fn should_encode_predictors_reversibly() {
+        let data = (0..60u8).map(|value| value.wrapping_mul(37)).collect::<Vec<_>>();
+
+        for predictor in [2, 10, 11, 12, 13, 14, 15] {
+            for (colors, bits_per_component) in [(3, 8), (1, 1), (2, 4), (1, 16)] {
+                let params = PredictorParams { predictor, colors, bits_per_component, columns: 5 };
+                let encoded = encode_predictor(data.clone(), &params).unwrap();
+
+                assert_eq!(decode_predictor(encoded, &params).unwrap(), data, "{:?}", params);
+            }
+        }
+    }
+
+    #[test]
+    fn should_reject_invalid_predictors() {
+        let params = PredictorParams { predictor: 7, ..PredictorParams::default() };
+        assert!(decode_predictor(vec![0], &params).is_err());
+
+        let params = PredictorParams { predictor: 10, ..PredictorParams::default() };
+        assert!(decode_predictor(vec![9, 0], &params).is_err());
+
+        for bits_per_component in [3, 12, 32] {
+            let params = PredictorParams { predictor: 2, bits_per_component, ..PredictorParams::default() };
+            assert!(decode_predictor(vec![0; 8], &params).is_err());
+        }
+
+        let params = PredictorParams { predictor: 12, colors: usize::MAX / 2, bits_per_component: 8, columns: 3 };
+        assert!(decode_predictor(vec![0; 8], &params).is_err());
+    }
+}
\ No newline at end of file
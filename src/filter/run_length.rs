@@ -0,0 +1,87 @@
+/// Code that ends run-length encoded data.
+const END_OF_DATA: u8 = 128;
+
+/// Maximum length of a run or of a literal sequence.
+const MAX_RUN: usize = 128;
+
+/// Decodes run-length encoded data (`/RunLengthDecode`).
+///
+/// Truncated data is decoded as far as it goes.
+pub fn decode_run_length(data: &[u8]) -> Result<Vec<u8>, String> {
+
+    let mut output = Vec::with_capacity(data.len() * 2);
+    let mut position = 0;
+
+    while let Some(length) = data.get(position).copied() {
+        match length {
+            END_OF_DATA => break,
+            0..=127 => {
+                let end = (position + 2 + length as usize).min(data.len());
+                output.extend_from_slice(&data[position + 1..end]);
+                position = end;
+            }
+            _ => {
+                if let Some(byte) = data.get(position + 1) {
+                    output.resize(output.len() + 257 - length as usize, *byte);
+                }
+
+                position += 2;
+            }
+        }
+    }
+
+    Ok(output)
+}
+
+/// Encodes data with run-length encoding (`/RunLengthDecode`).
+///
+/// Runs of two or more equal bytes are repeated, other bytes are copied
+/// literally.
+pub fn encode_run_length(data: &[u8]) -> Vec<u8> {
+
+    let mut output = Vec::with_capacity(data.len() + data.len() / MAX_RUN + 2);
+    let mut literal_start = 0;
+    let mut position = 0;
+
+    let flush = |output: &mut Vec<u8>, literal: &[u8]| {
+        for chunk in literal.chunks(MAX_RUN) {
+            output.push((chunk.len() - 1) as u8);
+            output.extend_from_slice(chunk);
+        }
+    };
+
+    while position < data.len() {
+
+        let run = data[position..].iter().take(MAX_RUN).take_while(|byte| **byte == data[position]).count();
+
+        if run >= 2 {
+            flush(&mut output, &data[literal_start..position]);
+            output.extend_from_slice(&[(257 - run) as u8, data[position]]);
+            position += run;
+            literal_start = position;
+        } else {
+            position += 1;
+        }
+    }
+
+    flush(&mut output, &data[literal_start..]);
+    output.push(END_OF_DATA);
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_run_length, encode_run_length};
+
+    #[test]
+    fn should_decode_and_encode_runs() {
+        assert_eq!(decode_run_length(&[2, b'a', b'b', b'c', 253, b'x', 128, 0, b'z']).unwrap(), b"abcxxxx");
+        assert_eq!(decode_run_length(&[4, b'a', b'b']).unwrap(), b"ab");
+
+        assert_eq!(encode_run_length(b"abcxxxx"), vec![2, b'a', b'b', b'c', 253, b'x', 128]);
+
+        let data = (0..300).map(|index| if index % 50 < 20 { 7 } else { index as u8 }).collect::<Vec<_>>();
+        assert_eq!(decode_run_length(&encode_run_length(&data)).unwrap(), data);
+    }
+}
\ No newline at end of file
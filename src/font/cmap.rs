@@ -0,0 +1,402 @@
+use std::collections::HashMap;
+
+use crate::content::{parse_operations, Operator};
+use crate::document::Document;
+use crate::object::Object;
+
+/// Maximum depth of `/UseCMap` chains.
+const MAX_USE_CMAP: usize = 8;
+
+/// Unicode form of the character codes of a predefined Unicode CMap
+/// (i.e `UniJIS-UCS2-H`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnicodeForm {
+    /// Two-byte UCS-2 codes.
+    Ucs2,
+    /// UTF-16BE codes of two or four bytes.
+    Utf16,
+    /// UTF-8 codes of one to four bytes.
+    Utf8,
+    /// Four-byte UTF-32BE codes.
+    Utf32,
+}
+
+/// A CMap mapping the character codes of a composite font to CIDs
+/// (ISO 32000-2, 9.7.5).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CMap {
+    /// Code space ranges: the lowest and highest codes, of equal length.
+    codespace: Vec<(Vec<u8>, Vec<u8>)>,
+    /// CIDs by character code (`cidchar`).
+    chars: HashMap<u32, u32>,
+    /// Ranges of codes (`cidrange`): the first and last codes, and the
+    /// CID of the first code.
+    ranges: Vec<(u32, u32, u32)>,
+    /// Whether every code is its own CID (`Identity-H` and `Identity-V`).
+    identity: bool,
+    /// Whether the CMap is for vertical writing (`/WMode 1`).
+    vertical: bool,
+    /// The Unicode form of the codes of a predefined Unicode CMap.
+    unicode: Option<UnicodeForm>,
+    /// Code ranges of the half-width glyphs of a predefined legacy CMap
+    /// whose CIDs are unknown.
+    half_width: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
+impl CMap {
+
+    /// Creates the `Identity-H` or `Identity-V` CMap.
+    pub fn identity(vertical: bool) -> Self {
+        Self {
+            codespace: vec![(vec![0x00, 0x00], vec![0xFF, 0xFF])],
+            identity: true,
+            vertical,
+            ..Self::default()
+        }
+    }
+
+    /// Creates a predefined CMap whose mapping is bundled: `Identity-H`
+    /// or `Identity-V`.
+    ///
+    /// The mappings of the other predefined CMaps (i.e `UniJIS-UCS2-H`
+    /// or `90ms-RKSJ-H`) are not bundled, so `None` is returned for them;
+    /// see `unmapped`.
+    pub fn predefined(name: &str) -> Option<Self> {
+
+        match name {
+            "Identity-H" | "Identity-V" => Some(Self::identity(name.ends_with("-V"))),
+            _ => None,
+        }
+    }
+
+    /// Creates a predefined CMap whose mapping is not bundled, from its name.
+    ///
+    /// Only the code space, the writing mode, the half-width codes and
+    /// the text of the codes of the Unicode CMaps are known: `cid`
+    /// returns `None` for every code.
+    pub fn unmapped(name: &str) -> Option<Self> {
+
+        let vertical = name.ends_with("-V");
+
+        if !name.ends_with("-H") && !vertical {
+            return None;
+        }
+
+        let ranges: &[(&[u8], &[u8])] = if name.starts_with("Uni") {
+            if name.contains("UCS2") {
+                &[(&[0x00, 0x00], &[0xFF, 0xFF])]
+            } else if name.contains("UTF16") {
+                &[(&[0x00, 0x00], &[0xD7, 0xFF]), (&[0xD8, 0x00, 0xDC, 0x00], &[0xDB, 0xFF, 0xDF, 0xFF]), (&[0xE0, 0x00], &[0xFF, 0xFF])]
+            } else if name.contains("UTF8") {
+                &[
+                    (&[0x00], &[0x7F]),
+                    (&[0xC0, 0x80], &[0xDF, 0xBF]),
+                    (&[0xE0, 0x80, 0x80], &[0xEF, 0xBF, 0xBF]),
+                    (&[0xF0, 0x80, 0x80, 0x80], &[0xF7, 0xBF, 0xBF, 0xBF]),
+                ]
+            } else if name.contains("UTF32") {
+                &[(&[0x00, 0x00, 0x00, 0x00], &[0x00, 0x10, 0xFF, 0xFF])]
+            } else {
+                return None;
+            }
+        } else if name.contains("RKSJ") {
+            &[(&[0x00], &[0x80]), (&[0x81, 0x40], &[0x9F, 0xFC]), (&[0xA0], &[0xDF]), (&[0xE0, 0x40], &[0xFC, 0xFC])]
+        } else if name.contains("GBK") || name.contains("UHC") || name.contains("B5") {
+            &[(&[0x00], &[0x80]), (&[0x81, 0x40], &[0xFE, 0xFE])]
+        } else if name.contains("EUC") {
+            &[(&[0x00], &[0x80]), (&[0x8E, 0xA0], &[0x8E, 0xDF]), (&[0xA1, 0xA1], &[0xFE, 0xFE])]
+        } else if matches!(name.split('-').next(), Some("H" | "V" | "Add" | "Ext" | "Hojo" | "Hankaku" | "Hiragana" | "Katakana" | "Roman")) {
+            &[(&[0x21, 0x21], &[0x7E, 0x7E])]
+        } else {
+            return None;
+        };
+
+        // Single-byte codes are ASCII or half-width katakana, like the
+        // two-byte EUC-JP codes starting with 0x8E.
+        let half_width = ranges.iter()
+            .filter(|(low, _)| low.len() == 1 || (name.contains("EUC") && low[0] == 0x8E))
+            .map(|(low, high)| (low.to_vec(), high.to_vec()))
+            .collect();
+
+        let unicode = match name.starts_with("Uni") {
+            true if name.contains("UCS2") => Some(UnicodeForm::Ucs2),
+            true if name.contains("UTF16") => Some(UnicodeForm::Utf16),
+            true if name.contains("UTF8") => Some(UnicodeForm::Utf8),
+            true => Some(UnicodeForm::Utf32),
+            false => None,
+        };
+
+        Some(Self {
+            codespace: ranges.iter().map(|(low, high)| (low.to_vec(), high.to_vec())).collect(),
+            vertical,
+            unicode,
+            half_width,
+            ..Self::default()
+        })
+    }
+
+    /// Reads the `/Encoding` entry of a composite font: the name of a
+    /// predefined CMap, or an embedded CMap stream.
+    ///
+    /// An unknown or unreadable CMap is read as `Identity-H`.
+    pub fn read(document: &Document, encoding: &Object) -> Result<Self, String> {
+        Self::read_chain(document, encoding, 0)
+    }
+
+    /// Reads a CMap, following its `/UseCMap` entry.
+    fn read_chain(document: &Document, encoding: &Object, depth: usize) -> Result<Self, String> {
+
+        let stream = match document.resolve(encoding)? {
+            Object::Name(name) => {
+                let name = String::from_utf8_lossy(&name.as_bytes()[1..]).into_owned();
+                return Ok(Self::predefined(&name).or_else(|| Self::unmapped(&name)).unwrap_or_else(|| Self::identity(false)));
+            }
+            Object::Stream(stream) => stream,
+            _ => return Ok(Self::identity(false)),
+        };
+
+        let Some(mut cmap) = stream.decode().ok().and_then(|bytes| CMap::parse(&bytes).ok()) else {
+            return Ok(Self::identity(false));
+        };
+
+        let dicionary = stream.dicionary();
+
+        if let Some(mode) = dicionary.get(b"/WMode").map(|mode| document.resolve(mode)).transpose()?.and_then(|mode| mode.as_i64()) {
+            cmap.vertical = mode == 1;
+        }
+
+        if let Some(parent) = dicionary.get(b"/UseCMap") && depth < MAX_USE_CMAP {
+            cmap.use_cmap(Self::read_chain(document, parent, depth + 1)?);
+        }
+
+        Ok(cmap)
+    }
+
+    /// Parses a decoded CMap stream.
+    ///
+    /// `usecmap` operators naming predefined CMaps are applied; other
+    /// parent CMaps are ignored.
+    pub fn parse(bytes: &[u8]) -> Result<Self, String> {
+
+        let mut cmap = Self::default();
+
+        for operation in parse_operations(bytes)? {
+
+            let Operator::Other(operator) = operation.operator() else {
+                continue;
+            };
+
+            let operands = operation.operands();
+
+            match operator.as_slice() {
+                b"endcodespacerange" => {
+                    for range in operands.chunks_exact(2) {
+                        if let (Some(low), Some(high)) = (range[0].as_string_bytes(), range[1].as_string_bytes())
+                            && low.len() == high.len()
+                            && (1..=4).contains(&low.len())
+                        {
+                            cmap.codespace.push((low, high));
+                        }
+                    }
+                }
+                b"endcidchar" => {
+                    for pair in operands.chunks_exact(2) {
+                        if let (Some(code), Some(cid)) = (code(&pair[0]), cid(&pair[1])) {
+                            cmap.chars.insert(code, cid);
+                        }
+                    }
+                }
+                b"endcidrange" => {
+                    for range in operands.chunks_exact(3) {
+                        if let (Some(first), Some(last), Some(cid)) = (code(&range[0]), code(&range[1]), cid(&range[2])) {
+                            cmap.ranges.push((first, last, cid));
+                        }
+                    }
+                }
+                b"def" if operation.name_operand(0).is_some_and(|name| name.as_bytes() == b"/WMode") => {
+                    cmap.vertical = operands.get(1).and_then(Object::as_i64) == Some(1);
+                }
+                b"usecmap" => {
+                    let name = operation.name_operand(0).map(|name| String::from_utf8_lossy(&name.as_bytes()[1..]).into_owned());
+
+                    if let Some(parent) = name.and_then(|name| Self::predefined(&name).or_else(|| Self::unmapped(&name))) {
+                        cmap.use_cmap(parent);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(cmap)
+    }
+
+    /// Adds the mappings of a parent CMap, which the CMap's own mappings override.
+    fn use_cmap(&mut self, parent: CMap) {
+
+        self.codespace.extend(parent.codespace);
+
+        for (code, cid) in parent.chars {
+            self.chars.entry(code).or_insert(cid);
+        }
+
+        self.ranges.splice(0..0, parent.ranges);
+        self.identity |= parent.identity;
+        self.unicode = self.unicode.or(parent.unicode);
+        self.half_width.extend(parent.half_width);
+    }
+
+    /// Returns `true` if the CMap is for vertical writing.
+    pub fn is_vertical(&self) -> bool {
+        self.vertical
+    }
+
+    /// Splits a string into character codes, following the code space ranges.
+    ///
+    /// Bytes matching no range are read as a code of the length of the
+    /// shortest range starting with a matching byte, or of one byte.
+    pub fn codes(&self, bytes: &[u8]) -> Vec<(u32, usize)> {
+
+        let mut codes = Vec::new();
+        let mut position = 0;
+
+        while position < bytes.len() {
+
+            let rest = &bytes[position..];
+
+            let length = (1..=4.min(rest.len()))
+                .find(|length| self.codespace.iter().any(|(low, high)| matches_range(&rest[..*length], low, high)))
+                .or_else(|| {
+                    self.codespace.iter()
+                        .filter(|(low, high)| (low[0]..=high[0]).contains(&rest[0]))
+                        .map(|(low, _)| low.len())
+                        .min()
+                })
+                .unwrap_or(1)
+                .min(rest.len());
+
+            let code = rest[..length].iter().fold(0, |code, byte| (code << 8) | *byte as u32);
+
+            codes.push((code, length));
+            position += length;
+        }
+
+        codes
+    }
+
+    /// Returns the CID of a character code, if it is known.
+    pub fn cid(&self, code: u32) -> Option<u32> {
+
+        if let Some(cid) = self.chars.get(&code) {
+            return Some(*cid);
+        }
+
+        // Later ranges override earlier ones.
+        if let Some((first, _, cid)) = self.ranges.iter().rev().find(|(first, last, _)| (*first..=*last).contains(&code)) {
+            return Some(cid + (code - first));
+        }
+
+        self.identity.then_some(code)
+    }
+
+    /// Returns `true` if a code of a predefined CMap whose CIDs are unknown
+    /// selects a half-width glyph: ASCII and half-width forms.
+    pub fn is_half_width(&self, code: u32, length: usize) -> bool {
+
+        if self.unicode.is_some() {
+            return self.text(code, length)
+                .and_then(|text| text.chars().next())
+                .is_some_and(|character| matches!(character, ' '..='~' | '\u{ff61}'..='\u{ffdc}' | '\u{ffe8}'..='\u{ffee}'));
+        }
+
+        let bytes = &code.to_be_bytes()[4 - length.min(4)..];
+
+        self.half_width.iter().any(|(low, high)| matches_range(bytes, low, high))
+    }
+
+    /// Returns the text of a character code of a predefined Unicode CMap.
+    pub fn text(&self, code: u32, length: usize) -> Option<String> {
+
+        let bytes = &code.to_be_bytes()[4 - length.min(4)..];
+
+        match self.unicode? {
+            UnicodeForm::Ucs2 | UnicodeForm::Utf32 => char::from_u32(code).map(String::from),
+            UnicodeForm::Utf16 => {
+                let units = bytes.chunks(2).map(|unit| u16::from_be_bytes([unit[0], *unit.get(1).unwrap_or(&0)])).collect::<Vec<_>>();
+                String::from_utf16(&units).ok()
+            }
+            UnicodeForm::Utf8 => std::str::from_utf8(bytes).ok().map(str::to_string),
+        }
+    }
+}
+
+/// Returns `true` if a code lies within a code space range, byte by byte.
+fn matches_range(code: &[u8], low: &[u8], high: &[u8]) -> bool {
+    code.len() == low.len() && code.iter().zip(low.iter().zip(high)).all(|(byte, (low, high))| (low..=high).contains(&byte))
+}
+
+/// Returns a character code from a hexadecimal string of up to four bytes.
+fn code(object: &Object) -> Option<u32> {
+
+    let bytes = object.as_string_bytes()?;
+
+    (bytes.len() <= 4).then(|| bytes.iter().fold(0, |code, byte| (code << 8) | *byte as u32))
+}
+
+/// Returns a CID operand.
+fn cid(object: &Object) -> Option<u32> {
+    object.as_i64().and_then(|cid| u32::try_from(cid).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CMap;
+
+    #[test]
+    fn should_split_codes_by_codespace() {
+        assert!(CMap::predefined("90ms-RKSJ-H").is_none());
+        assert_eq!(CMap::predefined("Identity-V"), Some(CMap::identity(true)));
+
+        let cmap = CMap::unmapped("90ms-RKSJ-H").unwrap();
+
+        assert_eq!(cmap.codes(b"A\x82\xa0\xb1"), vec![(0x41, 1), (0x82A0, 2), (0xB1, 1)]);
+        assert_eq!(cmap.cid(0x41), None);
+        assert!(!cmap.is_vertical());
+        assert!(cmap.is_half_width(0x41, 1) && cmap.is_half_width(0xB1, 1));
+        assert!(!cmap.is_half_width(0x8EA0, 2));
+
+        let cmap = CMap::unmapped("EUC-H").unwrap();
+        assert!(cmap.is_half_width(0x8EB1, 2));
+        assert!(!cmap.is_half_width(0xA4A2, 2));
+    }
+
+    #[test]
+    fn should_decode_unicode_cmaps() {
+        let cmap = CMap::unmapped("UniJIS-UTF16-V").unwrap();
+        let codes = cmap.codes(b"\x65\xe5\xd8\x3d\xde\x00");
+
+        assert!(cmap.is_vertical());
+        assert_eq!(codes, vec![(0x65E5, 2), (0xD83DDE00, 4)]);
+        assert_eq!(cmap.text(codes[0].0, codes[0].1).as_deref(), Some("\u{65e5}"));
+        assert_eq!(cmap.text(codes[1].0, codes[1].1).as_deref(), Some("\u{1f600}"));
+        assert!(!cmap.is_half_width(codes[0].0, codes[0].1));
+        assert!(cmap.is_half_width(0x0031, 2) && cmap.is_half_width(0xFF76, 2));
+        assert_eq!(CMap::unmapped("UniGB-UCS2-H").unwrap().text(0x4E2D, 2).as_deref(), Some("\u{4e2d}"));
+    }
+
+    #[test]
+    fn should_parse_embedded_cmaps() {
+        let cmap = CMap::parse(b"/CIDInit /ProcSet findresource begin 12 dict begin begincmap
+/CMapName /Custom def /WMode 1 def
+2 begincodespacerange <00> <7F> <8000> <FFFF> endcodespacerange
+1 begincidchar <41> 500 endcidchar
+1 begincidrange <8000> <80FF> 1000 endcidrange
+endcmap CMapName currentdict /CMap defineresource pop end end").unwrap();
+
+        assert!(cmap.is_vertical());
+        assert_eq!(cmap.codes(b"A\x80\x10"), vec![(0x41, 1), (0x8010, 2)]);
+        assert_eq!(cmap.cid(0x41), Some(500));
+        assert_eq!(cmap.cid(0x8010), Some(1016));
+        assert_eq!(cmap.cid(0x42), None);
+        assert_eq!(CMap::identity(false).cid(0x1234), Some(0x1234));
+    }
+}
\ No newline at end of file
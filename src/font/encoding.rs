@@ -0,0 +1,295 @@
+use crate::document::Document;
+use crate::font::glyph_to_unicode;
+use crate::object::Object;
+
+/// A predefined encoding of simple fonts (ISO 32000-2, Annex D).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BaseEncoding {
+    /// The built-in encoding of Latin-text Type 1 fonts, `/StandardEncoding`.
+    Standard,
+    /// The Windows code page 1252 encoding, `/WinAnsiEncoding`.
+    WinAnsi,
+    /// The Mac OS standard encoding for Latin text, `/MacRomanEncoding`.
+    MacRoman,
+    /// The encoding of text strings outside content streams.
+    PdfDoc,
+}
+
+impl BaseEncoding {
+
+    /// Creates a `BaseEncoding` from its name (i.e `/WinAnsiEncoding`).
+    pub fn from_name(name: &[u8]) -> Option<Self> {
+        match name {
+            b"/StandardEncoding" => Some(BaseEncoding::Standard),
+            b"/WinAnsiEncoding" => Some(BaseEncoding::WinAnsi),
+            b"/MacRomanEncoding" => Some(BaseEncoding::MacRoman),
+            b"/PDFDocEncoding" => Some(BaseEncoding::PdfDoc),
+            _ => None,
+        }
+    }
+
+    /// Returns the glyph name of a character code, if the code is defined.
+    pub fn glyph_name(&self, code: u8) -> Option<&'static str> {
+
+        let table = match self {
+            BaseEncoding::Standard => &STANDARD,
+            BaseEncoding::WinAnsi => &WIN_ANSI,
+            BaseEncoding::MacRoman => &MAC_ROMAN,
+            BaseEncoding::PdfDoc => &PDF_DOC,
+        };
+
+        Some(table[code as usize]).filter(|name| !name.is_empty())
+    }
+}
+
+/// The encoding of a simple font: glyph names by character code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Encoding {
+    /// Glyph names by character code.
+    names: Vec<Option<String>>,
+}
+
+impl Encoding {
+
+    /// Creates a new `Encoding` from a predefined encoding.
+    pub fn new(base: BaseEncoding) -> Self {
+        Self {
+            names: (0..=255).map(|code| base.glyph_name(code).map(str::to_string)).collect(),
+        }
+    }
+
+    /// Creates an `Encoding` without any defined code, for fonts using
+    /// their built-in encoding.
+    pub fn empty() -> Self {
+        Self { names: vec![None; 256] }
+    }
+
+    /// Reads the `/Encoding` entry of a font: a predefined encoding name,
+    /// or a dictionary with a `/BaseEncoding` and `/Differences`.
+    ///
+    /// `default` is used when the entry names no base encoding.
+    pub fn read(document: &Document, encoding: &Object, default: Encoding) -> Result<Self, String> {
+
+        match document.resolve(encoding)? {
+            Object::Name(name) => Ok(BaseEncoding::from_name(name.as_bytes()).map_or(default, Encoding::new)),
+            Object::Dicionary(dicionary) => {
+                let base = dicionary.get(b"/BaseEncoding")
+                    .map(|base| document.resolve(base))
+                    .transpose()?
+                    .and_then(|base| base.as_name().and_then(|name| BaseEncoding::from_name(name.as_bytes())));
+
+                let mut encoding = base.map_or(default, Encoding::new);
+
+                if let Some(differences) = dicionary.get(b"/Differences").map(|differences| document.resolve(differences)).transpose()? {
+                    encoding.apply_differences(differences.as_array().map(|array| array.as_objects()).unwrap_or_default());
+                }
+
+                Ok(encoding)
+            }
+            _ => Ok(default),
+        }
+    }
+
+    /// Applies a `/Differences` array (i.e `[39 /quotesingle 96 /grave /a]`):
+    /// each code is followed by the names of consecutive codes.
+    pub fn apply_differences(&mut self, differences: &[Object]) {
+
+        let mut code = None;
+
+        for difference in differences {
+            match difference {
+                Object::Name(name) => {
+                    if let Some(index) = code.filter(|index| *index < 256) {
+                        self.names[index] = Some(String::from_utf8_lossy(&name.as_bytes()[1..]).into_owned());
+                        code = Some(index + 1);
+                    }
+                }
+                _ => code = difference.as_i64().and_then(|index| usize::try_from(index).ok()),
+            }
+        }
+    }
+
+    /// Returns the glyph name of a character code, if the code is defined.
+    pub fn glyph_name(&self, code: u8) -> Option<&str> {
+        self.names[code as usize].as_deref()
+    }
+
+    /// Returns the text of a character code, from the Adobe Glyph List.
+    pub fn to_unicode(&self, code: u8) -> Option<String> {
+        self.glyph_name(code).and_then(glyph_to_unicode)
+    }
+}
+
+/// StandardEncoding (ISO 32000-2, Annex D).
+const STANDARD: [&str; 256] = [
+    "", "", "", "", "", "", "", "", // 0x00
+    "", "", "", "", "", "", "", "", // 0x08
+    "", "", "", "", "", "", "", "", // 0x10
+    "", "", "", "", "", "", "", "", // 0x18
+    "space", "exclam", "quotedbl", "numbersign", "dollar", "percent", "ampersand", "quoteright", // 0x20
+    "parenleft", "parenright", "asterisk", "plus", "comma", "hyphen", "period", "slash", // 0x28
+    "zero", "one", "two", "three", "four", "five", "six", "seven", // 0x30
+    "eight", "nine", "colon", "semicolon", "less", "equal", "greater", "question", // 0x38
+    "at", "A", "B", "C", "D", "E", "F", "G", // 0x40
+    "H", "I", "J", "K", "L", "M", "N", "O", // 0x48
+    "P", "Q", "R", "S", "T", "U", "V", "W", // 0x50
+    "X", "Y", "Z", "bracketleft", "backslash", "bracketright", "asciicircum", "underscore", // 0x58
+    "quoteleft", "a", "b", "c", "d", "e", "f", "g", // 0x60
+    "h", "i", "j", "k", "l", "m", "n", "o", // 0x68
+    "p", "q", "r", "s", "t", "u", "v", "w", // 0x70
+    "x", "y", "z", "braceleft", "bar", "braceright", "asciitilde", "", // 0x78
+    "", "", "", "", "", "", "", "", // 0x80
+    "", "", "", "", "", "", "", "", // 0x88
+    "", "", "", "", "", "", "", "", // 0x90
+    "", "", "", "", "", "", "", "", // 0x98
+    "", "exclamdown", "cent", "sterling", "fraction", "yen", "florin", "section", // 0xa0
+    "currency", "quotesingle", "quotedblleft", "guillemotleft", "guilsinglleft", "guilsinglright", "fi", "fl", // 0xa8
+    "", "endash", "dagger", "daggerdbl", "periodcentered", "", "paragraph", "bullet", // 0xb0
+    "quotesinglbase", "quotedblbase", "quotedblright", "guillemotright", "ellipsis", "perthousand", "", "questiondown", // 0xb8
+    "", "grave", "acute", "circumflex", "tilde", "macron", "breve", "dotaccent", // 0xc0
+    "dieresis", "", "ring", "cedilla", "", "hungarumlaut", "ogonek", "caron", // 0xc8
+    "emdash", "", "", "", "", "", "", "", // 0xd0
+    "", "", "", "", "", "", "", "", // 0xd8
+    "", "AE", "", "ordfeminine", "", "", "", "", // 0xe0
+    "Lslash", "Oslash", "OE", "ordmasculine", "", "", "", "", // 0xe8
+    "", "ae", "", "", "", "dotlessi", "", "", // 0xf0
+    "lslash", "oslash", "oe", "germandbls", "", "", "", "", // 0xf8
+];
+
+/// WinAnsiEncoding (ISO 32000-2, Annex D).
+const WIN_ANSI: [&str; 256] = [
+    "", "", "", "", "", "", "", "", // 0x00
+    "", "", "", "", "", "", "", "", // 0x08
+    "", "", "", "", "", "", "", "", // 0x10
+    "", "", "", "", "", "", "", "", // 0x18
+    "space", "exclam", "quotedbl", "numbersign", "dollar", "percent", "ampersand", "quotesingle", // 0x20
+    "parenleft", "parenright", "asterisk", "plus", "comma", "hyphen", "period", "slash", // 0x28
+    "zero", "one", "two", "three", "four", "five", "six", "seven", // 0x30
+    "eight", "nine", "colon", "semicolon", "less", "equal", "greater", "question", // 0x38
+    "at", "A", "B", "C", "D", "E", "F", "G", // 0x40
+    "H", "I", "J", "K", "L", "M", "N", "O", // 0x48
+    "P", "Q", "R", "S", "T", "U", "V", "W", // 0x50
+    "X", "Y", "Z", "bracketleft", "backslash", "bracketright", "asciicircum", "underscore", // 0x58
+    "grave", "a", "b", "c", "d", "e", "f", "g", // 0x60
+    "h", "i", "j", "k", "l", "m", "n", "o", // 0x68
+    "p", "q", "r", "s", "t", "u", "v", "w", // 0x70
+    "x", "y", "z", "braceleft", "bar", "braceright", "asciitilde", "", // 0x78
+    "Euro", "", "quotesinglbase", "florin", "quotedblbase", "ellipsis", "dagger", "daggerdbl", // 0x80
+    "circumflex", "perthousand", "Scaron", "guilsinglleft", "OE", "", "Zcaron", "", // 0x88
+    "", "quoteleft", "quoteright", "quotedblleft", "quotedblright", "bullet", "endash", "emdash", // 0x90
+    "tilde", "trademark", "scaron", "guilsinglright", "oe", "", "zcaron", "Ydieresis", // 0x98
+    "space", "exclamdown", "cent", "sterling", "currency", "yen", "brokenbar", "section", // 0xa0
+    "dieresis", "copyright", "ordfeminine", "guillemotleft", "logicalnot", "hyphen", "registered", "macron", // 0xa8
+    "degree", "plusminus", "twosuperior", "threesuperior", "acute", "mu", "paragraph", "periodcentered", // 0xb0
+    "cedilla", "onesuperior", "ordmasculine", "guillemotright", "onequarter", "onehalf", "threequarters", "questiondown", // 0xb8
+    "Agrave", "Aacute", "Acircumflex", "Atilde", "Adieresis", "Aring", "AE", "Ccedilla", // 0xc0
+    "Egrave", "Eacute", "Ecircumflex", "Edieresis", "Igrave", "Iacute", "Icircumflex", "Idieresis", // 0xc8
+    "Eth", "Ntilde", "Ograve", "Oacute", "Ocircumflex", "Otilde", "Odieresis", "multiply", // 0xd0
+    "Oslash", "Ugrave", "Uacute", "Ucircumflex", "Udieresis", "Yacute", "Thorn", "germandbls", // 0xd8
+    "agrave", "aacute", "acircumflex", "atilde", "adieresis", "aring", "ae", "ccedilla", // 0xe0
+    "egrave", "eacute", "ecircumflex", "edieresis", "igrave", "iacute", "icircumflex", "idieresis", // 0xe8
+    "eth", "ntilde", "ograve", "oacute", "ocircumflex", "otilde", "odieresis", "divide", // 0xf0
+    "oslash", "ugrave", "uacute", "ucircumflex", "udieresis", "yacute", "thorn", "ydieresis", // 0xf8
+];
+
+/// MacRomanEncoding (ISO 32000-2, Annex D), with the symbols of the Mac OS character set.
+const MAC_ROMAN: [&str; 256] = [
+    "", "", "", "", "", "", "", "", // 0x00
+    "", "", "", "", "", "", "", "", // 0x08
+    "", "", "", "", "", "", "", "", // 0x10
+    "", "", "", "", "", "", "", "", // 0x18
+    "space", "exclam", "quotedbl", "numbersign", "dollar", "percent", "ampersand", "quotesingle", // 0x20
+    "parenleft", "parenright", "asterisk", "plus", "comma", "hyphen", "period", "slash", // 0x28
+    "zero", "one", "two", "three", "four", "five", "six", "seven", // 0x30
+    "eight", "nine", "colon", "semicolon", "less", "equal", "greater", "question", // 0x38
+    "at", "A", "B", "C", "D", "E", "F", "G", // 0x40
+    "H", "I", "J", "K", "L", "M", "N", "O", // 0x48
+    "P", "Q", "R", "S", "T", "U", "V", "W", // 0x50
+    "X", "Y", "Z", "bracketleft", "backslash", "bracketright", "asciicircum", "underscore", // 0x58
+    "grave", "a", "b", "c", "d", "e", "f", "g", // 0x60
+    "h", "i", "j", "k", "l", "m", "n", "o", // 0x68
+    "p", "q", "r", "s", "t", "u", "v", "w", // 0x70
+    "x", "y", "z", "braceleft", "bar", "braceright", "asciitilde", "", // 0x78
+    "Adieresis", "Aring", "Ccedilla", "Eacute", "Ntilde", "Odieresis", "Udieresis", "aacute", // 0x80
+    "agrave", "acircumflex", "adieresis", "atilde", "aring", "ccedilla", "eacute", "egrave", // 0x88
+    "ecircumflex", "edieresis", "iacute", "igrave", "icircumflex", "idieresis", "ntilde", "oacute", // 0x90
+    "ograve", "ocircumflex", "odieresis", "otilde", "uacute", "ugrave", "ucircumflex", "udieresis", // 0x98
+    "dagger", "degree", "cent", "sterling", "section", "bullet", "paragraph", "germandbls", // 0xa0
+    "registered", "copyright", "trademark", "acute", "dieresis", "notequal", "AE", "Oslash", // 0xa8
+    "infinity", "plusminus", "lessequal", "greaterequal", "yen", "mu", "partialdiff", "summation", // 0xb0
+    "product", "pi", "integral", "ordfeminine", "ordmasculine", "Omega", "ae", "oslash", // 0xb8
+    "questiondown", "exclamdown", "logicalnot", "radical", "florin", "approxequal", "Delta", "guillemotleft", // 0xc0
+    "guillemotright", "ellipsis", "space", "Agrave", "Atilde", "Otilde", "OE", "oe", // 0xc8
+    "endash", "emdash", "quotedblleft", "quotedblright", "quoteleft", "quoteright", "divide", "lozenge", // 0xd0
+    "ydieresis", "Ydieresis", "fraction", "currency", "guilsinglleft", "guilsinglright", "fi", "fl", // 0xd8
+    "daggerdbl", "periodcentered", "quotesinglbase", "quotedblbase", "perthousand", "Acircumflex", "Ecircumflex", "Aacute", // 0xe0
+    "Edieresis", "Egrave", "Iacute", "Icircumflex", "Idieresis", "Igrave", "Oacute", "Ocircumflex", // 0xe8
+    "apple", "Ograve", "Uacute", "Ucircumflex", "Ugrave", "dotlessi", "circumflex", "tilde", // 0xf0
+    "macron", "breve", "dotaccent", "ring", "cedilla", "hungarumlaut", "ogonek", "caron", // 0xf8
+];
+
+/// PDFDocEncoding (ISO 32000-2, Annex D).
+const PDF_DOC: [&str; 256] = [
+    "", "", "", "", "", "", "", "", // 0x00
+    "", "", "", "", "", "", "", "", // 0x08
+    "", "", "", "", "", "", "", "", // 0x10
+    "breve", "caron", "circumflex", "dotaccent", "hungarumlaut", "ogonek", "ring", "tilde", // 0x18
+    "space", "exclam", "quotedbl", "numbersign", "dollar", "percent", "ampersand", "quotesingle", // 0x20
+    "parenleft", "parenright", "asterisk", "plus", "comma", "hyphen", "period", "slash", // 0x28
+    "zero", "one", "two", "three", "four", "five", "six", "seven", // 0x30
+    "eight", "nine", "colon", "semicolon", "less", "equal", "greater", "question", // 0x38
+    "at", "A", "B", "C", "D", "E", "F", "G", // 0x40
+    "H", "I", "J", "K", "L", "M", "N", "O", // 0x48
+    "P", "Q", "R", "S", "T", "U", "V", "W", // 0x50
+    "X", "Y", "Z", "bracketleft", "backslash", "bracketright", "asciicircum", "underscore", // 0x58
+    "grave", "a", "b", "c", "d", "e", "f", "g", // 0x60
+    "h", "i", "j", "k", "l", "m", "n", "o", // 0x68
+    "p", "q", "r", "s", "t", "u", "v", "w", // 0x70
+    "x", "y", "z", "braceleft", "bar", "braceright", "asciitilde", "", // 0x78
+    "bullet", "dagger", "daggerdbl", "ellipsis", "emdash", "endash", "florin", "fraction", // 0x80
+    "guilsinglleft", "guilsinglright", "minus", "perthousand", "quotedblbase", "quotedblleft", "quotedblright", "quoteleft", // 0x88
+    "quoteright", "quotesinglbase", "trademark", "fi", "fl", "Lslash", "OE", "Scaron", // 0x90
+    "Ydieresis", "Zcaron", "dotlessi", "lslash", "oe", "scaron", "zcaron", "", // 0x98
+    "Euro", "exclamdown", "cent", "sterling", "currency", "yen", "brokenbar", "section", // 0xa0
+    "dieresis", "copyright", "ordfeminine", "guillemotleft", "logicalnot", "", "registered", "macron", // 0xa8
+    "degree", "plusminus", "twosuperior", "threesuperior", "acute", "mu", "paragraph", "periodcentered", // 0xb0
+    "cedilla", "onesuperior", "ordmasculine", "guillemotright", "onequarter", "onehalf", "threequarters", "questiondown", // 0xb8
+    "Agrave", "Aacute", "Acircumflex", "Atilde", "Adieresis", "Aring", "AE", "Ccedilla", // 0xc0
+    "Egrave", "Eacute", "Ecircumflex", "Edieresis", "Igrave", "Iacute", "Icircumflex", "Idieresis", // 0xc8
+    "Eth", "Ntilde", "Ograve", "Oacute", "Ocircumflex", "Otilde", "Odieresis", "multiply", // 0xd0
+    "Oslash", "Ugrave", "Uacute", "Ucircumflex", "Udieresis", "Yacute", "Thorn", "germandbls", // 0xd8
+    "agrave", "aacute", "acircumflex", "atilde", "adieresis", "aring", "ae", "ccedilla", // 0xe0
+    "egrave", "eacute", "ecircumflex", "edieresis", "igrave", "iacute", "icircumflex", "idieresis", // 0xe8
+    "eth", "ntilde", "ograve", "oacute", "ocircumflex", "otilde", "odieresis", "divide", // 0xf0
+    "oslash", "ugrave", "uacute", "ucircumflex", "udieresis", "yacute", "thorn", "ydieresis", // 0xf8
+];
+
+#[cfg(test)]
+mod tests {
+    use super::{BaseEncoding, Encoding};
+    use crate::object::Object;
+
+    #[test]
+    fn should_map_codes_of_base_encodings() {
+        assert_eq!(BaseEncoding::Standard.glyph_name(0x27), Some("quoteright"));
+        assert_eq!(BaseEncoding::WinAnsi.glyph_name(0x27), Some("quotesingle"));
+        assert_eq!(BaseEncoding::WinAnsi.glyph_name(0x80), Some("Euro"));
+        assert_eq!(BaseEncoding::MacRoman.glyph_name(0x8E), Some("eacute"));
+        assert_eq!(BaseEncoding::PdfDoc.glyph_name(0x93), Some("fi"));
+        assert_eq!(BaseEncoding::Standard.glyph_name(0x80), None);
+        assert_eq!(BaseEncoding::from_name(b"/MacRomanEncoding"), Some(BaseEncoding::MacRoman));
+    }
+
+    #[test]
+    fn should_apply_differences() {
+        let mut encoding = Encoding::new(BaseEncoding::WinAnsi);
+        let differences = Object::parse(b"[65 /B /uni00E9 200 /f_i]").unwrap();
+
+        encoding.apply_differences(differences.as_array().unwrap().as_objects());
+
+        assert_eq!(encoding.glyph_name(65), Some("B"));
+        assert_eq!(encoding.to_unicode(66).as_deref(), Some("\u{e9}"));
+        assert_eq!(encoding.to_unicode(67).as_deref(), Some("C"));
+        assert_eq!(encoding.to_unicode(200).as_deref(), Some("fi"));
+    }
+}
\ No newline at end of file
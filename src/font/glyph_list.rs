@@ -0,0 +1,200 @@
+/// Returns the text of a glyph name, following the Adobe Glyph List Specification.
+///
+/// A suffix after a period is dropped (i.e `a.sc`), the components of a
+/// ligature joined by `_` are mapped one by one (i.e `f_f_i`), and
+/// `uniXXXX` and `uXXXX[XX]` names give their code points.
+pub fn glyph_to_unicode(name: &str) -> Option<String> {
+
+    let name = name.split('.').next().unwrap_or_default();
+
+    let text = name.split('_').filter_map(component).collect::<String>();
+
+    (!text.is_empty()).then_some(text)
+}
+
+/// Returns the text of one component of a glyph name.
+fn component(name: &str) -> Option<String> {
+
+    if let Ok(index) = GLYPHS.binary_search_by(|(glyph, _)| glyph.cmp(&name)) {
+        return Some(GLYPHS[index].1.to_string());
+    }
+
+    if let Some(hex) = name.strip_prefix("uni")
+        && !hex.is_empty()
+        && hex.len() % 4 == 0
+        && hex.bytes().all(|byte| byte.is_ascii_hexdigit())
+    {
+        let units = hex.as_bytes().chunks(4)
+            .map(|unit| u16::from_str_radix(std::str::from_utf8(unit).ok()?, 16).ok())
+            .collect::<Option<Vec<_>>>()?;
+
+        // Surrogates are not allowed in `uni` names.
+        return units.iter()
+            .map(|unit| char::from_u32(*unit as u32))
+            .collect::<Option<String>>();
+    }
+
+    if let Some(hex) = name.strip_prefix('u')
+        && (4..=6).contains(&hex.len())
+        && hex.bytes().all(|byte| byte.is_ascii_hexdigit())
+    {
+        return u32::from_str_radix(hex, 16).ok().and_then(char::from_u32).map(String::from);
+    }
+
+    None
+}
+
+/// Glyph names of the Adobe Glyph List used by the predefined encodings,
+/// with the common symbol, Greek and Latin Extended-A glyphs, sorted by name.
+const GLYPHS: &[(&str, char)] = &[
+    ("A", '\u{0041}'), ("AE", '\u{00C6}'), ("Aacute", '\u{00C1}'), ("Abreve", '\u{0102}'),
+    ("Acircumflex", '\u{00C2}'), ("Adieresis", '\u{00C4}'), ("Agrave", '\u{00C0}'), ("Alpha", '\u{0391}'),
+    ("Amacron", '\u{0100}'), ("Aogonek", '\u{0104}'), ("Aring", '\u{00C5}'), ("Atilde", '\u{00C3}'),
+    ("B", '\u{0042}'), ("Beta", '\u{0392}'), ("C", '\u{0043}'), ("Cacute", '\u{0106}'),
+    ("Ccaron", '\u{010C}'), ("Ccedilla", '\u{00C7}'), ("Ccircumflex", '\u{0108}'),
+    ("Cdotaccent", '\u{010A}'), ("Chi", '\u{03A7}'), ("D", '\u{0044}'), ("Dcaron", '\u{010E}'),
+    ("Dcroat", '\u{0110}'), ("Delta", '\u{2206}'), ("E", '\u{0045}'), ("Eacute", '\u{00C9}'),
+    ("Ebreve", '\u{0114}'), ("Ecaron", '\u{011A}'), ("Ecircumflex", '\u{00CA}'), ("Edieresis", '\u{00CB}'),
+    ("Edotaccent", '\u{0116}'), ("Egrave", '\u{00C8}'), ("Emacron", '\u{0112}'), ("Eng", '\u{014A}'),
+    ("Eogonek", '\u{0118}'), ("Epsilon", '\u{0395}'), ("Eta", '\u{0397}'), ("Eth", '\u{00D0}'),
+    ("Euro", '\u{20AC}'), ("F", '\u{0046}'), ("G", '\u{0047}'), ("Gamma", '\u{0393}'),
+    ("Gbreve", '\u{011E}'), ("Gcircumflex", '\u{011C}'), ("Gcommaaccent", '\u{0122}'),
+    ("Gdotaccent", '\u{0120}'), ("H", '\u{0048}'), ("Hbar", '\u{0126}'), ("Hcircumflex", '\u{0124}'),
+    ("I", '\u{0049}'), ("IJ", '\u{0132}'), ("Iacute", '\u{00CD}'), ("Ibreve", '\u{012C}'),
+    ("Icircumflex", '\u{00CE}'), ("Idieresis", '\u{00CF}'), ("Idotaccent", '\u{0130}'),
+    ("Ifraktur", '\u{2111}'), ("Igrave", '\u{00CC}'), ("Imacron", '\u{012A}'), ("Iogonek", '\u{012E}'),
+    ("Iota", '\u{0399}'), ("Itilde", '\u{0128}'), ("J", '\u{004A}'), ("Jcircumflex", '\u{0134}'),
+    ("K", '\u{004B}'), ("Kappa", '\u{039A}'), ("Kcommaaccent", '\u{0136}'), ("L", '\u{004C}'),
+    ("Lacute", '\u{0139}'), ("Lambda", '\u{039B}'), ("Lcaron", '\u{013D}'), ("Lcommaaccent", '\u{013B}'),
+    ("Ldot", '\u{013F}'), ("Lslash", '\u{0141}'), ("M", '\u{004D}'), ("Mu", '\u{039C}'), ("N", '\u{004E}'),
+    ("Nacute", '\u{0143}'), ("Ncaron", '\u{0147}'), ("Ncommaaccent", '\u{0145}'), ("Ntilde", '\u{00D1}'),
+    ("Nu", '\u{039D}'), ("O", '\u{004F}'), ("OE", '\u{0152}'), ("Oacute", '\u{00D3}'),
+    ("Obreve", '\u{014E}'), ("Ocircumflex", '\u{00D4}'), ("Odieresis", '\u{00D6}'), ("Ograve", '\u{00D2}'),
+    ("Ohungarumlaut", '\u{0150}'), ("Omacron", '\u{014C}'), ("Omega", '\u{2126}'), ("Omicron", '\u{039F}'),
+    ("Oslash", '\u{00D8}'), ("Otilde", '\u{00D5}'), ("P", '\u{0050}'), ("Phi", '\u{03A6}'),
+    ("Pi", '\u{03A0}'), ("Psi", '\u{03A8}'), ("Q", '\u{0051}'), ("R", '\u{0052}'), ("Racute", '\u{0154}'),
+    ("Rcaron", '\u{0158}'), ("Rcommaaccent", '\u{0156}'), ("Rfraktur", '\u{211C}'), ("Rho", '\u{03A1}'),
+    ("S", '\u{0053}'), ("Sacute", '\u{015A}'), ("Scaron", '\u{0160}'), ("Scedilla", '\u{015E}'),
+    ("Scircumflex", '\u{015C}'), ("Sigma", '\u{03A3}'), ("T", '\u{0054}'), ("Tau", '\u{03A4}'),
+    ("Tbar", '\u{0166}'), ("Tcaron", '\u{0164}'), ("Tcommaaccent", '\u{0162}'), ("Theta", '\u{0398}'),
+    ("Thorn", '\u{00DE}'), ("U", '\u{0055}'), ("Uacute", '\u{00DA}'), ("Ubreve", '\u{016C}'),
+    ("Ucircumflex", '\u{00DB}'), ("Udieresis", '\u{00DC}'), ("Ugrave", '\u{00D9}'),
+    ("Uhungarumlaut", '\u{0170}'), ("Umacron", '\u{016A}'), ("Uogonek", '\u{0172}'), ("Upsilon", '\u{03A5}'),
+    ("Uring", '\u{016E}'), ("Utilde", '\u{0168}'), ("V", '\u{0056}'), ("W", '\u{0057}'),
+    ("Wcircumflex", '\u{0174}'), ("X", '\u{0058}'), ("Xi", '\u{039E}'), ("Y", '\u{0059}'),
+    ("Yacute", '\u{00DD}'), ("Ycircumflex", '\u{0176}'), ("Ydieresis", '\u{0178}'), ("Z", '\u{005A}'),
+    ("Zacute", '\u{0179}'), ("Zcaron", '\u{017D}'), ("Zdotaccent", '\u{017B}'), ("Zeta", '\u{0396}'),
+    ("a", '\u{0061}'), ("aacute", '\u{00E1}'), ("abreve", '\u{0103}'), ("acircumflex", '\u{00E2}'),
+    ("acute", '\u{00B4}'), ("adieresis", '\u{00E4}'), ("ae", '\u{00E6}'), ("agrave", '\u{00E0}'),
+    ("aleph", '\u{2135}'), ("alpha", '\u{03B1}'), ("amacron", '\u{0101}'), ("ampersand", '\u{0026}'),
+    ("angle", '\u{2220}'), ("angleleft", '\u{2329}'), ("angleright", '\u{232A}'), ("aogonek", '\u{0105}'),
+    ("apple", '\u{F8FF}'), ("approxequal", '\u{2248}'), ("aring", '\u{00E5}'), ("arrowboth", '\u{2194}'),
+    ("arrowdblboth", '\u{21D4}'), ("arrowdbldown", '\u{21D3}'), ("arrowdblleft", '\u{21D0}'),
+    ("arrowdblright", '\u{21D2}'), ("arrowdblup", '\u{21D1}'), ("arrowdown", '\u{2193}'),
+    ("arrowleft", '\u{2190}'), ("arrowright", '\u{2192}'), ("arrowup", '\u{2191}'),
+    ("arrowupdn", '\u{2195}'), ("asciicircum", '\u{005E}'), ("asciitilde", '\u{007E}'),
+    ("asterisk", '\u{002A}'), ("asteriskmath", '\u{2217}'), ("at", '\u{0040}'), ("atilde", '\u{00E3}'),
+    ("b", '\u{0062}'), ("backslash", '\u{005C}'), ("bar", '\u{007C}'), ("beta", '\u{03B2}'),
+    ("block", '\u{2588}'), ("braceleft", '\u{007B}'), ("braceright", '\u{007D}'),
+    ("bracketleft", '\u{005B}'), ("bracketright", '\u{005D}'), ("breve", '\u{02D8}'),
+    ("brokenbar", '\u{00A6}'), ("bullet", '\u{2022}'), ("c", '\u{0063}'), ("cacute", '\u{0107}'),
+    ("caron", '\u{02C7}'), ("ccaron", '\u{010D}'), ("ccedilla", '\u{00E7}'), ("ccircumflex", '\u{0109}'),
+    ("cdotaccent", '\u{010B}'), ("cedilla", '\u{00B8}'), ("cent", '\u{00A2}'), ("chi", '\u{03C7}'),
+    ("circle", '\u{25CB}'), ("circlemultiply", '\u{2297}'), ("circleplus", '\u{2295}'),
+    ("circumflex", '\u{02C6}'), ("club", '\u{2663}'), ("colon", '\u{003A}'), ("comma", '\u{002C}'),
+    ("congruent", '\u{2245}'), ("copyright", '\u{00A9}'), ("currency", '\u{00A4}'), ("d", '\u{0064}'),
+    ("dagger", '\u{2020}'), ("daggerdbl", '\u{2021}'), ("dcaron", '\u{010F}'), ("dcroat", '\u{0111}'),
+    ("degree", '\u{00B0}'), ("delta", '\u{03B4}'), ("diamond", '\u{2666}'), ("dieresis", '\u{00A8}'),
+    ("divide", '\u{00F7}'), ("dkshade", '\u{2593}'), ("dollar", '\u{0024}'), ("dotaccent", '\u{02D9}'),
+    ("dotlessi", '\u{0131}'), ("dotmath", '\u{22C5}'), ("e", '\u{0065}'), ("eacute", '\u{00E9}'),
+    ("ebreve", '\u{0115}'), ("ecaron", '\u{011B}'), ("ecircumflex", '\u{00EA}'), ("edieresis", '\u{00EB}'),
+    ("edotaccent", '\u{0117}'), ("egrave", '\u{00E8}'), ("eight", '\u{0038}'), ("element", '\u{2208}'),
+    ("ellipsis", '\u{2026}'), ("emacron", '\u{0113}'), ("emdash", '\u{2014}'), ("emptyset", '\u{2205}'),
+    ("endash", '\u{2013}'), ("eng", '\u{014B}'), ("eogonek", '\u{0119}'), ("epsilon", '\u{03B5}'),
+    ("equal", '\u{003D}'), ("equivalence", '\u{2261}'), ("estimated", '\u{212E}'), ("eta", '\u{03B7}'),
+    ("eth", '\u{00F0}'), ("exclam", '\u{0021}'), ("exclamdbl", '\u{203C}'), ("exclamdown", '\u{00A1}'),
+    ("existential", '\u{2203}'), ("f", '\u{0066}'), ("female", '\u{2640}'), ("ff", '\u{FB00}'),
+    ("ffi", '\u{FB03}'), ("ffl", '\u{FB04}'), ("fi", '\u{FB01}'), ("filledbox", '\u{25A0}'),
+    ("five", '\u{0035}'), ("fiveeighths", '\u{215D}'), ("fl", '\u{FB02}'), ("florin", '\u{0192}'),
+    ("four", '\u{0034}'), ("fraction", '\u{2044}'), ("g", '\u{0067}'), ("gamma", '\u{03B3}'),
+    ("gbreve", '\u{011F}'), ("gcircumflex", '\u{011D}'), ("gcommaaccent", '\u{0123}'),
+    ("gdotaccent", '\u{0121}'), ("germandbls", '\u{00DF}'), ("gradient", '\u{2207}'), ("grave", '\u{0060}'),
+    ("greater", '\u{003E}'), ("greaterequal", '\u{2265}'), ("guillemotleft", '\u{00AB}'),
+    ("guillemotright", '\u{00BB}'), ("guilsinglleft", '\u{2039}'), ("guilsinglright", '\u{203A}'),
+    ("h", '\u{0068}'), ("hbar", '\u{0127}'), ("hcircumflex", '\u{0125}'), ("heart", '\u{2665}'),
+    ("house", '\u{2302}'), ("hungarumlaut", '\u{02DD}'), ("hyphen", '\u{002D}'), ("i", '\u{0069}'),
+    ("iacute", '\u{00ED}'), ("ibreve", '\u{012D}'), ("icircumflex", '\u{00EE}'), ("idieresis", '\u{00EF}'),
+    ("igrave", '\u{00EC}'), ("ij", '\u{0133}'), ("imacron", '\u{012B}'), ("infinity", '\u{221E}'),
+    ("integral", '\u{222B}'), ("intersection", '\u{2229}'), ("iogonek", '\u{012F}'), ("iota", '\u{03B9}'),
+    ("itilde", '\u{0129}'), ("j", '\u{006A}'), ("jcircumflex", '\u{0135}'), ("k", '\u{006B}'),
+    ("kappa", '\u{03BA}'), ("kcommaaccent", '\u{0137}'), ("kgreenlandic", '\u{0138}'), ("l", '\u{006C}'),
+    ("lacute", '\u{013A}'), ("lambda", '\u{03BB}'), ("lcaron", '\u{013E}'), ("lcommaaccent", '\u{013C}'),
+    ("ldot", '\u{0140}'), ("less", '\u{003C}'), ("lessequal", '\u{2264}'), ("logicaland", '\u{2227}'),
+    ("logicalnot", '\u{00AC}'), ("logicalor", '\u{2228}'), ("longs", '\u{017F}'), ("lozenge", '\u{25CA}'),
+    ("lslash", '\u{0142}'), ("ltshade", '\u{2591}'), ("m", '\u{006D}'), ("macron", '\u{00AF}'),
+    ("male", '\u{2642}'), ("minus", '\u{2212}'), ("minute", '\u{2032}'), ("mu", '\u{00B5}'),
+    ("multiply", '\u{00D7}'), ("musicalnote", '\u{266A}'), ("n", '\u{006E}'), ("nacute", '\u{0144}'),
+    ("napostrophe", '\u{0149}'), ("nbspace", '\u{00A0}'), ("ncaron", '\u{0148}'),
+    ("ncommaaccent", '\u{0146}'), ("nine", '\u{0039}'), ("notelement", '\u{2209}'), ("notequal", '\u{2260}'),
+    ("notsubset", '\u{2284}'), ("ntilde", '\u{00F1}'), ("nu", '\u{03BD}'), ("numbersign", '\u{0023}'),
+    ("o", '\u{006F}'), ("oacute", '\u{00F3}'), ("obreve", '\u{014F}'), ("ocircumflex", '\u{00F4}'),
+    ("odieresis", '\u{00F6}'), ("oe", '\u{0153}'), ("ogonek", '\u{02DB}'), ("ograve", '\u{00F2}'),
+    ("ohungarumlaut", '\u{0151}'), ("omacron", '\u{014D}'), ("omega", '\u{03C9}'), ("omicron", '\u{03BF}'),
+    ("one", '\u{0031}'), ("oneeighth", '\u{215B}'), ("onehalf", '\u{00BD}'), ("onequarter", '\u{00BC}'),
+    ("onesuperior", '\u{00B9}'), ("onethird", '\u{2153}'), ("openbullet", '\u{25E6}'),
+    ("ordfeminine", '\u{00AA}'), ("ordmasculine", '\u{00BA}'), ("oslash", '\u{00F8}'),
+    ("otilde", '\u{00F5}'), ("p", '\u{0070}'), ("paragraph", '\u{00B6}'), ("parenleft", '\u{0028}'),
+    ("parenright", '\u{0029}'), ("partialdiff", '\u{2202}'), ("percent", '\u{0025}'), ("period", '\u{002E}'),
+    ("periodcentered", '\u{00B7}'), ("perpendicular", '\u{22A5}'), ("perthousand", '\u{2030}'),
+    ("phi", '\u{03C6}'), ("pi", '\u{03C0}'), ("plus", '\u{002B}'), ("plusminus", '\u{00B1}'),
+    ("product", '\u{220F}'), ("propersubset", '\u{2282}'), ("propersuperset", '\u{2283}'),
+    ("proportional", '\u{221D}'), ("psi", '\u{03C8}'), ("q", '\u{0071}'), ("question", '\u{003F}'),
+    ("questiondown", '\u{00BF}'), ("quotedbl", '\u{0022}'), ("quotedblbase", '\u{201E}'),
+    ("quotedblleft", '\u{201C}'), ("quotedblright", '\u{201D}'), ("quoteleft", '\u{2018}'),
+    ("quoteright", '\u{2019}'), ("quotesinglbase", '\u{201A}'), ("quotesingle", '\u{0027}'),
+    ("r", '\u{0072}'), ("racute", '\u{0155}'), ("radical", '\u{221A}'), ("rcaron", '\u{0159}'),
+    ("rcommaaccent", '\u{0157}'), ("reflexsubset", '\u{2286}'), ("reflexsuperset", '\u{2287}'),
+    ("registered", '\u{00AE}'), ("rho", '\u{03C1}'), ("ring", '\u{02DA}'), ("s", '\u{0073}'),
+    ("sacute", '\u{015B}'), ("scaron", '\u{0161}'), ("scedilla", '\u{015F}'), ("scircumflex", '\u{015D}'),
+    ("second", '\u{2033}'), ("section", '\u{00A7}'), ("semicolon", '\u{003B}'), ("seven", '\u{0037}'),
+    ("seveneighths", '\u{215E}'), ("sfthyphen", '\u{00AD}'), ("shade", '\u{2592}'), ("sigma", '\u{03C3}'),
+    ("sigma1", '\u{03C2}'), ("similar", '\u{223C}'), ("six", '\u{0036}'), ("slash", '\u{002F}'),
+    ("smileface", '\u{263A}'), ("space", '\u{0020}'), ("spade", '\u{2660}'), ("sterling", '\u{00A3}'),
+    ("suchthat", '\u{220B}'), ("summation", '\u{2211}'), ("sun", '\u{263C}'), ("t", '\u{0074}'),
+    ("tau", '\u{03C4}'), ("tbar", '\u{0167}'), ("tcaron", '\u{0165}'), ("tcommaaccent", '\u{0163}'),
+    ("therefore", '\u{2234}'), ("theta", '\u{03B8}'), ("thorn", '\u{00FE}'), ("three", '\u{0033}'),
+    ("threeeighths", '\u{215C}'), ("threequarters", '\u{00BE}'), ("threesuperior", '\u{00B3}'),
+    ("tilde", '\u{02DC}'), ("trademark", '\u{2122}'), ("triagdn", '\u{25BC}'), ("triaglf", '\u{25C4}'),
+    ("triagrt", '\u{25BA}'), ("triagup", '\u{25B2}'), ("two", '\u{0032}'), ("twosuperior", '\u{00B2}'),
+    ("twothirds", '\u{2154}'), ("u", '\u{0075}'), ("uacute", '\u{00FA}'), ("ubreve", '\u{016D}'),
+    ("ucircumflex", '\u{00FB}'), ("udieresis", '\u{00FC}'), ("ugrave", '\u{00F9}'),
+    ("uhungarumlaut", '\u{0171}'), ("umacron", '\u{016B}'), ("underscore", '\u{005F}'),
+    ("union", '\u{222A}'), ("universal", '\u{2200}'), ("uogonek", '\u{0173}'), ("upsilon", '\u{03C5}'),
+    ("uring", '\u{016F}'), ("utilde", '\u{0169}'), ("v", '\u{0076}'), ("w", '\u{0077}'),
+    ("wcircumflex", '\u{0175}'), ("weierstrass", '\u{2118}'), ("x", '\u{0078}'), ("xi", '\u{03BE}'),
+    ("y", '\u{0079}'), ("yacute", '\u{00FD}'), ("ycircumflex", '\u{0177}'), ("ydieresis", '\u{00FF}'),
+    ("yen", '\u{00A5}'), ("z", '\u{007A}'), ("zacute", '\u{017A}'), ("zcaron", '\u{017E}'),
+    ("zdotaccent", '\u{017C}'), ("zero", '\u{0030}'), ("zeta", '\u{03B6}'),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::{glyph_to_unicode, GLYPHS};
+
+    #[test]
+    fn should_map_glyph_names() {
+        assert_eq!(glyph_to_unicode("A").as_deref(), Some("A"));
+        assert_eq!(glyph_to_unicode("quoteright").as_deref(), Some("\u{2019}"));
+        assert_eq!(glyph_to_unicode("Euro.oldstyle").as_deref(), Some("\u{20ac}"));
+        assert_eq!(glyph_to_unicode("f_f_i").as_deref(), Some("ffi"));
+        assert_eq!(glyph_to_unicode("uni00410042").as_deref(), Some("AB"));
+        assert_eq!(glyph_to_unicode("u1F600").as_deref(), Some("\u{1f600}"));
+        assert_eq!(glyph_to_unicode("uniD800"), None);
+        assert_eq!(glyph_to_unicode("g123"), None);
+    }
+
+    #[test]
+    fn should_keep_glyphs_sorted() {
+        assert!(GLYPHS.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+}
\ No newline at end of file
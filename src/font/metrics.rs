@@ -0,0 +1,272 @@
+use std::collections::HashMap;
+
+use crate::document::Document;
+use crate::font::encoding::Encoding;
+use crate::font::standard::StandardFont;
+use crate::geometry::{Matrix, Rectangle};
+use crate::object::{Dicionary, Object};
+
+/// Width used when a font gives no width for a simple font's character code.
+const DEFAULT_WIDTH: f64 = 500.0;
+
+/// Width used when a composite font gives no width (`/DW`).
+const DEFAULT_CID_WIDTH: f64 = 1000.0;
+
+/// Vertical metrics used when a composite font gives none (`/DW2`):
+/// the vertical position of the origin and the vertical advance.
+const DEFAULT_VERTICAL: (f64, f64) = (880.0, -1000.0);
+
+/// Glyph metrics of a font, in glyph space units: thousandths of text
+/// space units, except in Type 3 fonts which have their own font matrix.
+///
+/// Glyphs are selected by character code in simple fonts, and by CID
+/// in composite fonts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FontMetrics {
+    /// Advance widths by glyph.
+    widths: HashMap<u32, f64>,
+    /// Width of the glyphs missing from `widths`.
+    default_width: f64,
+    /// Whether the font is a composite (`/Type0`) font.
+    composite: bool,
+    /// Vertical metrics by glyph (`/W2`): the vertical advance and the
+    /// position of the vertical origin.
+    vertical: HashMap<u32, (f64, f64, f64)>,
+    /// Vertical origin and advance of the glyphs missing from `vertical` (`/DW2`).
+    default_vertical: (f64, f64),
+    /// Maximum height of glyphs above the baseline.
+    ascent: f64,
+    /// Maximum depth of glyphs below the baseline (negative).
+    descent: f64,
+    /// Maps glyph space to text space (`/FontMatrix`).
+    matrix: Matrix,
+}
+
+impl Default for FontMetrics {
+
+    fn default() -> Self {
+        Self {
+            widths: HashMap::new(),
+            default_width: DEFAULT_WIDTH,
+            composite: false,
+            vertical: HashMap::new(),
+            default_vertical: DEFAULT_VERTICAL,
+            ascent: 800.0,
+            descent: -200.0,
+            matrix: Matrix::new(0.001, 0.0, 0.0, 0.001, 0.0, 0.0),
+        }
+    }
+}
+
+impl FontMetrics {
+
+    /// Reads the metrics of a font dictionary.
+    ///
+    /// Simple fonts use `/FirstChar`, `/Widths` and `/MissingWidth`;
+    /// composite (`/Type0`) fonts use the `/W`, `/DW`, `/W2` and `/DW2`
+    /// entries of their descendant font.
+    ///
+    /// Standard 14 fonts without `/Widths` use their built-in metrics,
+    /// looked up by the glyph names of the encoding. Type 3 fonts use
+    /// their `/FontMatrix`, and the height of their `/FontBBox`.
+    pub fn read(document: &Document, font: &Dicionary, encoding: Option<&Encoding>) -> Result<Self, String> {
+
+        let mut metrics = Self::default();
+
+        let get = |dicionary: &Dicionary, key: &[u8]| -> Result<Option<Object>, String> {
+            dicionary.get(key).map(|value| document.resolve(value)).transpose()
+        };
+
+        let subtype = font.get(b"/Subtype").and_then(Object::as_name).map(|name| name.as_bytes().to_vec());
+
+        let font = if subtype.as_deref() == Some(b"/Type0") {
+            metrics.composite = true;
+            metrics.default_width = DEFAULT_CID_WIDTH;
+
+            let descendant = get(font, b"/DescendantFonts")?
+                .and_then(|fonts| fonts.as_array().and_then(|fonts| fonts.as_objects().first().cloned()));
+
+            match descendant.map(|descendant| document.resolve(&descendant)).transpose()? {
+                Some(Object::Dicionary(descendant)) => descendant,
+                _ => return Ok(metrics),
+            }
+        } else {
+            font.clone()
+        };
+
+        if subtype.as_deref() == Some(b"/Type3") {
+            if let Some(matrix) = get(&font, b"/FontMatrix")?.as_ref().and_then(Matrix::from_object) {
+                metrics.matrix = matrix;
+            }
+
+            // Without a bounding box, the default height is scaled to glyph space.
+            match get(&font, b"/FontBBox")?.as_ref().and_then(Rectangle::from_object) {
+                Some(bounding_box) if bounding_box.height() > 0.0 => {
+                    metrics.ascent = bounding_box.top();
+                    metrics.descent = bounding_box.bottom();
+                }
+                _ if metrics.matrix.d != 0.0 => {
+                    metrics.ascent /= metrics.matrix.d * 1000.0;
+                    metrics.descent /= metrics.matrix.d * 1000.0;
+                }
+                _ => {}
+            }
+        }
+
+        let standard = font.get(b"/BaseFont")
+            .and_then(Object::as_name)
+            .and_then(|name| StandardFont::from_name(&name.as_bytes()[1..]));
+
+        if let Some(standard) = standard {
+            metrics.ascent = standard.ascent();
+            metrics.descent = standard.descent();
+        }
+
+        if let Some(descriptor) = get(&font, b"/FontDescriptor")?.as_ref().and_then(Object::as_dicionary) {
+            let value = |key: &[u8]| get(descriptor, key).map(|value| value.and_then(|value| value.as_f64()));
+
+            if let Some(ascent) = value(b"/Ascent")?.filter(|ascent| *ascent > 0.0) {
+                metrics.ascent = ascent;
+            }
+
+            if let Some(descent) = value(b"/Descent")?.filter(|descent| *descent < 0.0) {
+                metrics.descent = descent;
+            }
+
+            if !metrics.composite && let Some(missing) = value(b"/MissingWidth")? {
+                metrics.default_width = missing;
+            }
+        }
+
+        if metrics.composite {
+            if let Some(width) = get(&font, b"/DW")?.and_then(|width| width.as_f64()) {
+                metrics.default_width = width;
+            }
+
+            if let Some(Object::Array(widths)) = get(&font, b"/W")? {
+                for (cid, values) in read_cid_metrics(document, widths.as_objects(), 1)? {
+                    metrics.widths.insert(cid, values[0]);
+                }
+            }
+
+            if let Some(Object::Array(vertical)) = get(&font, b"/DW2")?
+                && let [Some(origin), Some(advance)] = vertical.as_objects().iter().map(Object::as_f64).collect::<Vec<_>>()[..]
+            {
+                metrics.default_vertical = (origin, advance);
+            }
+
+            if let Some(Object::Array(vertical)) = get(&font, b"/W2")? {
+                for (cid, values) in read_cid_metrics(document, vertical.as_objects(), 3)? {
+                    metrics.vertical.insert(cid, (values[0], values[1], values[2]));
+                }
+            }
+        } else if let Some(Object::Array(widths)) = get(&font, b"/Widths")? {
+            let first = get(&font, b"/FirstChar")?.and_then(|first| first.as_i64()).unwrap_or(0);
+
+            for (index, width) in widths.as_objects().iter().enumerate() {
+                if let (Ok(code), Some(width)) = (u32::try_from(first + index as i64), document.resolve(width)?.as_f64()) {
+                    metrics.widths.insert(code, width);
+                }
+            }
+        } else if let Some(standard) = standard {
+            for code in 0..=255 {
+                let glyph = encoding.and_then(|encoding| encoding.glyph_name(code)).or_else(|| standard.builtin_glyph_name(code));
+
+                if let Some(width) = glyph.and_then(|glyph| standard.width(glyph)) {
+                    metrics.widths.insert(code as u32, width);
+                }
+            }
+        }
+
+        Ok(metrics)
+    }
+
+    /// Returns the advance width of a glyph.
+    pub fn width(&self, glyph: u32) -> f64 {
+        self.widths.get(&glyph).copied().unwrap_or(self.default_width)
+    }
+
+    /// Returns the width of the glyphs without a width of their own.
+    pub fn default_width(&self) -> f64 {
+        self.default_width
+    }
+
+    /// Returns the vertical metrics of a glyph of a composite font: the
+    /// vertical advance (negative), and the horizontal and vertical
+    /// position of the vertical origin.
+    pub fn vertical_metrics(&self, glyph: u32) -> (f64, f64, f64) {
+
+        self.vertical.get(&glyph).copied().unwrap_or_else(|| self.default_vertical_metrics(self.width(glyph)))
+    }
+
+    /// Returns the vertical metrics of a glyph of the given width without
+    /// vertical metrics of its own (`/DW2`).
+    pub fn default_vertical_metrics(&self, width: f64) -> (f64, f64, f64) {
+
+        let (origin, advance) = self.default_vertical;
+
+        (advance, width / 2.0, origin)
+    }
+
+    /// Returns the height of glyphs above the baseline.
+    pub fn ascent(&self) -> f64 {
+        self.ascent
+    }
+
+    /// Returns the depth of glyphs below the baseline (negative).
+    pub fn descent(&self) -> f64 {
+        self.descent
+    }
+
+    /// Returns the matrix mapping glyph space to text space.
+    pub fn matrix(&self) -> &Matrix {
+        &self.matrix
+    }
+}
+
+/// Reads a `/W` or `/W2` array of metrics with `count` values per glyph:
+/// `c [v1 v2 ...]` and `c_first c_last v...` elements.
+fn read_cid_metrics(document: &Document, metrics: &[Object], count: usize) -> Result<Vec<(u32, Vec<f64>)>, String> {
+
+    let metrics = metrics.iter().map(|value| document.resolve(value)).collect::<Result<Vec<_>, _>>()?;
+    let mut glyphs = Vec::new();
+    let mut index = 0;
+
+    while index + 1 < metrics.len() {
+
+        let Some(first) = metrics[index].as_i64().and_then(|first| u32::try_from(first).ok()) else {
+            break;
+        };
+
+        match &metrics[index + 1] {
+            Object::Array(list) => {
+                let values = list.as_objects().iter().map(Object::as_f64).collect::<Vec<_>>();
+
+                for (offset, values) in values.chunks_exact(count).enumerate() {
+                    if let Some(values) = values.iter().copied().collect::<Option<Vec<_>>>() {
+                        glyphs.push((first.saturating_add(offset as u32), values));
+                    }
+                }
+
+                index += 2;
+            }
+            last => {
+                let values = metrics.get(index + 2..index + 2 + count)
+                    .and_then(|values| values.iter().map(Object::as_f64).collect::<Option<Vec<_>>>());
+
+                let (Some(last), Some(values)) = (last.as_i64(), values) else {
+                    break;
+                };
+
+                // Ranges are capped so that a corrupt entry cannot exhaust memory.
+                for cid in first..=u32::try_from(last).unwrap_or(0).min(first.saturating_add(0xFFFF)) {
+                    glyphs.push((cid, values.clone()));
+                }
+
+                index += 2 + count;
+            }
+        }
+    }
+
+    Ok(glyphs)
+}
\ No newline at end of file
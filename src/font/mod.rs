@@ -0,0 +1,259 @@
+//! This module contains the fonts used by content streams.
+mod cid_to_unicode;
+mod cmap;
+mod encoding;
+mod glyph_list;
+mod metrics;
+mod standard;
+mod to_unicode;
+
+use crate::document::Document;
+use crate::object::{Dicionary, Object};
+
+pub use crate::font::cid_to_unicode::CidOrdering;
+pub use crate::font::cmap::CMap;
+pub use crate::font::encoding::BaseEncoding;
+pub use crate::font::encoding::Encoding;
+pub use crate::font::glyph_list::glyph_to_unicode;
+pub use crate::font::metrics::FontMetrics;
+pub use crate::font::to_unicode::ToUnicode;
+
+/// Font descriptor flag of fonts using characters outside the standard Latin set.
+const SYMBOLIC: i64 = 1 << 2;
+
+/// A font of a content stream: its metrics and the text of its character codes.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Font {
+    /// The glyph metrics.
+    metrics: FontMetrics,
+    /// The encoding of a simple font.
+    encoding: Option<Encoding>,
+    /// The CMap of a composite font.
+    cmap: Option<CMap>,
+    /// The character collection of a composite font, if it is a known one.
+    ordering: Option<CidOrdering>,
+    /// The `/ToUnicode` CMap, if any.
+    to_unicode: Option<ToUnicode>,
+}
+
+impl Font {
+
+    /// Reads a font dictionary.
+    ///
+    /// A `/ToUnicode` CMap that cannot be parsed is ignored, so that
+    /// the encoding is used instead.
+    pub fn read(document: &Document, font: &Dicionary) -> Result<Self, String> {
+
+        let subtype = font.get(b"/Subtype").and_then(Object::as_name).map(|name| name.as_bytes().to_vec());
+
+        let (encoding, cmap, ordering) = match subtype.as_deref() {
+            Some(b"/Type0") => {
+                let cmap = match font.get(b"/Encoding") {
+                    Some(encoding) => CMap::read(document, encoding)?,
+                    None => CMap::identity(false),
+                };
+
+                (None, Some(cmap), CidOrdering::read(document, font)?)
+            }
+            _ => {
+                let default = if is_symbolic(document, font)? { Encoding::empty() } else { Encoding::new(BaseEncoding::Standard) };
+
+                let encoding = match font.get(b"/Encoding") {
+                    Some(encoding) => Encoding::read(document, encoding, default)?,
+                    None => default,
+                };
+
+                (Some(encoding), None, None)
+            }
+        };
+
+        let metrics = FontMetrics::read(document, font, encoding.as_ref())?;
+
+        let to_unicode = match font.get(b"/ToUnicode").map(|cmap| document.resolve(cmap)).transpose()? {
+            Some(Object::Stream(cmap)) => cmap.decode().ok()
+                .and_then(|bytes| ToUnicode::parse(&bytes).ok())
+                .filter(|cmap| !cmap.is_empty()),
+            _ => None,
+        };
+
+        Ok(Self { metrics, encoding, cmap, ordering, to_unicode })
+    }
+
+    /// Returns the glyph metrics.
+    pub fn metrics(&self) -> &FontMetrics {
+        &self.metrics
+    }
+
+    /// Returns `true` if the font is for vertical writing.
+    pub fn is_vertical(&self) -> bool {
+        self.cmap.as_ref().is_some_and(CMap::is_vertical)
+    }
+
+    /// Splits a string into character codes.
+    ///
+    /// Simple fonts use one byte per code; composite fonts follow the
+    /// code space ranges of their CMap.
+    pub fn codes(&self, bytes: &[u8]) -> Vec<(u32, usize)> {
+
+        match &self.cmap {
+            Some(cmap) => cmap.codes(bytes),
+            None => bytes.iter().map(|byte| (*byte as u32, 1)).collect(),
+        }
+    }
+
+    /// Returns the glyph selected by a character code: the code itself in
+    /// simple fonts, and its CID in composite fonts, if it is known.
+    pub fn glyph(&self, code: u32) -> Option<u32> {
+
+        match &self.cmap {
+            Some(cmap) => cmap.cid(code),
+            None => Some(code),
+        }
+    }
+
+    /// Returns the advance width of a character code.
+    ///
+    /// Codes whose CID is unknown, because the mapping of their predefined
+    /// CMap is not bundled, are half the default width when they select
+    /// half-width glyphs, and the default width otherwise.
+    pub fn width(&self, code: u32, length: usize) -> f64 {
+
+        match (self.glyph(code), &self.cmap) {
+            (Some(glyph), _) => self.metrics.width(glyph),
+            (None, Some(cmap)) if cmap.is_half_width(code, length) => self.metrics.default_width() / 2.0,
+            (None, _) => self.metrics.default_width(),
+        }
+    }
+
+    /// Returns the vertical metrics of a character code: the vertical
+    /// advance (negative), and the horizontal and vertical position of
+    /// the vertical origin.
+    pub fn vertical_metrics(&self, code: u32, length: usize) -> (f64, f64, f64) {
+
+        match self.glyph(code) {
+            Some(glyph) => self.metrics.vertical_metrics(glyph),
+            None => self.metrics.default_vertical_metrics(self.width(code, length)),
+        }
+    }
+
+    /// Returns `true` if word spacing applies to a character code: the
+    /// single-byte code 32.
+    pub fn is_word_space(&self, code: u32, length: usize) -> bool {
+        code == 32 && length == 1
+    }
+
+    /// Returns the text of a character code.
+    ///
+    /// The `/ToUnicode` CMap is used first, then the glyph names of the
+    /// encoding, or the codes of a Unicode CMap and the Roman CIDs of a
+    /// known character collection. Unmapped codes of simple fonts are read
+    /// as Latin-1; other unmapped codes, whose CIDs or glyph ids say
+    /// nothing of the character, are U+FFFD.
+    pub fn text(&self, code: u32, length: usize) -> String {
+
+        if let Some(text) = self.to_unicode.as_ref().and_then(|cmap| cmap.get(code)) {
+            return text;
+        }
+
+        if let Some(text) = self.cmap.as_ref().and_then(|cmap| cmap.text(code, length)) {
+            return text;
+        }
+
+        if let Some(ordering) = self.ordering
+            && let Some(character) = self.cmap.as_ref().and_then(|cmap| cmap.cid(code)).and_then(|cid| ordering.to_unicode(cid))
+        {
+            return character.to_string();
+        }
+
+        if length == 1 && let Some(text) = self.encoding.as_ref().and_then(|encoding| encoding.to_unicode(code as u8)) {
+            return text;
+        }
+
+        match length {
+            1 if self.cmap.is_none() => char::from(code as u8).to_string(),
+            _ => char::REPLACEMENT_CHARACTER.to_string(),
+        }
+    }
+}
+
+/// Returns `true` if a simple font uses its built-in encoding by default:
+/// Type 3 fonts, the Symbol and ZapfDingbats fonts, and symbolic fonts.
+fn is_symbolic(document: &Document, font: &Dicionary) -> Result<bool, String> {
+
+    let name = |key: &[u8]| font.get(key).and_then(Object::as_name).map(|name| name.as_bytes().to_vec());
+
+    if name(b"/Subtype").as_deref() == Some(b"/Type3") || matches!(name(b"/BaseFont").as_deref(), Some(b"/Symbol" | b"/ZapfDingbats")) {
+        return Ok(true);
+    }
+
+    let flags = match font.get(b"/FontDescriptor").map(|descriptor| document.resolve(descriptor)).transpose()? {
+        Some(Object::Dicionary(descriptor)) => descriptor.get(b"/Flags").map(|flags| document.resolve(flags)).transpose()?.and_then(|flags| flags.as_i64()),
+        _ => None,
+    };
+
+    Ok(flags.is_some_and(|flags| flags & SYMBOLIC != 0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Font;
+    use crate::document::Document;
+    use crate::document::tests::build_pdf;
+    use crate::object::ObjectId;
+
+    #[test]
+    fn should_decode_with_to_unicode_and_encoding() {
+        let source = build_pdf(&[
+            b"<</Type /Catalog /Pages 2 0 R>>",
+            b"<</Type /Pages /Kids [] /Count 0>>",
+            b"<</Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding <</BaseEncoding /WinAnsiEncoding /Differences [65 /eacute]>> /ToUnicode 4 0 R>>",
+            b"<</Length 39>>\nstream\n1 beginbfchar <42> <00660069> endbfchar\nendstream",
+            b"<</Type /Font /Subtype /Type1 /BaseFont /Helvetica>>",
+            b"<</Type /Font /Subtype /Type0 /BaseFont /Mincho /Encoding /UniJIS-UCS2-H /DescendantFonts [8 0 R]>>",
+            b"<</Type /Font /Subtype /Type0 /BaseFont /Mincho /Encoding /Identity-H /DescendantFonts [8 0 R]>>",
+            b"<</Type /Font /Subtype /CIDFontType0 /BaseFont /Mincho /CIDSystemInfo <</Registry (Adobe) /Ordering (Japan1) /Supplement 6>>>>",
+            b"<</Type /Font /Subtype /Type0 /BaseFont /Mincho /Encoding /90ms-RKSJ-H /DescendantFonts [8 0 R]>>",
+        ]);
+
+        let document = Document::read(&source).unwrap();
+        let font = |number| Font::read(&document, document.get(ObjectId::new(number, 0)).unwrap().as_dicionary().unwrap()).unwrap();
+
+        let mapped = font(3);
+        assert_eq!(mapped.text(0x41, 1), "\u{e9}");
+        assert_eq!(mapped.text(0x42, 1), "fi");
+        assert_eq!(mapped.text(0x80, 1), "\u{20ac}");
+
+        let standard = font(5);
+        assert_eq!(standard.text(0x27, 1), "\u{2019}");
+        assert_eq!(standard.text(0xA0, 1), "\u{a0}");
+        assert_eq!(standard.width(0x57, 1), 944.0);
+        assert_eq!(standard.width(0x27, 1), 222.0);
+        assert_eq!(standard.metrics().ascent(), 718.0);
+        assert_eq!(mapped.width(0x41, 1), 556.0);
+
+        let unicode = font(6);
+        assert_eq!(unicode.codes(b"\x65\xe5\x67\x2c"), vec![(0x65E5, 2), (0x672C, 2)]);
+        assert_eq!(unicode.text(0x65E5, 2), "\u{65e5}");
+        assert_eq!(unicode.glyph(0x65E5), None);
+        assert_eq!(unicode.width(0x65E5, 2), 1000.0);
+        assert_eq!(unicode.width(0x0041, 2), 500.0);
+        assert_eq!(unicode.width(0xFF71, 2), 500.0);
+
+        let legacy = font(9);
+        assert_eq!(legacy.codes(b"A\x82\xa0\xb1"), vec![(0x41, 1), (0x82A0, 2), (0xB1, 1)]);
+        assert_eq!(legacy.width(0x41, 1), 500.0);
+        assert_eq!(legacy.width(0x82A0, 2), 1000.0);
+        assert_eq!(legacy.width(0xB1, 1), 500.0);
+        assert_eq!(legacy.text(0x82A0, 2), "\u{fffd}");
+
+        let identity = font(7);
+        assert_eq!(identity.glyph(0x003D), Some(61));
+        assert_eq!(identity.text(0x003D, 2), "\u{a5}");
+        assert_eq!(identity.text(0x0022, 2), "A");
+        assert_eq!(identity.text(0x0279, 2), "\u{3000}");
+        assert_eq!(identity.text(0x034A, 2), "\u{3041}");
+        assert_eq!(identity.text(0x0465, 2), "\u{4e9c}");
+        assert_eq!(identity.text(0x1D35, 2), "\u{7464}");
+        assert_eq!(identity.text(0xFFFF, 2), "\u{fffd}");
+    }
+}
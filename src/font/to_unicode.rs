@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+
+use crate::content::{parse_operations, Operator};
+use crate::font::glyph_to_unicode;
+use crate::object::Object;
+
+/// A `/ToUnicode` CMap: the text of the character codes of a font.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ToUnicode {
+    /// Text by character code (`bfchar`, and `bfrange` with arrays).
+    chars: HashMap<u32, String>,
+    /// Ranges of codes (`bfrange`): the first and last codes, and the
+    /// UTF-16 text of the first code, whose last unit is incremented
+    /// for each following code.
+    ranges: Vec<(u32, u32, Vec<u16>)>,
+}
+
+impl ToUnicode {
+
+    /// Parses a decoded `/ToUnicode` CMap stream.
+    ///
+    /// Only the `bfchar` and `bfrange` mappings are read; the rest of
+    /// the CMap program is ignored.
+    pub fn parse(bytes: &[u8]) -> Result<Self, String> {
+
+        let mut cmap = Self::default();
+
+        for operation in parse_operations(bytes)? {
+
+            let Operator::Other(operator) = operation.operator() else {
+                continue;
+            };
+
+            let operands = operation.operands();
+
+            match operator.as_slice() {
+                b"endbfchar" => {
+                    for pair in operands.chunks_exact(2) {
+                        if let (Some(code), Some(text)) = (code(&pair[0]), text(&pair[1])) {
+                            cmap.chars.insert(code, text);
+                        }
+                    }
+                }
+                b"endbfrange" => {
+                    for range in operands.chunks_exact(3) {
+                        cmap.add_range(&range[0], &range[1], &range[2]);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(cmap)
+    }
+
+    /// Adds a `bfrange` mapping.
+    fn add_range(&mut self, first: &Object, last: &Object, destination: &Object) {
+
+        let (Some(first), Some(last)) = (code(first), code(last)) else {
+            return;
+        };
+
+        match destination {
+            Object::Array(texts) => {
+                for (code, text) in (first..=last).zip(texts.as_objects()) {
+                    if let Some(text) = text_string(text) {
+                        self.chars.insert(code, text);
+                    }
+                }
+            }
+            _ => {
+                if let Some(units) = destination.as_string_bytes().map(|bytes| utf16_units(&bytes)).filter(|units| !units.is_empty()) && first <= last {
+                    self.ranges.push((first, last, units));
+                }
+            }
+        }
+    }
+
+    /// Returns the text of a character code, if it is mapped.
+    pub fn get(&self, code: u32) -> Option<String> {
+
+        if let Some(text) = self.chars.get(&code) {
+            return Some(text.clone());
+        }
+
+        // Later ranges override earlier ones.
+        let (first, _, units) = self.ranges.iter().rev().find(|(first, last, _)| (*first..=*last).contains(&code))?;
+
+        let mut units = units.clone();
+        let last = units.len() - 1;
+        units[last] = units[last].wrapping_add((code - first) as u16);
+
+        Some(String::from_utf16_lossy(&units))
+    }
+
+    /// Returns `true` if no code is mapped.
+    pub fn is_empty(&self) -> bool {
+        self.chars.is_empty() && self.ranges.is_empty()
+    }
+}
+
+/// Returns a character code from a hexadecimal string of up to four bytes.
+fn code(object: &Object) -> Option<u32> {
+
+    let bytes = object.as_string_bytes()?;
+
+    (bytes.len() <= 4).then(|| bytes.iter().fold(0, |code, byte| (code << 8) | *byte as u32))
+}
+
+/// Returns the text of a `bfchar` destination: a UTF-16BE string, or a glyph name.
+fn text(object: &Object) -> Option<String> {
+
+    match object {
+        Object::Name(name) => glyph_to_unicode(&String::from_utf8_lossy(&name.as_bytes()[1..])),
+        _ => text_string(object),
+    }
+}
+
+/// Returns the text of a UTF-16BE string.
+fn text_string(object: &Object) -> Option<String> {
+    object.as_string_bytes().map(|bytes| String::from_utf16_lossy(&utf16_units(&bytes)))
+}
+
+/// Splits big-endian bytes into UTF-16 units.
+fn utf16_units(bytes: &[u8]) -> Vec<u16> {
+    bytes.chunks(2).map(|unit| unit.iter().fold(0, |value, byte| (value << 8) | *byte as u16)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ToUnicode;
+
+    #[test]
+    fn should_parse_bfchar_and_bfrange() {
+        let cmap = ToUnicode::parse(b"/CIDInit /ProcSet findresource begin 12 dict begin begincmap
+/CIDSystemInfo << /Registry (Adobe) /Ordering (UCS) /Supplement 0 >> def
+/CMapName /Adobe-Identity-UCS def /CMapType 2 def
+1 begincodespacerange <0000> <FFFF> endcodespacerange
+3 beginbfchar <0003> <0020> <0011> <D83DDE00> <0012> /quoteright endbfchar
+2 beginbfrange <0024> <0026> <0041> <0030> <0031> [<0066006C> <00E9>] endbfrange
+endcmap CMapName currentdict /CMap defineresource pop end end").unwrap();
+
+        assert_eq!(cmap.get(0x03).as_deref(), Some(" "));
+        assert_eq!(cmap.get(0x11).as_deref(), Some("\u{1f600}"));
+        assert_eq!(cmap.get(0x12).as_deref(), Some("\u{2019}"));
+        assert_eq!(cmap.get(0x26).as_deref(), Some("C"));
+        assert_eq!(cmap.get(0x30).as_deref(), Some("fl"));
+        assert_eq!(cmap.get(0x31).as_deref(), Some("\u{e9}"));
+        assert_eq!(cmap.get(0x27), None);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,120 @@
+use crate::geometry::Rectangle;
+use crate::object::Object;
+
+/// An affine transformation matrix (`[a b c d e f]`).
+///
+/// Points are row vectors, so `x' = a*x + c*y + e` and `y' = b*x + d*y + f`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Matrix {
+    /// The horizontal scaling component.
+    pub a: f64,
+    /// The vertical skewing component.
+    pub b: f64,
+    /// The horizontal skewing component.
+    pub c: f64,
+    /// The vertical scaling component.
+    pub d: f64,
+    /// The horizontal translation.
+    pub e: f64,
+    /// The vertical translation.
+    pub f: f64,
+}
+
+impl Default for Matrix {
+
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Matrix {
+
+    /// The identity matrix.
+    pub const IDENTITY: Matrix = Matrix { a: 1.0, b: 0.0, c: 0.0, d: 1.0, e: 0.0, f: 0.0 };
+
+    /// Creates a new `Matrix` from its six components.
+    pub fn new(a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) -> Self {
+        Self { a, b, c, d, e, f }
+    }
+
+    /// Creates a `Matrix` from a PDF matrix array (`[a b c d e f]`).
+    pub fn from_object(object: &Object) -> Option<Self> {
+
+        let values = object.as_array()?.as_objects().iter()
+            .map(Object::as_f64)
+            .collect::<Option<Vec<_>>>()?;
+
+        match values[..] {
+            [a, b, c, d, e, f] => Some(Self::new(a, b, c, d, e, f)),
+            _ => None,
+        }
+    }
+
+    /// Creates a translation matrix.
+    pub fn translate(x: f64, y: f64) -> Self {
+        Self::new(1.0, 0.0, 0.0, 1.0, x, y)
+    }
+
+    /// Returns the matrix that applies `self` first and then `other`.
+    pub fn multiply(&self, other: &Matrix) -> Matrix {
+        Matrix {
+            a: self.a * other.a + self.b * other.c,
+            b: self.a * other.b + self.b * other.d,
+            c: self.c * other.a + self.d * other.c,
+            d: self.c * other.b + self.d * other.d,
+            e: self.e * other.a + self.f * other.c + other.e,
+            f: self.e * other.b + self.f * other.d + other.f,
+        }
+    }
+
+    /// Transforms a point.
+    pub fn transform_point(&self, x: f64, y: f64) -> (f64, f64) {
+        (self.a * x + self.c * y + self.e, self.b * x + self.d * y + self.f)
+    }
+
+    /// Returns the bounding box of a transformed rectangle.
+    pub fn transform_rectangle(&self, rectangle: &Rectangle) -> Rectangle {
+
+        let corners = [
+            self.transform_point(rectangle.left(), rectangle.bottom()),
+            self.transform_point(rectangle.right(), rectangle.bottom()),
+            self.transform_point(rectangle.left(), rectangle.top()),
+            self.transform_point(rectangle.right(), rectangle.top()),
+        ];
+
+        Rectangle::bounding(&corners).unwrap_or(*rectangle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Matrix;
+    use crate::geometry::Rectangle;
+    use crate::object::Object;
+
+    #[test]
+    fn should_concatenate_and_transform() {
+        let scale = Matrix::new(2.0, 0.0, 0.0, 3.0, 0.0, 0.0);
+        let translate = Matrix::translate(10.0, 20.0);
+
+        assert_eq!(scale.multiply(&translate).transform_point(1.0, 1.0), (12.0, 23.0));
+        assert_eq!(translate.multiply(&scale).transform_point(1.0, 1.0), (22.0, 63.0));
+        assert_eq!(Matrix::IDENTITY.multiply(&scale), scale);
+    }
+
+    #[test]
+    fn should_transform_rectangles_to_bounding_boxes() {
+        let rotate = Matrix::new(0.0, 1.0, -1.0, 0.0, 0.0, 0.0);
+        let rectangle = Rectangle::new(0.0, 0.0, 10.0, 5.0);
+
+        assert_eq!(rotate.transform_rectangle(&rectangle), Rectangle::new(-5.0, 0.0, 0.0, 10.0));
+    }
+
+    #[test]
+    fn should_create_from_object() {
+        let matrix = Matrix::from_object(&Object::parse(b"[2 0 0 2 10 20.5]").unwrap()).unwrap();
+
+        assert_eq!(matrix, Matrix::new(2.0, 0.0, 0.0, 2.0, 10.0, 20.5));
+        assert_eq!(Matrix::from_object(&Object::parse(b"[1 0 0 1]").unwrap()), None);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,6 @@
+//! This module contains the geometric primitives used on page coordinates.
+mod matrix;
+mod rectangle;
+
+pub use crate::geometry::matrix::Matrix;
+pub use crate::geometry::rectangle::Rectangle;
\ No newline at end of file
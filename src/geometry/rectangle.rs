@@ -0,0 +1,147 @@
+use crate::object::Object;
+
+/// A rectangle in default user space (i.e `/MediaBox`).
+///
+/// The corners are normalized, so that `left <= right` and `bottom <= top`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rectangle {
+    /// The lower-left x coordinate.
+    left: f64,
+    /// The lower-left y coordinate.
+    bottom: f64,
+    /// The upper-right x coordinate.
+    right: f64,
+    /// The upper-right y coordinate.
+    top: f64,
+}
+
+impl Rectangle {
+
+    /// Creates a new `Rectangle` from two opposite corners.
+    pub fn new(x1: f64, y1: f64, x2: f64, y2: f64) -> Self {
+        Self {
+            left: x1.min(x2),
+            bottom: y1.min(y2),
+            right: x1.max(x2),
+            top: y1.max(y2),
+        }
+    }
+
+    /// Creates a `Rectangle` from a PDF rectangle array (`[x1 y1 x2 y2]`).
+    pub fn from_object(object: &Object) -> Option<Self> {
+
+        let values = object.as_array()?.as_objects().iter()
+            .map(Object::as_f64)
+            .collect::<Option<Vec<_>>>()?;
+
+        match values[..] {
+            [x1, y1, x2, y2] => Some(Self::new(x1, y1, x2, y2)),
+            _ => None,
+        }
+    }
+
+    /// Returns the smallest rectangle containing all the given points.
+    pub fn bounding(points: &[(f64, f64)]) -> Option<Self> {
+
+        let (first, rest) = points.split_first()?;
+
+        let (left, bottom, right, top) = rest.iter().fold(
+            (first.0, first.1, first.0, first.1),
+            |(left, bottom, right, top), (x, y)| (left.min(*x), bottom.min(*y), right.max(*x), top.max(*y)),
+        );
+
+        Some(Self { left, bottom, right, top })
+    }
+
+    /// Returns the smallest rectangle containing both rectangles.
+    pub fn union(&self, other: &Rectangle) -> Rectangle {
+        Rectangle {
+            left: self.left.min(other.left),
+            bottom: self.bottom.min(other.bottom),
+            right: self.right.max(other.right),
+            top: self.top.max(other.top),
+        }
+    }
+
+    /// Returns the lower-left x coordinate.
+    pub fn left(&self) -> f64 {
+        self.left
+    }
+
+    /// Returns the lower-left y coordinate.
+    pub fn bottom(&self) -> f64 {
+        self.bottom
+    }
+
+    /// Returns the upper-right x coordinate.
+    pub fn right(&self) -> f64 {
+        self.right
+    }
+
+    /// Returns the upper-right y coordinate.
+    pub fn top(&self) -> f64 {
+        self.top
+    }
+
+    /// Returns the width of the rectangle.
+    pub fn width(&self) -> f64 {
+        self.right - self.left
+    }
+
+    /// Returns the height of the rectangle.
+    pub fn height(&self) -> f64 {
+        self.top - self.bottom
+    }
+
+    /// Returns the overlapping area of two rectangles, if they overlap.
+    pub fn intersection(&self, other: &Rectangle) -> Option<Rectangle> {
+
+        let left = self.left.max(other.left);
+        let bottom = self.bottom.max(other.bottom);
+        let right = self.right.min(other.right);
+        let top = self.top.min(other.top);
+
+        (left < right && bottom < top).then(|| Rectangle::new(left, bottom, right, top))
+    }
+
+    /// Returns `true` if the rectangles overlap with a non-empty area.
+    pub fn intersects(&self, other: &Rectangle) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Returns `true` if the other rectangle lies entirely within this one.
+    pub fn contains(&self, other: &Rectangle) -> bool {
+        self.left <= other.left && self.bottom <= other.bottom && self.right >= other.right && self.top >= other.top
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Rectangle;
+    use crate::object::Object;
+
+    #[test]
+    fn should_normalize_and_parse_rectangles() {
+        let rectangle = Rectangle::from_object(&Object::parse(b"[612 396 0 0.5]").unwrap()).unwrap();
+
+        assert_eq!(rectangle, Rectangle::new(0.0, 0.5, 612.0, 396.0));
+        assert_eq!(rectangle.width(), 612.0);
+        assert_eq!(rectangle.height(), 395.5);
+        assert!(Rectangle::from_object(&Object::parse(b"[0 0 612]").unwrap()).is_none());
+        assert!(Rectangle::from_object(&Object::parse(b"[0 0 612 /A]").unwrap()).is_none());
+    }
+
+    #[test]
+    fn should_intersect_rectangles() {
+        let page = Rectangle::new(0.0, 0.0, 100.0, 100.0);
+        let area = Rectangle::new(50.0, 50.0, 150.0, 150.0);
+
+        assert_eq!(page.intersection(&area), Some(Rectangle::new(50.0, 50.0, 100.0, 100.0)));
+        assert!(!page.intersects(&Rectangle::new(100.0, 0.0, 200.0, 100.0)));
+        assert!(page.contains(&Rectangle::new(10.0, 10.0, 20.0, 20.0)));
+        assert!(!page.contains(&area));
+        assert_eq!(page.union(&area), Rectangle::new(0.0, 0.0, 150.0, 150.0));
+        assert_eq!(Rectangle::bounding(&[(3.0, 1.0), (-1.0, 4.0)]), Some(Rectangle::new(-1.0, 1.0, 3.0, 4.0)));
+        assert_eq!(Rectangle::bounding(&[]), None);
+    }
+}
\ No newline at end of file
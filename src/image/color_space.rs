@@ -0,0 +1,201 @@
+use crate::document::Document;
+use crate::object::Object;
+
+/// Maximum nesting of color spaces (i.e `/Indexed` over `/ICCBased`).
+const MAX_COLOR_SPACE_DEPTH: usize = 4;
+
+/// The color space of image samples (`/ColorSpace`).
+///
+/// CIE-based spaces are read as the device space with the same number
+/// of components, which is enough to blank pixels.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ColorSpace {
+    /// One gray component (`/DeviceGray`, `/CalGray`).
+    Gray,
+    /// Red, green and blue components (`/DeviceRGB`, `/CalRGB`).
+    Rgb,
+    /// Cyan, magenta, yellow and black components (`/DeviceCMYK`).
+    Cmyk,
+    /// One index into a table of colors of a base space (`/Indexed`).
+    Indexed {
+        /// The color space of the table.
+        base: Box<ColorSpace>,
+        /// The highest valid index.
+        hival: u8,
+        /// The colors of the table, with one byte per base component.
+        lookup: Vec<u8>,
+    },
+}
+
+impl ColorSpace {
+
+    /// Reads a color space from a name or an array.
+    pub fn read(document: &Document, object: &Object) -> Result<Self, String> {
+        Self::read_nested(document, object, 0)
+    }
+
+    /// Reads a color space, limiting the nesting of base spaces.
+    fn read_nested(document: &Document, object: &Object, depth: usize) -> Result<Self, String> {
+
+        if depth > MAX_COLOR_SPACE_DEPTH {
+            return Err("Color spaces are nested too deeply".to_string());
+        }
+
+        let object = document.resolve(object)?;
+
+        let (family, operands) = match &object {
+            Object::Name(name) => (name.as_bytes(), &[][..]),
+            Object::Array(array) => match array.as_objects() {
+                [Object::Name(name), operands @ ..] => (name.as_bytes(), operands),
+                _ => return Err("Color space array must begin with a name".to_string()),
+            },
+            _ => return Err("Color space must be a name or an array".to_string()),
+        };
+
+        match family {
+            b"/DeviceGray" | b"/G" | b"/CalGray" => Ok(ColorSpace::Gray),
+            b"/DeviceRGB" | b"/RGB" | b"/CalRGB" => Ok(ColorSpace::Rgb),
+            b"/DeviceCMYK" | b"/CMYK" => Ok(ColorSpace::Cmyk),
+            b"/ICCBased" => Self::read_icc_based(document, operands, depth),
+            b"/Indexed" | b"/I" => Self::read_indexed(document, operands, depth),
+            _ => Err(format!("Unsupported color space: {}", String::from_utf8_lossy(family))),
+        }
+    }
+
+    /// Reads an `/ICCBased` color space from its `/Alternate` space or,
+    /// failing that, its number of components.
+    fn read_icc_based(document: &Document, operands: &[Object], depth: usize) -> Result<Self, String> {
+
+        let Some(profile) = operands.first() else {
+            return Err("/ICCBased color space has no profile".to_string());
+        };
+
+        let profile = document.resolve(profile)?;
+
+        let Some(dicionary) = profile.as_dicionary() else {
+            return Err("/ICCBased profile must be a stream".to_string());
+        };
+
+        if let Some(alternate) = dicionary.get(b"/Alternate") {
+            return Self::read_nested(document, alternate, depth + 1);
+        }
+
+        let components = dicionary.get(b"/N").map(|n| document.resolve(n)).transpose()?.and_then(|n| n.as_i64());
+
+        match components {
+            Some(1) => Ok(ColorSpace::Gray),
+            Some(3) => Ok(ColorSpace::Rgb),
+            Some(4) => Ok(ColorSpace::Cmyk),
+            _ => Err("/ICCBased profile must have 1, 3 or 4 components".to_string()),
+        }
+    }
+
+    /// Reads an `/Indexed` color space from its base space, highest
+    /// index and lookup table.
+    ///
+    /// A short lookup table is padded with zero bytes.
+    fn read_indexed(document: &Document, operands: &[Object], depth: usize) -> Result<Self, String> {
+
+        let [base, hival, lookup] = operands else {
+            return Err("/Indexed color space must have a base, a highest index and a lookup table".to_string());
+        };
+
+        let base = Self::read_nested(document, base, depth + 1)?;
+
+        if matches!(base, ColorSpace::Indexed { .. }) {
+            return Err("/Indexed color space cannot have an indexed base".to_string());
+        }
+
+        let hival = document.resolve(hival)?.as_i64()
+            .and_then(|hival| u8::try_from(hival).ok())
+            .ok_or_else(|| "/Indexed highest index must be between 0 and 255".to_string())?;
+
+        let mut lookup = match document.resolve(lookup)? {
+            Object::Stream(stream) => stream.decode()?,
+            object => object.as_string_bytes().ok_or_else(|| "/Indexed lookup table must be a string or a stream".to_string())?,
+        };
+
+        lookup.resize((hival as usize + 1) * base.components(), 0);
+
+        Ok(ColorSpace::Indexed { base: Box::new(base), hival, lookup })
+    }
+
+    /// Returns the number of components of a color.
+    pub fn components(&self) -> usize {
+        match self {
+            ColorSpace::Gray | ColorSpace::Indexed { .. } => 1,
+            ColorSpace::Rgb => 3,
+            ColorSpace::Cmyk => 4,
+        }
+    }
+
+    /// Returns the device color space with the given number of components.
+    pub fn from_components(components: usize) -> Option<Self> {
+        match components {
+            1 => Some(ColorSpace::Gray),
+            3 => Some(ColorSpace::Rgb),
+            4 => Some(ColorSpace::Cmyk),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of the device color space (i.e `/DeviceRGB`).
+    pub fn name(&self) -> &'static [u8] {
+        match self {
+            ColorSpace::Gray => b"/DeviceGray",
+            ColorSpace::Rgb => b"/DeviceRGB",
+            ColorSpace::Cmyk => b"/DeviceCMYK",
+            ColorSpace::Indexed { base, .. } => base.name(),
+        }
+    }
+
+    /// Returns the 8-bit samples of black in the device color space.
+    pub fn black(&self) -> &'static [u8] {
+        match self {
+            ColorSpace::Gray => &[0],
+            ColorSpace::Rgb => &[0, 0, 0],
+            ColorSpace::Cmyk => &[0, 0, 0, 255],
+            ColorSpace::Indexed { base, .. } => base.black(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ColorSpace;
+    use crate::document::Document;
+    use crate::document::tests::build_pdf;
+    use crate::object::Object;
+
+    #[test]
+    fn should_read_color_spaces() {
+        let source = build_pdf(&[
+            b"<</Type /Catalog /Pages 2 0 R>>",
+            b"<</Type /Pages /Kids [] /Count 0>>",
+            b"<</N 3 /Length 0>>\nstream\n\nendstream",
+            b"<</N 4 /Alternate /DeviceGray /Length 0>>\nstream\n\nendstream",
+            b"<</Length 3>>\nstream\n\x00\x80\xFF\nendstream",
+        ]);
+
+        let document = Document::read(&source).unwrap();
+        let read = |bytes: &[u8]| ColorSpace::read(&document, &Object::parse(bytes).unwrap());
+
+        assert_eq!(read(b"/DeviceCMYK").unwrap(), ColorSpace::Cmyk);
+        assert_eq!(read(b"[/CalRGB <</WhitePoint [1 1 1]>>]").unwrap(), ColorSpace::Rgb);
+        assert_eq!(read(b"[/ICCBased 3 0 R]").unwrap(), ColorSpace::Rgb);
+        assert_eq!(read(b"[/ICCBased 4 0 R]").unwrap(), ColorSpace::Gray);
+        assert_eq!(read(b"[/I /G 2 5 0 R]").unwrap(), ColorSpace::Indexed {
+            base: Box::new(ColorSpace::Gray),
+            hival: 2,
+            lookup: vec![0x00, 0x80, 0xFF],
+        });
+        assert_eq!(read(b"[/Indexed /DeviceRGB 1 <FF0000>]").unwrap(), ColorSpace::Indexed {
+            base: Box::new(ColorSpace::Rgb),
+            hival: 1,
+            lookup: vec![0xFF, 0, 0, 0, 0, 0],
+        });
+
+        assert!(read(b"/Pattern").is_err());
+        assert!(read(b"[/Indexed [/Indexed /G 1 <00FF>] 1 <0001>]").is_err());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,6 @@
+//! This module contains the decoding of image XObjects into pixels.
+mod color_space;
+mod raster;
+
+pub use crate::image::color_space::ColorSpace;
+pub use crate::image::raster::Raster;
\ No newline at end of file
@@ -0,0 +1,343 @@
+use crate::document::Document;
+use crate::filter::encode_stream;
+use crate::geometry::{Matrix, Rectangle};
+use crate::image::ColorSpace;
+use crate::object::{Boolean, Dicionary, Integer, Name, Object, Stream};
+
+/// The decoded pixels of an image XObject.
+///
+/// Samples are 8 bits per component, interleaved, from the top row
+/// down. Indexed images are expanded to the colors of their base space.
+/// Stencil masks (`/ImageMask true`) are gray, painted pixels being 0
+/// and unpainted ones 255.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Raster {
+    width: usize,
+    height: usize,
+    color_space: ColorSpace,
+    mask: bool,
+    samples: Vec<u8>,
+}
+
+impl Raster {
+
+    /// Decodes the pixels of an image XObject.
+    ///
+    /// Images without `/ColorSpace` (i.e JPEG data) take the device space
+    /// matching the number of components of their data.
+    pub fn decode(document: &Document, image: &Stream) -> Result<Self, String> {
+
+        let dicionary = image.dicionary();
+        let entry = |key: &[u8]| dicionary.get(key).map(|value| document.resolve(value)).transpose();
+
+        let mask = matches!(entry(b"/ImageMask")?, Some(Object::Boolean(mask)) if mask.as_bool());
+
+        let dimension = |key: &[u8]| -> Result<usize, String> {
+            entry(key)?.and_then(|value| value.as_i64())
+                .and_then(|value| usize::try_from(value).ok())
+                .filter(|value| *value > 0)
+                .ok_or_else(|| format!("Image {} must be a positive integer", String::from_utf8_lossy(key)))
+        };
+
+        let width = dimension(b"/Width")?;
+        let height = dimension(b"/Height")?;
+
+        let bits = match entry(b"/BitsPerComponent")?.and_then(|value| value.as_i64()) {
+            Some(1) | None if mask => 1,
+            Some(bits) if mask => return Err(format!("Image masks must have 1 bit per component, not {}", bits)),
+            Some(bits @ (1 | 2 | 4 | 8 | 16)) => bits as usize,
+            None => 8,
+            Some(bits) => return Err(format!("Unsupported bits per component: {}", bits)),
+        };
+
+        let data = image.decode()?;
+
+        // The samples of masks read as gray, `/Decode` choosing the painted value.
+        let color_space = match entry(b"/ColorSpace")? {
+            _ if mask => ColorSpace::Gray,
+            Some(color_space) => ColorSpace::read(document, &color_space)?,
+            None => [1, 3, 4].into_iter()
+                .find(|components| row_length(width, *components, bits) * height == data.len())
+                .and_then(ColorSpace::from_components)
+                .ok_or_else(|| "Image has no color space".to_string())?,
+        };
+
+        let components = color_space.components();
+        let row = row_length(width, components, bits);
+
+        if data.len() < row * height {
+            return Err(format!("Image data is too short: {} bytes for {} rows of {} bytes", data.len(), height, row));
+        }
+
+        let ranges = decode_ranges(entry(b"/Decode")?.as_ref(), &color_space, bits)?;
+        let maximum = ((1u32 << bits) - 1) as f64;
+
+        let (output, mut samples) = match &color_space {
+            ColorSpace::Indexed { base, .. } => ((**base).clone(), Vec::with_capacity(width * height * base.components())),
+            device => (device.clone(), Vec::with_capacity(width * height * components)),
+        };
+
+        for line in data.chunks(row).take(height) {
+            for index in 0..width * components {
+
+                let (minimum, extent) = ranges[index % components];
+                let value = minimum + sample(line, index, bits) as f64 * extent / maximum;
+
+                match &color_space {
+                    ColorSpace::Indexed { base, hival, lookup } => {
+                        let color = value.round().clamp(0.0, *hival as f64) as usize * base.components();
+                        samples.extend_from_slice(&lookup[color..color + base.components()]);
+                    }
+                    _ => samples.push((value * 255.0).round().clamp(0.0, 255.0) as u8),
+                }
+            }
+        }
+
+        Ok(Self { width, height, color_space: output, mask, samples })
+    }
+
+    /// Paints black the pixels that overlap an area, or leaves them
+    /// unpainted in stencil masks.
+    ///
+    /// `ctm` maps the unit square of the image to page space, as when the
+    /// image is painted. Returns the number of pixels blanked.
+    pub fn blank(&mut self, ctm: &Matrix, area: &Rectangle) -> usize {
+
+        if !ctm.transform_rectangle(&Rectangle::new(0.0, 0.0, 1.0, 1.0)).intersects(area) {
+            return 0;
+        }
+
+        let black = if self.mask { &[255] } else { self.color_space.black() };
+        let components = black.len();
+        let mut count = 0;
+
+        for row in 0..self.height {
+
+            let top = 1.0 - row as f64 / self.height as f64;
+            let bottom = 1.0 - (row + 1) as f64 / self.height as f64;
+
+            for column in 0..self.width {
+
+                let left = column as f64 / self.width as f64;
+                let right = (column + 1) as f64 / self.width as f64;
+
+                if ctm.transform_rectangle(&Rectangle::new(left, bottom, right, top)).intersects(area) {
+                    let start = (row * self.width + column) * components;
+                    self.samples[start..start + components].copy_from_slice(black);
+                    count += 1;
+                }
+            }
+        }
+
+        count
+    }
+
+    /// Creates an image XObject of the samples, compressed with `/FlateDecode`.
+    ///
+    /// Stencil masks stay masks of 1 bit per pixel, 0 being painted.
+    pub fn to_stream(&self) -> Result<Stream, String> {
+
+        let integer = |value: usize| Object::Integer(Integer::new(value.to_string().as_bytes()).unwrap());
+        let name = |value: &[u8]| Object::Name(Name::new(value).unwrap());
+
+        let mut dicionary = Dicionary::new(vec![]);
+        dicionary.insert(Name::new(b"/Type").unwrap(), name(b"/XObject"));
+        dicionary.insert(Name::new(b"/Subtype").unwrap(), name(b"/Image"));
+        dicionary.insert(Name::new(b"/Width").unwrap(), integer(self.width));
+        dicionary.insert(Name::new(b"/Height").unwrap(), integer(self.height));
+
+        if self.mask {
+            dicionary.insert(Name::new(b"/ImageMask").unwrap(), Object::Boolean(Boolean::new(true)));
+            dicionary.insert(Name::new(b"/BitsPerComponent").unwrap(), integer(1));
+            dicionary.insert(Name::new(b"/Filter").unwrap(), name(b"/FlateDecode"));
+
+            let mut data = vec![0; row_length(self.width, 1, 1) * self.height];
+            let row = data.len() / self.height;
+
+            for (index, sample) in self.samples.iter().enumerate() {
+                if *sample != 0 {
+                    let (line, column) = (index / self.width, index % self.width);
+                    data[line * row + column / 8] |= 0x80 >> (column % 8);
+                }
+            }
+
+            return encode_stream(dicionary, &data);
+        }
+
+        dicionary.insert(Name::new(b"/ColorSpace").unwrap(), name(self.color_space.name()));
+        dicionary.insert(Name::new(b"/BitsPerComponent").unwrap(), integer(8));
+        dicionary.insert(Name::new(b"/Filter").unwrap(), name(b"/FlateDecode"));
+
+        encode_stream(dicionary, &self.samples)
+    }
+}
+
+/// Returns the number of bytes of a row, which is padded to a whole byte.
+fn row_length(width: usize, components: usize, bits: usize) -> usize {
+    (width * components * bits).div_ceil(8)
+}
+
+/// Reads the sample at the given index of a row.
+fn sample(row: &[u8], index: usize, bits: usize) -> u32 {
+
+    match bits {
+        16 => u16::from_be_bytes([row[index * 2], row[index * 2 + 1]]) as u32,
+        8 => row[index] as u32,
+        _ => {
+            let offset = index * bits;
+            let shift = 8 - bits - offset % 8;
+            (row[offset / 8] as u32 >> shift) & ((1 << bits) - 1)
+        }
+    }
+}
+
+/// Returns the minimum and extent of each component from a `/Decode` array.
+///
+/// Without one, components range from 0 to 1, and indexes from 0 to the
+/// largest sample value.
+fn decode_ranges(decode: Option<&Object>, color_space: &ColorSpace, bits: usize) -> Result<Vec<(f64, f64)>, String> {
+
+    let components = color_space.components();
+
+    let Some(decode) = decode else {
+        let maximum = match color_space {
+            ColorSpace::Indexed { .. } => ((1u32 << bits) - 1) as f64,
+            _ => 1.0,
+        };
+        return Ok(vec![(0.0, maximum); components]);
+    };
+
+    let values = decode.as_array()
+        .and_then(|array| array.as_objects().iter().map(Object::as_f64).collect::<Option<Vec<_>>>())
+        .filter(|values| values.len() == components * 2)
+        .ok_or_else(|| format!("/Decode must be an array of {} numbers", components * 2))?;
+
+    Ok(values.chunks(2).map(|pair| (pair[0], pair[1] - pair[0])).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Raster;
+    use crate::document::Document;
+    use crate::document::tests::build_pdf;
+    use crate::geometry::{Matrix, Rectangle};
+    use crate::image::ColorSpace;
+    use crate::object::ObjectId;
+
+    fn image_stream(dicionary: &[u8], data: &[u8]) -> Vec<u8> {
+        [
+            b"<<".as_slice(),
+            dicionary,
+            format!(" /Length {}>>\nstream\n", data.len()).as_bytes(),
+            data,
+            b"\nendstream",
+        ].concat()
+    }
+
+    fn decode_images(images: &[Vec<u8>]) -> Vec<Result<Raster, String>> {
+        let mut objects: Vec<&[u8]> = vec![b"<</Type /Catalog /Pages 2 0 R>>", b"<</Type /Pages /Kids [] /Count 0>>"];
+        objects.extend(images.iter().map(Vec::as_slice));
+
+        let document = Document::read(&build_pdf(&objects)).unwrap();
+
+        (0..images.len())
+            .map(|index| {
+                let image = document.get(ObjectId::new(index as u32 + 3, 0)).unwrap();
+                Raster::decode(&document, image.as_stream().unwrap())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn should_unpack_samples_of_every_depth() {
+        let images = decode_images(&[
+            image_stream(b"/Width 3 /Height 2 /ColorSpace /DeviceGray /BitsPerComponent 1 /Decode [1 0]", &[0b1010_0000, 0b0100_0000]),
+            image_stream(b"/Width 4 /Height 1 /ColorSpace /DeviceGray /BitsPerComponent 2", &[0b0001_1011]),
+            image_stream(b"/Width 3 /Height 1 /ColorSpace [/Indexed /DeviceRGB 2 <FF000000FF000000FF>] /BitsPerComponent 4", &[0x21, 0x00]),
+            image_stream(b"/Width 1 /Height 1 /ColorSpace /DeviceRGB /BitsPerComponent 16", &[0xFF, 0xFF, 0x80, 0x00, 0x00, 0x00]),
+            image_stream(b"/Width 2 /Height 1 /ColorSpace /DeviceCMYK /BitsPerComponent 8 /Decode [1 0 0 1 0 1 0 0.5]", &[0, 0, 0, 255, 255, 10, 20, 255]),
+        ]);
+
+        let gray = images[0].as_ref().unwrap();
+        assert_eq!((gray.width, gray.height), (3, 2));
+        assert_eq!(gray.samples, [0, 255, 0, 255, 0, 255]);
+
+        assert_eq!(images[1].as_ref().unwrap().samples, &[0, 85, 170, 255]);
+
+        let indexed = images[2].as_ref().unwrap();
+        assert_eq!(indexed.color_space, ColorSpace::Rgb);
+        assert_eq!(indexed.samples, &[0, 0, 255, 0, 255, 0, 255, 0, 0]);
+
+        assert_eq!(images[3].as_ref().unwrap().samples, &[255, 128, 0]);
+        assert_eq!(images[4].as_ref().unwrap().samples, &[255, 0, 0, 128, 0, 10, 20, 128]);
+    }
+
+    #[test]
+    fn should_decode_compressed_images() {
+        let images = decode_images(&[
+            image_stream(b"/Width 11 /Height 9 /BitsPerComponent 8 /Filter /DCTDecode", include_bytes!("../../tests/image/gray.jpg")),
+            image_stream(
+                b"/Width 2600 /Height 24 /ColorSpace /DeviceGray /BitsPerComponent 1 /Filter /CCITTFaxDecode /DecodeParms <</K -1 /Columns 2600 /Rows 24>>",
+                include_bytes!("../../tests/image/g4.fax"),
+            ),
+            image_stream(b"/Width 2 /Height 2 /ColorSpace /DeviceRGB /BitsPerComponent 8", &[0; 6]),
+        ]);
+
+        let jpeg = images[0].as_ref().unwrap();
+        assert_eq!(jpeg.color_space, ColorSpace::Gray);
+        assert_eq!(jpeg.samples.len(), 99);
+
+        let bitmap = include_bytes!("../../tests/image/fax.raw");
+        let fax = images[1].as_ref().unwrap();
+        let black = (0..2600 * 24).filter(|index| bitmap[index / 8] & (0x80 >> (index % 8)) != 0).count();
+        assert_eq!(fax.samples.iter().filter(|sample| **sample == 0).count(), black);
+
+        assert!(images[2].is_err());
+    }
+
+    #[test]
+    fn should_blank_stencil_masks_to_unpainted() {
+        let images = decode_images(&[
+            image_stream(b"/Width 2 /Height 2 /ImageMask true", &[0b0100_0000, 0b0000_0000]),
+            image_stream(b"/Width 2 /Height 2 /ImageMask true /Decode [1 0]", &[0b0100_0000, 0b0000_0000]),
+            image_stream(b"/Width 2 /Height 2 /ImageMask true /BitsPerComponent 8", &[0; 4]),
+        ]);
+
+        let mut mask = images[0].clone().unwrap();
+        assert_eq!(mask.samples, [0, 255, 0, 0]);
+        assert_eq!(images[1].as_ref().unwrap().samples, [255, 0, 255, 255]);
+        assert!(images[2].is_err());
+
+        let ctm = Matrix::new(20.0, 0.0, 0.0, 20.0, 0.0, 0.0);
+        assert_eq!(mask.blank(&ctm, &Rectangle::new(0.0, 0.0, 5.0, 5.0)), 1);
+        assert_eq!(mask.samples, [0, 255, 255, 0]);
+
+        let stream = mask.to_stream().unwrap();
+        assert_eq!(stream.decode().unwrap(), [0b0100_0000, 0b1000_0000]);
+
+        let document = Document::read(&build_pdf(&[b"<</Type /Catalog /Pages 2 0 R>>", b"<</Type /Pages /Kids [] /Count 0>>"])).unwrap();
+        assert_eq!(Raster::decode(&document, &stream).unwrap(), mask);
+    }
+
+    #[test]
+    fn should_blank_pixels_under_area() {
+        let mut raster = Raster { width: 4, height: 4, color_space: ColorSpace::Rgb, mask: false, samples: vec![255; 48] };
+
+        let ctm = Matrix::new(40.0, 0.0, 0.0, 40.0, 100.0, 100.0);
+        assert_eq!(raster.blank(&ctm, &Rectangle::new(100.0, 100.0, 115.0, 115.0)), 4);
+        assert_eq!(raster.blank(&ctm, &Rectangle::new(0.0, 0.0, 50.0, 50.0)), 0);
+
+        let black = (0..16).filter(|pixel| raster.samples[pixel * 3..pixel * 3 + 3] == [0, 0, 0]).collect::<Vec<_>>();
+        assert_eq!(black, vec![8, 9, 12, 13]);
+    }
+
+    #[test]
+    fn should_encode_as_image_stream() {
+        let raster = Raster { width: 2, height: 1, color_space: ColorSpace::Cmyk, mask: false, samples: vec![0, 0, 0, 255, 10, 20, 30, 40] };
+
+        let stream = raster.to_stream().unwrap();
+        let document = Document::read(&build_pdf(&[b"<</Type /Catalog /Pages 2 0 R>>", b"<</Type /Pages /Kids [] /Count 0>>"])).unwrap();
+
+        assert_eq!(Raster::decode(&document, &stream).unwrap(), raster);
+    }
+}
\ No newline at end of file
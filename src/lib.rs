@@ -1,12 +1,34 @@
 mod api;
 pub(crate) mod byte_source;
+pub(crate) mod content;
+pub(crate) mod document;
+pub(crate) mod filter;
+pub(crate) mod font;
+pub(crate) mod geometry;
+pub(crate) mod image;
 pub(crate) mod object;
 pub(crate) mod parser;
+pub(crate) mod redaction;
 pub(crate) mod specification;
 pub(crate) mod structure;
 pub(crate) mod token;
 pub(crate) mod value;
+pub(crate) mod writer;
 
 pub mod prelude {
     pub use crate::api::redactor::Redactor;
+    pub use crate::document::Document;
+    pub use crate::document::Linearization;
+    pub use crate::geometry::Rectangle;
+    pub use crate::redaction::Detector;
+    pub use crate::redaction::PatternDetector;
+    pub use crate::redaction::RedactionReport;
+    pub use crate::redaction::TextMatch;
+    pub use crate::redaction::TextPattern;
+    pub use crate::structure::xref::RepairReport;
+    pub use crate::structure::xref::Xref;
+    pub use crate::writer::RewriteReport;
+    pub use crate::writer::WriteOptions;
+    pub use crate::writer::Writer;
+    pub use crate::writer::XrefStyle;
 }
\ No newline at end of file
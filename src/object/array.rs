@@ -10,6 +10,7 @@ pub struct Array {
 impl Array {
     
     /// Creates a new `Array` from the given objects.
+    #[allow(clippy::needless_borrow)]
     pub fn new(objects: Vec<Object>) -> Self {
 
         let mut bytes = Vec::new();
@@ -108,5 +109,9 @@ mod tests {
             array.as_bytes(),
             b"[42 true /TestName null 3.14 (ABC\\tD\n) <</Elements [0 -0.4] /Count +2>> <4A6F686E> [1 2 3 [76 (F\\)\\r)]]]"
         );
+
+        let parsed = Object::parse(array.as_bytes()).unwrap();
+
+        assert_eq!(parsed.as_bytes(), array.as_bytes());
     }
 }
\ No newline at end of file
@@ -28,14 +28,17 @@ impl Boolean {
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
     use super::Boolean;
+    use crate::object::Object;
 
     #[test]
     fn should_create_true_boolean() {
         let boolean = Boolean::new(true);
         assert_eq!(boolean.as_bool(), true);
         assert_eq!(boolean.as_bytes(), b"true");
+        assert_eq!(Object::parse(boolean.as_bytes()).unwrap(), Object::Boolean(boolean));
     }
 
     #[test]
@@ -43,5 +46,6 @@ mod tests {
         let boolean = Boolean::new(false);
         assert_eq!(boolean.as_bool(), false);
         assert_eq!(boolean.as_bytes(), b"false");
+        assert_eq!(Object::parse(boolean.as_bytes()).unwrap(), Object::Boolean(boolean));
     }
 }
\ No newline at end of file
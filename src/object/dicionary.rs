@@ -1,4 +1,3 @@
-use core::str;
 use std::collections::HashMap;
 
 use crate::object::{Name, Object};
@@ -14,37 +13,34 @@ pub struct DicionaryEntry {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Dicionary {
     entries: HashMap<Name, Object>,
+    /// Keys in insertion order, so the byte representation is stable.
+    keys: Vec<Name>,
     bytes: Vec<u8>,
 }
 
 impl Dicionary {
     
     /// Creates a new `Dicionary` from the given entries.
+    /// 
+    /// When a key appears more than once, the last value wins.
     pub fn new(entries: Vec<DicionaryEntry>) -> Self {
 
-        let mut bytes = Vec::new();
+        let mut dicionary = Self {
+            entries: HashMap::with_capacity(entries.len()),
+            keys: Vec::with_capacity(entries.len()),
+            bytes: Vec::new(),
+        };
 
-        bytes.extend_from_slice(b"<<");
-        
-        for (index, entry) in &mut entries.iter().enumerate() {
+        for entry in entries {
 
-            if index > 0 && entries.len() > 1 {
-                bytes.push(b' ');
+            if dicionary.entries.insert(entry.key.clone(), entry.value).is_none() {
+                dicionary.keys.push(entry.key);
             }
-
-            bytes.extend_from_slice(entry.key.as_bytes());
-            bytes.push(b' ');
-            bytes.extend_from_slice(entry.value.as_bytes());
         }
-        
-        bytes.extend_from_slice(b">>");
 
-        let entries: HashMap<Name, Object> = entries.into_iter().map(|e| (e.key, e.value)).collect();
-        
-        Self {
-            entries,
-            bytes,
-        }
+        dicionary.update_bytes();
+
+        dicionary
     }
 
     /// Returns the entries of the Dicionary.
@@ -53,11 +49,89 @@ impl Dicionary {
         &self.entries
     }
 
+    /// Returns an iterator over the entries in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = (&Name, &Object)> {
+
+        self.keys.iter().map(|key| (key, &self.entries[key]))
+    }
+
+    /// Returns the value for the given key (i.e `b"/Type"`).
+    pub fn get(&self, key: &[u8]) -> Option<&Object> {
+
+        self.entries.get(key)
+    }
+
+    /// Returns `true` if the Dicionary contains the given key.
+    pub fn contains_key(&self, key: &[u8]) -> bool {
+
+        self.entries.contains_key(key)
+    }
+
+    /// Returns the number of entries.
+    pub fn len(&self) -> usize {
+
+        self.keys.len()
+    }
+
+    /// Returns `true` if the Dicionary has no entries.
+    pub fn is_empty(&self) -> bool {
+
+        self.keys.is_empty()
+    }
+
+    /// Inserts an entry and returns the previous value of the key, if any.
+    pub fn insert(&mut self, key: Name, value: Object) -> Option<Object> {
+
+        let previous = self.entries.insert(key.clone(), value);
+
+        if previous.is_none() {
+            self.keys.push(key);
+        }
+
+        self.update_bytes();
+
+        previous
+    }
+
+    /// Removes an entry and returns its value, if any.
+    pub fn remove(&mut self, key: &[u8]) -> Option<Object> {
+
+        let value = self.entries.remove(key)?;
+
+        self.keys.retain(|k| k.as_bytes() != key);
+        self.update_bytes();
+
+        Some(value)
+    }
+
     /// Returns the byte representation of the Dicionary.
     pub fn as_bytes(&self) -> &[u8] {
 
         &self.bytes
     }
+
+    /// Rebuilds the byte representation from the entries.
+    fn update_bytes(&mut self) {
+
+        let mut bytes = Vec::new();
+
+        bytes.extend_from_slice(b"<<");
+        
+        for (index, key) in self.keys.iter().enumerate() {
+
+            if index > 0 {
+                bytes.push(b' ');
+            }
+
+            bytes.extend_from_slice(key.as_bytes());
+            bytes.push(b' ');
+            bytes.extend_from_slice(self.entries[key].as_bytes());
+        }
+        
+        bytes.extend_from_slice(b">>");
+
+        self.bytes = bytes;
+    }
 }
 
 #[cfg(test)]
@@ -83,5 +157,30 @@ mod tests {
         let expected_bytes = b"<</Key1 42 /Key2 100>>";
 
         assert_eq!(bytes, expected_bytes);
+
+        let parsed = Object::parse(bytes).unwrap();
+
+        assert_eq!(parsed, Object::Dicionary(dicionary));
+    }
+
+    #[test]
+    fn should_insert_and_remove_entries_in_order() {
+        let mut dicionary = Dicionary::new(vec![
+            DicionaryEntry {
+                key: Name::new(b"/Type").unwrap(),
+                value: Object::Name(Name::new(b"/Page").unwrap()),
+            },
+        ]);
+
+        dicionary.insert(Name::new(b"/Rotate").unwrap(), Object::Integer(Integer::new(b"90").unwrap()));
+        dicionary.insert(Name::new(b"/Type").unwrap(), Object::Name(Name::new(b"/Pages").unwrap()));
+
+        assert_eq!(dicionary.as_bytes(), b"<</Type /Pages /Rotate 90>>");
+        assert_eq!(dicionary.get(b"/Rotate").unwrap().as_bytes(), b"90");
+        assert_eq!(dicionary.len(), 2);
+
+        assert!(dicionary.remove(b"/Type").is_some());
+        assert!(!dicionary.contains_key(b"/Type"));
+        assert_eq!(dicionary.as_bytes(), b"<</Rotate 90>>");
     }
 }
\ No newline at end of file
@@ -30,6 +30,24 @@ impl HexadecimalString {
         }
     }
 
+    /// Creates a new `HexadecimalString` holding the given decoded bytes.
+    pub fn from_value(value: &[u8]) -> Self {
+
+        Self::new(value.iter().map(|byte| HexadecimalChar::new(format!("{:02X}", byte).as_bytes())).collect())
+    }
+
+    /// Returns the characters of the Hexadecimal String.
+    pub fn characters(&self) -> &[HexadecimalChar] {
+
+        &self.chars
+    }
+
+    /// Returns the decoded bytes of the Hexadecimal String.
+    pub fn value(&self) -> Vec<u8> {
+
+        self.chars.iter().map(|c| c.as_byte()).collect()
+    }
+
     /// Returns the byte representation of the Hexadecimal String.
     pub fn as_bytes(&self) -> &[u8] {
 
@@ -40,6 +58,7 @@ impl HexadecimalString {
 #[cfg(test)]
 mod tests {
     use super::HexadecimalString;
+    use crate::object::Object;
     use crate::value::HexadecimalChar;
 
     #[test]
@@ -53,5 +72,19 @@ mod tests {
         ]);
 
         assert_eq!(hex_string.as_bytes(), b"<4A6F686E>");
+        assert_eq!(hex_string.value(), b"John");
+
+        let parsed = Object::parse(hex_string.as_bytes()).unwrap();
+
+        assert_eq!(parsed, Object::HexadecimalString(hex_string));
+    }
+
+    #[test]
+    fn should_create_from_value() {
+
+        let hex_string = HexadecimalString::from_value(&[0x00, 0xAB, b'J']);
+
+        assert_eq!(hex_string.as_bytes(), b"<00AB4A>");
+        assert_eq!(hex_string.value(), vec![0x00, 0xAB, b'J']);
     }
 }
\ No newline at end of file
@@ -18,9 +18,39 @@ impl Integer {
         Ok(Self { bytes: bytes.to_vec() })
     }
 
+    /// Creates a new `Integer` from the given Rust integer.
+    pub fn from_value(value: i64) -> Self {
+
+        Self { bytes: value.to_string().into_bytes() }
+    }
+
+    /// Returns the Rust integer.
+    /// 
+    /// Values out of the `i64` range are saturated.
+    pub fn as_i64(&self) -> i64 {
+
+        // Safe because the bytes are validated as ASCII digits on creation.
+        let text = std::str::from_utf8(&self.bytes).unwrap();
+
+        text.parse().unwrap_or(if text.starts_with('-') { i64::MIN } else { i64::MAX })
+    }
+
     /// Returns the byte representation of the Integer.
     pub fn as_bytes(&self) -> &[u8] {
 
         &self.bytes
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Integer;
+
+    #[test]
+    fn should_convert_integer_values() {
+        assert_eq!(Integer::new(b"+42").unwrap().as_i64(), 42);
+        assert_eq!(Integer::new(b"-0017").unwrap().as_i64(), -17);
+        assert_eq!(Integer::new(b"99999999999999999999").unwrap().as_i64(), i64::MAX);
+        assert_eq!(Integer::from_value(-5).as_bytes(), b"-5");
+    }
 }
\ No newline at end of file
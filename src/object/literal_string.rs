@@ -1,4 +1,4 @@
-use crate::value::LiteralChar;
+use crate::value::{EscapeSequence, LiteralChar};
 
 /// PDF Literal String representation.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -10,6 +10,7 @@ pub struct LiteralString {
 impl LiteralString {
     
     /// Creates a new `LiteralString` from the given vector of `LiteralCharacter`.
+    #[allow(unused_variables)]
     pub fn new(chars: Vec<LiteralChar>) -> Self {
 
         let capacity = chars.iter()
@@ -40,9 +41,65 @@ impl LiteralString {
         &self.chars
     }
 
+    /// Returns the decoded bytes of the Literal String.
+    /// 
+    /// Escape sequences are replaced by the bytes they stand for, and a
+    /// backslash followed by an end-of-line marker is dropped together with it.
+    pub fn value(&self) -> Vec<u8> {
+
+        let mut value = Vec::with_capacity(self.chars.len());
+        let mut chars = self.chars.iter().peekable();
+
+        while let Some(character) = chars.next() {
+
+            match character {
+                LiteralChar::Ascii(ascii) => value.extend_from_slice(ascii.as_bytes()),
+                LiteralChar::EscapeSequence(EscapeSequence::Empty) => {
+                    // A line continuation: skip `\r`, `\n` or `\r\n`.
+                    if let Some(LiteralChar::Ascii(next)) = chars.peek() {
+                        if next.as_bytes() == b"\r" {
+                            chars.next();
+
+                            if matches!(chars.peek(), Some(LiteralChar::Ascii(a)) if a.as_bytes() == b"\n") {
+                                chars.next();
+                            }
+                        } else if next.as_bytes() == b"\n" {
+                            chars.next();
+                        }
+                    }
+                }
+                LiteralChar::EscapeSequence(escape_sequence) => value.extend(escape_sequence.value()),
+            }
+        }
+
+        value
+    }
+
     /// Returns the byte representation of the Literal String.
     pub fn as_bytes(&self) -> &[u8] {
 
         &self.bytes
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LiteralString;
+    use crate::object::Object;
+    use crate::value::{Ascii, EscapeSequence, LiteralChar};
+
+    #[test]
+    fn should_returns_valid_bytes_and_value() {
+
+        let string = LiteralString::new(vec![
+            LiteralChar::Ascii(Ascii::new(b'A')),
+            LiteralChar::EscapeSequence(EscapeSequence::Backspace),
+            LiteralChar::EscapeSequence(EscapeSequence::LeftParenthesis),
+            LiteralChar::Ascii(Ascii::new(b'B')),
+        ]);
+
+        assert_eq!(string.as_bytes(), b"(A\\b\\(B)");
+        assert_eq!(string.value(), b"A\x08(B");
+        assert_eq!(Object::parse(string.as_bytes()).unwrap(), Object::LiteralString(string));
+    }
 }
\ No newline at end of file
@@ -1,4 +1,9 @@
 //! This module contains PDF object representations.
+use std::sync::Arc;
+
+use crate::byte_source::ByteSource;
+use crate::parser::ObjectParser;
+
 mod array;
 mod boolean;
 mod dicionary;
@@ -8,16 +13,20 @@ mod literal_string;
 mod name;
 mod null;
 mod real;
+mod reference;
+mod stream;
 
 pub use array::Array;
 pub use boolean::Boolean;
-pub use dicionary::Dicionary;
+pub use dicionary::{Dicionary, DicionaryEntry};
 pub use hexadecimal_string::HexadecimalString;
 pub use integer::Integer;
 pub use literal_string::LiteralString;
 pub use name::Name;
 pub use null::Null;
 pub use real::Real;
+pub use reference::{ObjectId, Reference};
+pub use stream::Stream;
 
 /// PDF Object representation.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -40,10 +49,106 @@ pub enum Object {
     Null(Null),
     /// PDF `Real` object.
     Real(Real),
+    /// PDF indirect `Reference` to another object.
+    Reference(Reference),
+    /// PDF `Stream` object.
+    Stream(Stream),
 }
 
 impl Object {
+
+    /// Parses an `Object` from the given bytes.
+    /// 
+    /// ```rs
+    /// let object = Object::parse(b"[1 (two) /Three]")?;
+    /// ```
+    pub fn parse(bytes: &[u8]) -> Result<Self, String> {
+
+        ObjectParser::new(bytes).parse_object()
+    }
+
+    /// Parses an `Object` at the given offset of the `ByteSource`.
+    /// 
+    /// Returns the object and the offset just past its end, so that
+    /// consecutive objects can be read without copying the source.
+    /// Stream payloads are sliced from the source on access.
+    pub fn parse_source(source: &Arc<dyn ByteSource>, offset: usize) -> Result<(Self, usize), String> {
+
+        let mut parser = ObjectParser::from_shared_source(source);
+        parser.seek(offset);
+
+        let object = parser.parse_object()?;
+
+        Ok((object, parser.position()))
+    }
     
+    /// Returns the value of an `Integer` object.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Object::Integer(integer) => Some(integer.as_i64()),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of an `Integer` or `Real` object.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Object::Integer(integer) => Some(integer.as_i64() as f64),
+            Object::Real(real) => Some(real.as_f64()),
+            _ => None,
+        }
+    }
+
+    /// Returns the `Name` of a Name object.
+    pub fn as_name(&self) -> Option<&Name> {
+        match self {
+            Object::Name(name) => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Returns the `Array` of an Array object.
+    pub fn as_array(&self) -> Option<&Array> {
+        match self {
+            Object::Array(array) => Some(array),
+            _ => None,
+        }
+    }
+
+    /// Returns the `Dicionary` of a Dictionary object, or the dictionary of a Stream.
+    pub fn as_dicionary(&self) -> Option<&Dicionary> {
+        match self {
+            Object::Dicionary(dicionary) => Some(dicionary),
+            Object::Stream(stream) => Some(stream.dicionary()),
+            _ => None,
+        }
+    }
+
+    /// Returns the `Stream` of a Stream object.
+    pub fn as_stream(&self) -> Option<&Stream> {
+        match self {
+            Object::Stream(stream) => Some(stream),
+            _ => None,
+        }
+    }
+
+    /// Returns the identifier of the object referenced by a Reference object.
+    pub fn as_reference(&self) -> Option<ObjectId> {
+        match self {
+            Object::Reference(reference) => Some(reference.id()),
+            _ => None,
+        }
+    }
+
+    /// Returns the decoded bytes of a string object.
+    pub fn as_string_bytes(&self) -> Option<Vec<u8>> {
+        match self {
+            Object::LiteralString(string) => Some(string.value()),
+            Object::HexadecimalString(string) => Some(string.value()),
+            _ => None,
+        }
+    }
+
     /// Returns the byte representation of the Object.
     pub fn as_bytes(&self) -> &[u8] {
         match self {
@@ -56,6 +161,8 @@ impl Object {
             Object::Real(obj) => obj.as_bytes(),
             Object::LiteralString(obj) => obj.as_bytes(),
             Object::HexadecimalString(obj) => obj.as_bytes(),
+            Object::Reference(obj) => obj.as_bytes(),
+            Object::Stream(obj) => obj.as_bytes(),
         }
     }
 }
\ No newline at end of file
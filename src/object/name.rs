@@ -1,4 +1,6 @@
-use crate::specification::object::name::validate_name_bytes;
+use std::borrow::Borrow;
+
+use crate::specification::object::name::{decode_name_escapes, encode_name_escapes, validate_name_bytes};
 
 /// PDF Name object representation.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -19,6 +21,25 @@ impl Name {
         Ok(Self { bytes: bytes.to_vec() })
     }
 
+    /// Creates a new `Name` from the given decoded value.
+    /// 
+    /// The value must not contain the leading solidus. Characters
+    /// that cannot appear in a Name are written as `#xx` escapes.
+    pub fn from_value(value: &[u8]) -> Self {
+
+        let mut bytes = vec![b'/'];
+        bytes.extend_from_slice(&encode_name_escapes(value));
+
+        Self { bytes }
+    }
+
+    /// Returns the decoded value of the Name without the leading solidus.
+    pub fn value(&self) -> Vec<u8> {
+
+        // Names are validated on creation, so only malformed escapes can fail.
+        decode_name_escapes(&self.bytes[1..]).unwrap_or_else(|_| self.bytes[1..].to_vec())
+    }
+
     /// Returns the byte representation of the Name.
     pub fn as_bytes(&self) -> &[u8] {
 
@@ -26,14 +47,23 @@ impl Name {
     }
 }
 
+impl Borrow<[u8]> for Name {
+
+    fn borrow(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::Name;
+    use crate::object::Object;
 
     #[test]
     fn should_create_valid_name() {
         let name = Name::new(b"/ExampleName").unwrap();
         assert_eq!(name.as_bytes(), b"/ExampleName");
+        assert_eq!(Object::parse(name.as_bytes()).unwrap(), Object::Name(name));
     }
 
     #[test]
@@ -41,4 +71,11 @@ mod tests {
         let result = Name::new(b"/Invalid Name ");
         assert!(result.is_err());
     }
+
+    #[test]
+    fn should_create_name_from_value() {
+        let name = Name::from_value(b"Lime Green");
+        assert_eq!(name.as_bytes(), b"/Lime#20Green");
+        assert_eq!(name.value(), b"Lime Green");
+    }
 }
\ No newline at end of file
@@ -18,6 +18,13 @@ impl Null {
     }
 }
 
+impl Default for Null {
+
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl PartialEq for Null {
 
     /// Null objects are always considered unequal.
@@ -18,9 +18,63 @@ impl Real {
         Ok(Self { bytes: bytes.to_vec() })
     }
 
+    /// Creates a new `Real` from the given Rust float.
+    /// 
+    /// The value is written with at most five decimal places,
+    /// because PDF does not allow exponential notation.
+    pub fn from_value(value: f64) -> Self {
+
+        let value = if value.is_finite() { value } else { 0.0 };
+
+        let mut text = format!("{:.5}", value);
+
+        while text.ends_with('0') {
+            text.pop();
+        }
+
+        if text.ends_with('.') {
+            text.pop();
+        }
+
+        if text == "-0" {
+            text = "0".to_string();
+        }
+
+        Self { bytes: text.into_bytes() }
+    }
+
+    /// Returns the Rust float.
+    pub fn as_f64(&self) -> f64 {
+
+        // Safe because the bytes are validated as ASCII on creation.
+        let text = std::str::from_utf8(&self.bytes).unwrap();
+
+        // Rust does not accept a sign directly followed by a dot (i.e `-.5`).
+        match text.strip_prefix('-') {
+            Some(rest) => -format!("0{}", rest.trim_start_matches('+')).parse::<f64>().unwrap_or(0.0),
+            None => format!("0{}", text.trim_start_matches('+')).parse().unwrap_or(0.0),
+        }
+    }
+
     /// Returns the byte representation of the Real.
     pub fn as_bytes(&self) -> &[u8] {
 
         &self.bytes
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Real;
+
+    #[test]
+    fn should_convert_real_values() {
+        assert_eq!(Real::new(b"2.5").unwrap().as_f64(), 2.5);
+        assert_eq!(Real::new(b"-.5").unwrap().as_f64(), -0.5);
+        assert_eq!(Real::new(b"+4.").unwrap().as_f64(), 4.0);
+        assert_eq!(Real::from_value(0.25).as_bytes(), b"0.25");
+        assert_eq!(Real::from_value(-2.0).as_bytes(), b"-2");
+        assert_eq!(Real::from_value(1.0 / 3.0).as_bytes(), b"0.33333");
+        assert_eq!(Real::from_value(-0.000001).as_bytes(), b"0");
+    }
 }
\ No newline at end of file
@@ -0,0 +1,72 @@
+/// PDF object identifier: an object number plus a generation number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ObjectId {
+    /// The object number.
+    number: u32,
+    /// The generation number.
+    generation: u16,
+}
+
+impl ObjectId {
+
+    /// Creates a new `ObjectId` from the given object and generation numbers.
+    pub fn new(number: u32, generation: u16) -> Self {
+        Self { number, generation }
+    }
+
+    /// Returns the object number.
+    pub fn number(&self) -> u32 {
+        self.number
+    }
+
+    /// Returns the generation number.
+    pub fn generation(&self) -> u16 {
+        self.generation
+    }
+}
+
+/// PDF indirect reference representation (i.e `2 0 R`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reference {
+    /// The referenced object.
+    id: ObjectId,
+    /// Byte representation of the reference.
+    bytes: Vec<u8>,
+}
+
+impl Reference {
+
+    /// Creates a new `Reference` to the given object.
+    pub fn new(id: ObjectId) -> Self {
+        Self {
+            id,
+            bytes: format!("{} {} R", id.number(), id.generation()).into_bytes(),
+        }
+    }
+
+    /// Returns the identifier of the referenced object.
+    pub fn id(&self) -> ObjectId {
+        self.id
+    }
+
+    /// Returns the byte representation of the Reference.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ObjectId, Reference};
+    use crate::object::Object;
+
+    #[test]
+    fn should_create_reference_and_return_bytes() {
+        let reference = Reference::new(ObjectId::new(12, 3));
+
+        assert_eq!(reference.id().number(), 12);
+        assert_eq!(reference.id().generation(), 3);
+        assert_eq!(reference.as_bytes(), b"12 3 R");
+        assert_eq!(Object::parse(reference.as_bytes()).unwrap(), Object::Reference(reference));
+    }
+}
\ No newline at end of file
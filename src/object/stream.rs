@@ -0,0 +1,163 @@
+use std::ops::Range;
+use std::sync::{Arc, OnceLock};
+
+use crate::byte_source::ByteSource;
+use crate::object::{Dicionary, Integer, Name, Object};
+
+/// Payload of a PDF Stream object.
+#[derive(Debug, Clone)]
+pub enum StreamData {
+    /// Payload held in memory.
+    Owned(Vec<u8>),
+    /// Payload backed by a byte range of a `ByteSource`.
+    ///
+    /// The bytes are sliced from the source on access and never copied.
+    Source {
+        /// The byte source holding the payload.
+        source: Arc<dyn ByteSource>,
+        /// Byte range of the payload in the source.
+        range: Range<usize>,
+    },
+}
+
+impl StreamData {
+
+    /// Returns the payload bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            StreamData::Owned(data) => data,
+            StreamData::Source { source, range } => source.slice(range.clone()),
+        }
+    }
+
+    /// Returns the length of the payload.
+    pub fn len(&self) -> usize {
+        match self {
+            StreamData::Owned(data) => data.len(),
+            StreamData::Source { range, .. } => range.len(),
+        }
+    }
+
+    /// Returns `true` if the payload is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// PDF Stream object representation.
+///
+/// A stream pairs a `Dicionary` with a payload. The payload is kept
+/// encoded, exactly as it appears in the file, and its `/Length`
+/// entry always holds the direct length of the payload.
+#[derive(Debug, Clone)]
+pub struct Stream {
+    /// The stream dictionary.
+    dicionary: Dicionary,
+    /// The encoded payload.
+    data: StreamData,
+    /// Byte representation, built on first use.
+    bytes: OnceLock<Vec<u8>>,
+}
+
+impl Stream {
+
+    /// Creates a new `Stream` from the given dictionary and encoded payload.
+    pub fn new(dicionary: Dicionary, data: Vec<u8>) -> Self {
+
+        Self::with_data(dicionary, StreamData::Owned(data))
+    }
+
+    /// Creates a new `Stream` whose payload is the given range of the `ByteSource`.
+    pub fn from_source(dicionary: Dicionary, source: Arc<dyn ByteSource>, range: Range<usize>) -> Self {
+
+        Self::with_data(dicionary, StreamData::Source { source, range })
+    }
+
+    /// Creates a new `Stream` from the given dictionary and `StreamData`.
+    pub fn with_data(mut dicionary: Dicionary, data: StreamData) -> Self {
+
+        dicionary.insert(
+            Name::new(b"/Length").unwrap(),
+            Object::Integer(Integer::from_value(data.len() as i64)),
+        );
+
+        Self { dicionary, data, bytes: OnceLock::new() }
+    }
+
+    /// Returns the stream dictionary.
+    pub fn dicionary(&self) -> &Dicionary {
+        &self.dicionary
+    }
+
+    /// Returns the encoded payload.
+    pub fn data(&self) -> &StreamData {
+        &self.data
+    }
+
+    /// Returns the payload decoded through the stream's filters.
+    pub fn decode(&self) -> Result<Vec<u8>, String> {
+
+        crate::filter::decode_stream(self)
+    }
+
+    /// Returns the byte representation of the Stream.
+    ///
+    /// The payload is copied into the representation on the first call.
+    pub fn as_bytes(&self) -> &[u8] {
+
+        self.bytes.get_or_init(|| {
+            let data = self.data.as_bytes();
+            let dicionary = self.dicionary.as_bytes();
+
+            let mut bytes = Vec::with_capacity(dicionary.len() + data.len() + 21);
+
+            bytes.extend_from_slice(dicionary);
+            bytes.extend_from_slice(b"\nstream\r\n");
+            bytes.extend_from_slice(data);
+            bytes.extend_from_slice(b"\r\nendstream");
+
+            bytes
+        })
+    }
+}
+
+impl PartialEq for Stream {
+
+    fn eq(&self, other: &Self) -> bool {
+        self.dicionary == other.dicionary && self.data.as_bytes() == other.data.as_bytes()
+    }
+}
+
+impl Eq for Stream {}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::{Stream, StreamData};
+    use crate::byte_source::memory::MemoryByteSource;
+    use crate::object::{Dicionary, DicionaryEntry, Name, Object};
+
+    #[test]
+    fn should_create_stream_and_return_bytes() {
+        let stream = Stream::new(Dicionary::new(vec![]), b"BT ET".to_vec());
+
+        assert_eq!(stream.as_bytes(), b"<</Length 5>>\nstream\r\nBT ET\r\nendstream");
+        assert_eq!(Object::parse(stream.as_bytes()).unwrap(), Object::Stream(stream));
+    }
+
+    #[test]
+    fn should_slice_payload_from_source() {
+        let source = Arc::new(MemoryByteSource::new(b"....payload....".to_vec()));
+        let dicionary = Dicionary::new(vec![DicionaryEntry {
+            key: Name::new(b"/Length").unwrap(),
+            value: Object::parse(b"9 0 R").unwrap(),
+        }]);
+
+        let stream = Stream::from_source(dicionary, source, 4..11);
+
+        assert!(matches!(stream.data(), StreamData::Source { .. }));
+        assert_eq!(stream.data().as_bytes(), b"payload");
+        assert_eq!(stream.dicionary().as_bytes(), b"<</Length 7>>");
+    }
+}
\ No newline at end of file
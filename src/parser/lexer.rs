@@ -0,0 +1,339 @@
+use crate::byte_source::ByteSource;
+use crate::specification::object::integer::validate_integer_bytes;
+use crate::specification::object::real::validate_real_number_bytes;
+use crate::token::{Delimiter, Keyword, Token, TokenKind, Whitespace};
+
+/// Returns `true` if the byte is a PDF whitespace character.
+pub(crate) fn is_whitespace(byte: u8) -> bool {
+    Whitespace::from_byte(byte).is_some()
+}
+
+/// Returns `true` if the byte is a PDF delimiter character.
+pub(crate) fn is_delimiter(byte: u8) -> bool {
+    Delimiter::from_byte(byte).is_some()
+}
+
+/// Returns `true` if the byte is a PDF regular character.
+pub(crate) fn is_regular(byte: u8) -> bool {
+    !is_whitespace(byte) && !is_delimiter(byte)
+}
+
+/// A zero-copy PDF lexer.
+///
+/// The lexer walks over a borrowed byte slice and yields `Token`s
+/// that borrow from it, so a `FileByteSource` is never copied.
+///
+/// ```rs
+/// let mut lexer = Lexer::from_source(&source);
+/// while let Some(token) = lexer.next_token() {
+///     println!("{:?}", token?);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Lexer<'a> {
+    /// The bytes being lexed.
+    bytes: &'a [u8],
+    /// Current byte offset.
+    position: usize,
+}
+
+impl<'a> Lexer<'a> {
+
+    /// Creates a new `Lexer` over the given bytes.
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, position: 0 }
+    }
+
+    /// Creates a new `Lexer` over the whole content of the given `ByteSource`.
+    pub fn from_source<S: ByteSource + ?Sized>(source: &'a S) -> Self {
+
+        Self::new(source.slice(0..source.len()))
+    }
+
+    /// Returns the bytes being lexed.
+    pub fn bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Returns the current byte offset.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Moves the lexer to the given byte offset.
+    pub fn seek(&mut self, position: usize) {
+        self.position = position.min(self.bytes.len());
+    }
+
+    /// Skips whitespace characters at the current position.
+    pub fn skip_whitespace(&mut self) {
+
+        while self.position < self.bytes.len() && is_whitespace(self.bytes[self.position]) {
+            self.position += 1;
+        }
+    }
+
+    /// Returns the next token, or `None` at the end of the input.
+    pub fn next_token(&mut self) -> Option<Result<Token<'a>, String>> {
+
+        self.skip_whitespace();
+
+        let start = self.position;
+        let byte = *self.bytes.get(start)?;
+
+        let result = match byte {
+            b'%' => Ok(self.lex_comment()),
+            b'/' => Ok(self.lex_name()),
+            b'(' => self.lex_literal_string(),
+            b'<' => {
+                if self.bytes.get(start + 1) == Some(&b'<') {
+                    self.position += 2;
+                    Ok(TokenKind::DictionaryStart)
+                } else {
+                    self.lex_hexadecimal_string()
+                }
+            }
+            b'>' => {
+                if self.bytes.get(start + 1) == Some(&b'>') {
+                    self.position += 2;
+                    Ok(TokenKind::DictionaryEnd)
+                } else {
+                    self.position += 1;
+                    Err(format!("Unexpected `>` at offset {}", start))
+                }
+            }
+            b'[' | b']' | b'{' | b'}' => {
+                self.position += 1;
+                // Safe because the byte was matched against delimiters above.
+                Ok(TokenKind::Delimiter(Delimiter::from_byte(byte).unwrap()))
+            }
+            b')' => {
+                self.position += 1;
+                Err(format!("Unexpected `)` at offset {}", start))
+            }
+            _ => Ok(self.lex_regular()),
+        };
+
+        Some(result.map(|kind| Token::new(kind, start, &self.bytes[start..self.position])))
+    }
+
+    /// Lexes a comment. The current byte must be `%`.
+    fn lex_comment(&mut self) -> TokenKind<'a> {
+
+        let start = self.position + 1;
+        let mut end = start;
+
+        while end < self.bytes.len() && !matches!(self.bytes[end], b'\r' | b'\n') {
+            end += 1;
+        }
+
+        self.position = end;
+
+        TokenKind::Comment(&self.bytes[start..end])
+    }
+
+    /// Lexes a name. The current byte must be `/`.
+    fn lex_name(&mut self) -> TokenKind<'a> {
+
+        let start = self.position;
+        let mut end = start + 1;
+
+        while end < self.bytes.len() && is_regular(self.bytes[end]) {
+            end += 1;
+        }
+
+        self.position = end;
+
+        TokenKind::Name(&self.bytes[start..end])
+    }
+
+    /// Lexes a literal string. The current byte must be `(`.
+    fn lex_literal_string(&mut self) -> Result<TokenKind<'a>, String> {
+
+        let start = self.position + 1;
+        let mut end = start;
+        let mut depth = 1;
+
+        while end < self.bytes.len() {
+
+            match self.bytes[end] {
+                // An escaped byte never changes the nesting depth.
+                b'\\' => end += 1,
+                b'(' => depth += 1,
+                b')' => {
+                    depth -= 1;
+
+                    if depth == 0 {
+                        self.position = end + 1;
+                        return Ok(TokenKind::LiteralString(&self.bytes[start..end]));
+                    }
+                }
+                _ => {}
+            }
+
+            end += 1;
+        }
+
+        self.position = self.bytes.len();
+
+        Err(format!("Unterminated literal string at offset {}", start - 1))
+    }
+
+    /// Lexes a hexadecimal string. The current byte must be `<`.
+    fn lex_hexadecimal_string(&mut self) -> Result<TokenKind<'a>, String> {
+
+        let start = self.position + 1;
+        let mut end = start;
+
+        while end < self.bytes.len() {
+
+            let byte = self.bytes[end];
+
+            if byte == b'>' {
+                self.position = end + 1;
+                return Ok(TokenKind::HexadecimalString(&self.bytes[start..end]));
+            }
+
+            if !byte.is_ascii_hexdigit() && !is_whitespace(byte) {
+                self.position = end;
+                return Err(format!("Invalid byte {:#04X} in hexadecimal string at offset {}", byte, end));
+            }
+
+            end += 1;
+        }
+
+        self.position = self.bytes.len();
+
+        Err(format!("Unterminated hexadecimal string at offset {}", start - 1))
+    }
+
+    /// Lexes a number or a keyword.
+    fn lex_regular(&mut self) -> TokenKind<'a> {
+
+        let start = self.position;
+        let mut end = start;
+
+        while end < self.bytes.len() && is_regular(self.bytes[end]) {
+            end += 1;
+        }
+
+        self.position = end;
+
+        let bytes = &self.bytes[start..end];
+
+        if validate_integer_bytes(bytes).is_ok() {
+            return TokenKind::Integer(bytes);
+        }
+
+        if validate_real_number_bytes(bytes).is_ok() {
+            return TokenKind::Real(bytes);
+        }
+
+        TokenKind::Keyword(Keyword::new(bytes))
+    }
+}
+
+impl<'a> Iterator for Lexer<'a> {
+    type Item = Result<Token<'a>, String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_token()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Lexer;
+    use crate::byte_source::memory::MemoryByteSource;
+    use crate::token::{Delimiter, Keyword, TokenKind};
+
+    fn kinds(bytes: &[u8]) -> Vec<TokenKind<'_>> {
+        Lexer::new(bytes).map(|t| *t.unwrap().kind()).collect()
+    }
+
+    #[test]
+    fn should_lex_numbers() {
+        assert_eq!(
+            kinds(b"42 -7 +3 3.14 -.5 4."),
+            vec![
+                TokenKind::Integer(b"42"),
+                TokenKind::Integer(b"-7"),
+                TokenKind::Integer(b"+3"),
+                TokenKind::Real(b"3.14"),
+                TokenKind::Real(b"-.5"),
+                TokenKind::Real(b"4."),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_lex_names_and_delimiters() {
+        assert_eq!(
+            kinds(b"<</Type/Page/Kids[4 0 R]/A#20B>>"),
+            vec![
+                TokenKind::DictionaryStart,
+                TokenKind::Name(b"/Type"),
+                TokenKind::Name(b"/Page"),
+                TokenKind::Name(b"/Kids"),
+                TokenKind::Delimiter(Delimiter::LeftSquare),
+                TokenKind::Integer(b"4"),
+                TokenKind::Integer(b"0"),
+                TokenKind::Keyword(Keyword::R),
+                TokenKind::Delimiter(Delimiter::RightSquare),
+                TokenKind::Name(b"/A#20B"),
+                TokenKind::DictionaryEnd,
+            ]
+        );
+    }
+
+    #[test]
+    fn should_lex_strings() {
+        assert_eq!(
+            kinds(b"(a (nested) \\) string) <4A 6f\n68>"),
+            vec![
+                TokenKind::LiteralString(b"a (nested) \\) string"),
+                TokenKind::HexadecimalString(b"4A 6f\n68"),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_lex_keywords_and_comments() {
+        assert_eq!(
+            kinds(b"1 0 obj % a comment\r\nnull endobj BT Tj"),
+            vec![
+                TokenKind::Integer(b"1"),
+                TokenKind::Integer(b"0"),
+                TokenKind::Keyword(Keyword::Obj),
+                TokenKind::Comment(b" a comment"),
+                TokenKind::Keyword(Keyword::Null),
+                TokenKind::Keyword(Keyword::EndObj),
+                TokenKind::Keyword(Keyword::Other(b"BT")),
+                TokenKind::Keyword(Keyword::Other(b"Tj")),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_keep_offsets_and_borrow_from_source() {
+        let source = MemoryByteSource::new(b"  /Name (str)".to_vec());
+        let tokens: Vec<_> = Lexer::from_source(&source).map(|t| t.unwrap()).collect();
+
+        assert_eq!(tokens[0].offset(), 2);
+        assert_eq!(tokens[0].as_bytes(), b"/Name");
+        assert_eq!(tokens[1].offset(), 8);
+        assert_eq!(tokens[1].end(), 13);
+        assert_eq!(tokens[1].as_bytes(), b"(str)");
+    }
+
+    #[test]
+    fn should_error_on_malformed_input() {
+        assert!(Lexer::new(b"(unterminated").next_token().unwrap().is_err());
+        assert!(Lexer::new(b"<4G>").next_token().unwrap().is_err());
+        assert!(Lexer::new(b")").next_token().unwrap().is_err());
+
+        let mut lexer = Lexer::new(b"> 1");
+        assert!(lexer.next_token().unwrap().is_err());
+        assert_eq!(*lexer.next_token().unwrap().unwrap().kind(), TokenKind::Integer(b"1"));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,8 @@
+//! This module contains the PDF parser.
+mod lexer;
+mod object;
+pub(crate) mod search;
+
+pub use crate::parser::lexer::Lexer;
+pub(crate) use crate::parser::lexer::{is_regular, is_whitespace};
+pub use crate::parser::object::ObjectParser;
@@ -0,0 +1,634 @@
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use crate::byte_source::ByteSource;
+use crate::object::{Array, Boolean, Dicionary, HexadecimalString, Integer, LiteralString, Name, Null, Object, Real};
+use crate::object::{DicionaryEntry, ObjectId, Reference, Stream};
+use crate::parser::Lexer;
+use crate::parser::lexer::is_whitespace;
+use crate::parser::search::find;
+use crate::specification::object::name::{decode_name_escapes, encode_name_escapes};
+use crate::specification::structure::indirect_object::{validate_generation_number, validate_object_number};
+use crate::structure::IndirectObject;
+use crate::specification::value::literal_char::escape_sequence::validate_escape_sequence_bytes;
+use crate::token::{Delimiter, Keyword, Token, TokenKind};
+use crate::value::{Ascii, CharacterCode, EscapeSequence, HexadecimalChar, LiteralChar};
+
+/// Maximum nesting depth of arrays and dictionaries.
+const MAX_DEPTH: usize = 256;
+
+/// Resolves the value of an indirect `/Length` entry of a stream.
+pub type LengthResolver<'a> = &'a dyn Fn(ObjectId) -> Option<usize>;
+
+/// A recursive PDF object parser.
+///
+/// ```rs
+/// let mut parser = ObjectParser::new(b"<</Type /Page /Rotate 90>>");
+/// let object = parser.parse_object()?;
+/// ```
+#[derive(Clone)]
+pub struct ObjectParser<'a> {
+    /// The lexer providing tokens.
+    lexer: Lexer<'a>,
+    /// The shared source, used to slice stream payloads without copying.
+    source: Option<Arc<dyn ByteSource>>,
+    /// Resolver for indirect stream lengths.
+    length_resolver: Option<LengthResolver<'a>>,
+}
+
+impl<'a> ObjectParser<'a> {
+
+    /// Creates a new `ObjectParser` over the given bytes.
+    /// 
+    /// Stream payloads are copied out of the bytes.
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { lexer: Lexer::new(bytes), source: None, length_resolver: None }
+    }
+
+    /// Creates a new `ObjectParser` over a shared `ByteSource`.
+    /// 
+    /// Stream payloads keep a reference to the source and are never copied.
+    pub fn from_shared_source(source: &'a Arc<dyn ByteSource>) -> Self {
+        Self {
+            lexer: Lexer::from_source(source.as_ref()),
+            source: Some(Arc::clone(source)),
+            length_resolver: None,
+        }
+    }
+
+    /// Sets the resolver used for stream `/Length` entries that are indirect references.
+    pub fn with_length_resolver(mut self, resolver: LengthResolver<'a>) -> Self {
+        self.length_resolver = Some(resolver);
+        self
+    }
+
+    /// Returns the current byte offset.
+    pub fn position(&self) -> usize {
+        self.lexer.position()
+    }
+
+    /// Moves the parser to the given byte offset.
+    pub fn seek(&mut self, position: usize) {
+        self.lexer.seek(position);
+    }
+
+    /// Returns the underlying lexer.
+    pub fn lexer(&mut self) -> &mut Lexer<'a> {
+        &mut self.lexer
+    }
+
+    /// Parses the next object.
+    pub fn parse_object(&mut self) -> Result<Object, String> {
+
+        let token = self.next_token()?
+            .ok_or_else(|| format!("Unexpected end of input at offset {}", self.lexer.position()))?;
+
+        let object = self.parse_object_from(token, 0)?;
+
+        // Only a top-level dictionary can be followed by stream data.
+        let Object::Dicionary(dicionary) = object else {
+            return Ok(object);
+        };
+
+        match self.clone().next_token() {
+            Ok(Some(token)) if token.is_keyword(Keyword::Stream) => {
+                self.lexer.seek(token.end());
+                self.parse_stream(dicionary)
+            }
+            _ => Ok(Object::Dicionary(dicionary)),
+        }
+    }
+
+    /// Parses stream data following the `stream` keyword.
+    /// 
+    /// The declared `/Length` is honoured when it is consistent with the
+    /// position of `endstream`; otherwise the payload ends at `endstream`.
+    fn parse_stream(&mut self, dicionary: Dicionary) -> Result<Object, String> {
+
+        let bytes = self.lexer.bytes();
+        let mut start = self.lexer.position();
+
+        // The `stream` keyword is followed by CRLF or LF (a lone CR is tolerated).
+        match bytes.get(start..) {
+            Some([b'\r', b'\n', ..]) => start += 2,
+            Some([b'\n' | b'\r', ..]) => start += 1,
+            _ => {}
+        }
+
+        let declared = match dicionary.get(b"/Length") {
+            Some(Object::Integer(length)) => usize::try_from(length.as_i64()).ok(),
+            Some(Object::Reference(reference)) => self.length_resolver.and_then(|resolve| resolve(reference.id())),
+            _ => None,
+        };
+
+        let end = match declared.filter(|&length| is_stream_end(bytes, start, length)) {
+            Some(length) => start + length,
+            None => {
+                let keyword = find(&bytes[start..], b"endstream")
+                    .ok_or_else(|| format!("Unterminated stream at offset {}", start))?;
+
+                let mut end = start + keyword;
+
+                // The end-of-line marker before `endstream` is not part of the data.
+                if end > start && bytes[end - 1] == b'\n' {
+                    end -= 1;
+                }
+
+                if end > start && bytes[end - 1] == b'\r' {
+                    end -= 1;
+                }
+
+                end
+            }
+        };
+
+        self.lexer.seek(end);
+
+        match self.next_token()? {
+            Some(token) if token.is_keyword(Keyword::EndStream) => {}
+            _ => return Err(format!("Expected `endstream` at offset {}", end)),
+        }
+
+        let stream = match &self.source {
+            Some(source) => Stream::from_source(dicionary, Arc::clone(source), start..end),
+            None => Stream::new(dicionary, bytes[start..end].to_vec()),
+        };
+
+        Ok(Object::Stream(stream))
+    }
+
+    /// Parses the next indirect object (`N G obj ... endobj`).
+    /// 
+    /// A missing `endobj` keyword is tolerated, because
+    /// many writers in the wild omit it.
+    pub fn parse_indirect_object(&mut self) -> Result<IndirectObject, String> {
+
+        let id = self.parse_object_header()?;
+        let object = self.parse_object()?;
+
+        if let Ok(Some(token)) = self.clone().next_token()
+            && token.is_keyword(Keyword::EndObj)
+        {
+            self.lexer.seek(token.end());
+        }
+
+        Ok(IndirectObject::new(id, object))
+    }
+
+    /// Parses an indirect object header (`N G obj`) and returns its identifier.
+    pub fn parse_object_header(&mut self) -> Result<ObjectId, String> {
+
+        let offset = self.lexer.position();
+
+        let number = self.parse_integer_token()?;
+        let generation = self.parse_integer_token()?;
+
+        match self.next_token()? {
+            Some(token) if token.is_keyword(Keyword::Obj) => {}
+            _ => return Err(format!("Expected `obj` keyword after object header at offset {}", offset)),
+        }
+
+        validate_object_number(number)?;
+        validate_generation_number(generation)?;
+
+        Ok(ObjectId::new(number as u32, generation as u16))
+    }
+
+    /// Parses the next token as an integer value.
+    fn parse_integer_token(&mut self) -> Result<i64, String> {
+
+        match self.next_token()? {
+            Some(token) => match token.kind() {
+                TokenKind::Integer(bytes) => Ok(Integer::new(bytes)?.as_i64()),
+                _ => Err(format!("Expected an integer at offset {}", token.offset())),
+            },
+            None => Err("Unexpected end of input while expecting an integer".to_string()),
+        }
+    }
+
+    /// Tries to parse an indirect reference whose object number has been read.
+    /// 
+    /// The parser is left untouched unless the generation number and
+    /// the `R` keyword follow.
+    fn try_parse_reference(&mut self, number: &Integer) -> Result<Option<Object>, String> {
+
+        let mut lookahead = self.clone();
+
+        let generation = match lookahead.next_token() {
+            Ok(Some(token)) => match token.kind() {
+                TokenKind::Integer(bytes) => Integer::new(bytes)?,
+                _ => return Ok(None),
+            },
+            _ => return Ok(None),
+        };
+
+        match lookahead.next_token() {
+            Ok(Some(token)) if token.is_keyword(Keyword::R) => {}
+            _ => return Ok(None),
+        }
+
+        *self = lookahead;
+
+        let number = number.as_i64();
+        let generation = generation.as_i64();
+
+        // A reference to an object that cannot exist is treated as `null`.
+        if validate_object_number(number).is_err() || validate_generation_number(generation).is_err() {
+            return Ok(Some(Object::Null(Null::new())));
+        }
+
+        let id = ObjectId::new(number as u32, generation as u16);
+
+        Ok(Some(Object::Reference(Reference::new(id))))
+    }
+
+    /// Returns the next token that is not a comment.
+    pub(crate) fn next_token(&mut self) -> Result<Option<Token<'a>>, String> {
+
+        loop {
+            match self.lexer.next_token() {
+                Some(Ok(token)) if matches!(token.kind(), TokenKind::Comment(_)) => continue,
+                Some(Ok(token)) => return Ok(Some(token)),
+                Some(Err(e)) => return Err(e),
+                None => return Ok(None),
+            }
+        }
+    }
+
+    /// Parses an object starting with the given token.
+    fn parse_object_from(&mut self, token: Token<'a>, depth: usize) -> Result<Object, String> {
+
+        if depth > MAX_DEPTH {
+            return Err(format!("Objects are nested too deeply at offset {}", token.offset()));
+        }
+
+        match *token.kind() {
+            TokenKind::Integer(bytes) => {
+                let integer = Integer::new(bytes)?;
+
+                match self.try_parse_reference(&integer)? {
+                    Some(reference) => Ok(reference),
+                    None => Ok(Object::Integer(integer)),
+                }
+            }
+            TokenKind::Real(bytes) => Ok(Object::Real(Real::new(bytes)?)),
+            TokenKind::Name(bytes) => Ok(Object::Name(parse_name(bytes)?)),
+            TokenKind::LiteralString(bytes) => Ok(Object::LiteralString(parse_literal_string(bytes)?)),
+            TokenKind::HexadecimalString(bytes) => Ok(Object::HexadecimalString(parse_hexadecimal_string(bytes))),
+            TokenKind::Keyword(Keyword::True) => Ok(Object::Boolean(Boolean::new(true))),
+            TokenKind::Keyword(Keyword::False) => Ok(Object::Boolean(Boolean::new(false))),
+            TokenKind::Keyword(Keyword::Null) => Ok(Object::Null(Null::new())),
+            TokenKind::Delimiter(Delimiter::LeftSquare) => self.parse_array(depth),
+            TokenKind::DictionaryStart => self.parse_dicionary(depth),
+            _ => Err(format!(
+                "Unexpected token {:?} at offset {}",
+                String::from_utf8_lossy(token.as_bytes()),
+                token.offset()
+            )),
+        }
+    }
+
+    /// Parses the rest of an array after `[`.
+    fn parse_array(&mut self, depth: usize) -> Result<Object, String> {
+
+        let mut objects = Vec::new();
+
+        loop {
+            let token = self.next_token()?
+                .ok_or_else(|| "Unterminated array".to_string())?;
+
+            if matches!(token.kind(), TokenKind::Delimiter(Delimiter::RightSquare)) {
+                return Ok(Object::Array(Array::new(objects)));
+            }
+
+            objects.push(self.parse_object_from(token, depth + 1)?);
+        }
+    }
+
+    /// Parses the rest of a dictionary after `<<`.
+    fn parse_dicionary(&mut self, depth: usize) -> Result<Object, String> {
+
+        let mut entries = Vec::new();
+
+        loop {
+            let token = self.next_token()?
+                .ok_or_else(|| "Unterminated dictionary".to_string())?;
+
+            let key = match *token.kind() {
+                TokenKind::DictionaryEnd => return Ok(Object::Dicionary(Dicionary::new(entries))),
+                TokenKind::Name(bytes) => parse_name(bytes)?,
+                _ => return Err(format!("Dictionary key must be a name at offset {}", token.offset())),
+            };
+
+            let token = self.next_token()?
+                .ok_or_else(|| "Unterminated dictionary".to_string())?;
+
+            let value = self.parse_object_from(token, depth + 1)?;
+
+            entries.push(DicionaryEntry { key, value });
+        }
+    }
+}
+
+impl Debug for ObjectParser<'_> {
+
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ObjectParser")
+            .field("lexer", &self.lexer)
+            .field("source", &self.source)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Returns `true` if stream data of the given length starting at `start`
+/// is followed by optional whitespace and the `endstream` keyword.
+fn is_stream_end(bytes: &[u8], start: usize, length: usize) -> bool {
+
+    let Some(mut end) = start.checked_add(length).filter(|&end| end <= bytes.len()) else {
+        return false;
+    };
+
+    while end < bytes.len() && is_whitespace(bytes[end]) {
+        end += 1;
+    }
+
+    bytes[end..].starts_with(b"endstream")
+}
+
+/// Parses a raw name token into a normalized `Name`.
+///
+/// `#xx` escapes are decoded and only the characters that
+/// require it are escaped again, so `/A#42` becomes `/AB`.
+fn parse_name(bytes: &[u8]) -> Result<Name, String> {
+
+    let value = decode_name_escapes(&bytes[1..])?;
+
+    let mut normalized = vec![b'/'];
+    normalized.extend_from_slice(&encode_name_escapes(&value));
+
+    Name::new(&normalized)
+}
+
+/// Parses the raw contents of a literal string token.
+fn parse_literal_string(bytes: &[u8]) -> Result<LiteralString, String> {
+
+    let mut chars = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+
+        let byte = bytes[index];
+
+        if byte == b'\\' {
+            index += 1;
+
+            let (escape_sequence, length) = parse_escape_sequence(&bytes[index..])?;
+            let is_empty = escape_sequence == EscapeSequence::Empty;
+
+            chars.push(LiteralChar::EscapeSequence(escape_sequence));
+            index += length;
+
+            // A backslash at the end of a line continues the string on the
+            // next line, so the end-of-line marker is kept verbatim.
+            if is_empty {
+                let length = match bytes.get(index..) {
+                    Some([b'\r', b'\n', ..]) => 2,
+                    Some([b'\r' | b'\n', ..]) => 1,
+                    _ => 0,
+                };
+
+                for &byte in &bytes[index..index + length] {
+                    chars.push(LiteralChar::Ascii(Ascii::new(byte)));
+                }
+
+                index += length;
+            }
+
+            continue;
+        }
+
+        // An unescaped end-of-line marker is treated as a single line feed.
+        if byte == b'\r' {
+            chars.push(LiteralChar::EscapeSequence(EscapeSequence::EndOfLine));
+            index += if bytes.get(index + 1) == Some(&b'\n') { 2 } else { 1 };
+            continue;
+        }
+
+        if byte.is_ascii() {
+            chars.push(LiteralChar::Ascii(Ascii::new(byte)));
+        } else {
+            chars.push(LiteralChar::EscapeSequence(EscapeSequence::CharacterCode(
+                CharacterCode::new(format!("\\{:03o}", byte).into_bytes())
+            )));
+        }
+
+        index += 1;
+    }
+
+    Ok(LiteralString::new(chars))
+}
+
+/// Parses an escape sequence from the bytes following a backslash.
+///
+/// Returns the escape sequence and the number of bytes consumed.
+fn parse_escape_sequence(bytes: &[u8]) -> Result<(EscapeSequence, usize), String> {
+
+    let Some(&byte) = bytes.first() else {
+        return Ok((EscapeSequence::Empty, 0));
+    };
+
+    let escape_sequence = match byte {
+        b'n' => EscapeSequence::LineFeed,
+        b'r' => EscapeSequence::CarriageReturn,
+        b't' => EscapeSequence::Tab,
+        b'b' => EscapeSequence::Backspace,
+        b'f' => EscapeSequence::FormFeed,
+        b'(' => EscapeSequence::LeftParenthesis,
+        b')' => EscapeSequence::RightParenthesis,
+        b'\\' => EscapeSequence::Backslash,
+        b'0'..=b'7' => {
+            let length = bytes.iter().take(3).take_while(|b| matches!(b, b'0'..=b'7')).count();
+
+            let mut code = vec![b'\\'];
+            code.extend_from_slice(&bytes[..length]);
+
+            validate_escape_sequence_bytes(&code)?;
+
+            return Ok((EscapeSequence::CharacterCode(CharacterCode::new(code)), length));
+        }
+        // A backslash that does not start an escape sequence is ignored,
+        // and the following byte (including an end-of-line) is kept as is.
+        _ => return Ok((EscapeSequence::Empty, 0)),
+    };
+
+    Ok((escape_sequence, 1))
+}
+
+/// Parses the raw contents of a hexadecimal string token.
+///
+/// Whitespace is ignored, and a final odd digit is followed by `0`.
+fn parse_hexadecimal_string(bytes: &[u8]) -> HexadecimalString {
+
+    let digits: Vec<u8> = bytes.iter().copied().filter(u8::is_ascii_hexdigit).collect();
+
+    let chars = digits.chunks(2)
+        .map(|pair| match pair {
+            [high, low] => HexadecimalChar::new(&[*high, *low]),
+            _ => HexadecimalChar::new(&[pair[0], b'0']),
+        })
+        .collect();
+
+    HexadecimalString::new(chars)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::ObjectParser;
+    use crate::byte_source::ByteSource;
+    use crate::byte_source::memory::MemoryByteSource;
+    use crate::object::{Object, ObjectId};
+
+    fn parse(bytes: &[u8]) -> Object {
+        ObjectParser::new(bytes).parse_object().unwrap()
+    }
+
+    #[test]
+    fn should_parse_nested_objects() {
+        let object = parse(b"<</Type /Page /Kids [1 [2.5 (x)] <</A true>>] /N null>>");
+        assert_eq!(object.as_bytes(), b"<</Type /Page /Kids [1 [2.5 (x)] <</A true>>] /N null>>");
+    }
+
+    #[test]
+    fn should_normalize_name_escapes() {
+        assert_eq!(parse(b"/A#42C").as_bytes(), b"/ABC");
+        assert_eq!(parse(b"/Lime#20Green").as_bytes(), b"/Lime#20Green");
+        assert_eq!(parse(b"/paired#28#29parentheses").as_bytes(), b"/paired#28#29parentheses");
+    }
+
+    #[test]
+    fn should_parse_literal_strings() {
+        let Object::LiteralString(string) = parse(b"(a (b) \\(c\\) \\101\\7 \\\r\nd\re\\q)") else {
+            panic!("Expected a literal string");
+        };
+
+        assert_eq!(string.value(), b"a (b) (c) A\x07 d\neq");
+    }
+
+    #[test]
+    fn should_escape_non_ascii_bytes_in_literal_strings() {
+        let Object::LiteralString(string) = parse(b"(\xFE\xFF)") else {
+            panic!("Expected a literal string");
+        };
+
+        assert_eq!(string.value(), b"\xFE\xFF");
+        assert_eq!(string.as_bytes(), b"(\\376\\377)");
+    }
+
+    #[test]
+    fn should_parse_hexadecimal_strings_with_whitespace() {
+        let Object::HexadecimalString(string) = parse(b"<4A 6f\n68 6E 7>") else {
+            panic!("Expected a hexadecimal string");
+        };
+
+        assert_eq!(string.value(), b"John\x70");
+        assert_eq!(string.as_bytes(), b"<4A6f686E70>");
+    }
+
+    #[test]
+    fn should_skip_comments() {
+        assert_eq!(parse(b"% comment\n[1 % inner\n 2]").as_bytes(), b"[1 2]");
+    }
+
+    #[test]
+    fn should_parse_objects_from_source() {
+        let source: Arc<dyn ByteSource> = Arc::new(MemoryByteSource::new(b"[1 2] /Name".to_vec()));
+        let mut parser = ObjectParser::from_shared_source(&source);
+
+        assert_eq!(parser.parse_object().unwrap().as_bytes(), b"[1 2]");
+        assert_eq!(parser.parse_object().unwrap().as_bytes(), b"/Name");
+        assert!(parser.parse_object().is_err());
+    }
+
+    #[test]
+    fn should_parse_references() {
+        assert_eq!(parse(b"[1 0 R 2 5 R 3 4]").as_bytes(), b"[1 0 R 2 5 R 3 4]");
+        assert_eq!(parse(b"<</Pages 2 0 R /Count 1>>").as_bytes(), b"<</Pages 2 0 R /Count 1>>");
+        assert_eq!(parse(b"[0 0 R]").as_bytes(), b"[null]");
+
+        let mut parser = ObjectParser::new(b"1 2");
+        assert_eq!(parser.parse_object().unwrap().as_bytes(), b"1");
+        assert_eq!(parser.parse_object().unwrap().as_bytes(), b"2");
+    }
+
+    #[test]
+    fn should_parse_indirect_objects() {
+        let mut parser = ObjectParser::new(b"1 0 obj\n<</Type /Catalog /Pages 2 0 R>>\nendobj\n2 0 obj [3 0 R] 3 0 obj 42 endobj");
+
+        let first = parser.parse_indirect_object().unwrap();
+        assert_eq!(first.id(), ObjectId::new(1, 0));
+        assert_eq!(first.object().as_bytes(), b"<</Type /Catalog /Pages 2 0 R>>");
+
+        let second = parser.parse_indirect_object().unwrap();
+        assert_eq!(second.id(), ObjectId::new(2, 0));
+        assert_eq!(second.object().as_bytes(), b"[3 0 R]");
+
+        let third = parser.parse_indirect_object().unwrap();
+        assert_eq!(third.id(), ObjectId::new(3, 0));
+
+        assert!(ObjectParser::new(b"1 0 42 endobj").parse_indirect_object().is_err());
+        assert!(ObjectParser::new(b"0 0 obj 42 endobj").parse_indirect_object().is_err());
+    }
+
+    #[test]
+    fn should_parse_streams_with_declared_length() {
+        let mut parser = ObjectParser::new(b"1 0 obj\n<</Length 10>>\nstream\r\nendstream \r\nendstream\nendobj");
+        let object = parser.parse_indirect_object().unwrap().into_object();
+
+        let Object::Stream(stream) = object else {
+            panic!("Expected a stream");
+        };
+
+        assert_eq!(stream.data().as_bytes(), b"endstream ");
+        assert!(parser.parse_object().is_err());
+    }
+
+    #[test]
+    fn should_parse_streams_with_wrong_or_missing_length() {
+        for input in [&b"<</Length 99>>stream\nBT ET\nendstream"[..], b"<<>>stream\r\nBT ET\r\nendstream"] {
+            let Object::Stream(stream) = parse(input) else {
+                panic!("Expected a stream");
+            };
+
+            assert_eq!(stream.data().as_bytes(), b"BT ET");
+            assert_eq!(stream.dicionary().as_bytes(), b"<</Length 5>>");
+        }
+    }
+
+    #[test]
+    fn should_resolve_indirect_stream_lengths_and_borrow_from_source() {
+        let source: Arc<dyn ByteSource> = Arc::new(MemoryByteSource::new(
+            b"4 0 obj <</Length 5 0 R>> stream\nAB\nendstream\nCD\nendstream endobj".to_vec()
+        ));
+
+        let resolver = |id: ObjectId| (id == ObjectId::new(5, 0)).then_some(15);
+        let mut parser = ObjectParser::from_shared_source(&source).with_length_resolver(&resolver);
+
+        let Object::Stream(stream) = parser.parse_indirect_object().unwrap().into_object() else {
+            panic!("Expected a stream");
+        };
+
+        assert_eq!(stream.data().as_bytes(), b"AB\nendstream\nCD");
+        assert_eq!(stream.data().as_bytes().as_ptr(), source.slice(33..48).as_ptr());
+    }
+
+    #[test]
+    fn should_error_on_malformed_objects() {
+        assert!(ObjectParser::new(b"[1 2").parse_object().is_err());
+        assert!(ObjectParser::new(b"<</A>>").parse_object().is_err());
+        assert!(ObjectParser::new(b"<<1 2>>").parse_object().is_err());
+        assert!(ObjectParser::new(b"]").parse_object().is_err());
+        assert!(ObjectParser::new(&[b'['; 1000]).parse_object().is_err());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,33 @@
+/// Returns the offset of the first occurrence of `needle` in `haystack`.
+pub(crate) fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+
+    if needle.is_empty() || needle.len() > haystack.len() {
+        return None;
+    }
+
+    haystack.windows(needle.len()).position(|window| window == needle)
+}
+
+/// Returns the offset of the last occurrence of `needle` in `haystack`.
+pub(crate) fn rfind(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+
+    if needle.is_empty() || needle.len() > haystack.len() {
+        return None;
+    }
+
+    haystack.windows(needle.len()).rposition(|window| window == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find, rfind};
+
+    #[test]
+    fn should_find_first_and_last_occurrences() {
+        assert_eq!(find(b"abcabc", b"bc"), Some(1));
+        assert_eq!(rfind(b"abcabc", b"bc"), Some(4));
+        assert_eq!(find(b"abc", b"x"), None);
+        assert_eq!(rfind(b"ab", b"abc"), None);
+        assert_eq!(find(b"abc", b""), None);
+    }
+}
\ No newline at end of file
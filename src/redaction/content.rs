@@ -0,0 +1,597 @@
+use std::collections::HashSet;
+
+use crate::content::{parse_operations, Interpreter, Operation, Operator};
+use crate::document::Document;
+use crate::filter::encode_stream;
+use crate::geometry::{Matrix, Rectangle};
+use crate::image::Raster;
+use crate::object::{Dicionary, HexadecimalString, Name, Object, ObjectId, Real, Reference, Stream};
+use crate::redaction::RedactionReport;
+
+/// Line width assumed for strokes thinner than a device pixel.
+const MIN_STROKE_WIDTH: f64 = 1.0;
+
+/// An element of a text-showing operation.
+#[derive(Debug, Clone, PartialEq)]
+enum TextElement {
+    /// A string of character codes.
+    Text(Vec<u8>),
+    /// A position adjustment in thousandths of text space units.
+    Adjust(f64),
+}
+
+/// A path under construction, with the operations that build it.
+#[derive(Debug, Clone, Default)]
+struct Path {
+    /// The operations building the path, clipping operators included.
+    operations: Vec<Vec<u8>>,
+    /// Bounding box of the path in default user space.
+    bounding_box: Option<Rectangle>,
+    /// Boxes of the `re` subpaths, while the path consists only of
+    /// axis-aligned rectangles.
+    rectangles: Option<Vec<Rectangle>>,
+    /// Whether the path is used as a clipping path.
+    clip: bool,
+}
+
+/// What becomes of an XObject that paints inside the areas.
+#[derive(Debug, Clone, PartialEq)]
+enum Replacement {
+    /// The XObject is kept, as none of its content is inside the areas.
+    Keep,
+    /// The XObject is replaced by a redacted copy.
+    Copy(Stream),
+    /// The XObject is removed, as it cannot be redacted.
+    Remove,
+}
+
+/// The result of redacting a content stream.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RedactedContent {
+    /// The redacted content.
+    pub(crate) content: Vec<u8>,
+    /// Counts of the removed content.
+    pub(crate) report: RedactionReport,
+    /// Resource names of the XObjects that are no longer painted.
+    pub(crate) unused_xobjects: Vec<Vec<u8>>,
+    /// Resource names of the redacted copies of XObjects, with their identifiers.
+    pub(crate) added_xobjects: Vec<(Vec<u8>, ObjectId)>,
+    /// The objects to add to the document: the redacted copies.
+    pub(crate) objects: Vec<(ObjectId, Object)>,
+}
+
+impl RedactedContent {
+
+    /// Returns the resources of the content once redacted: the XObjects
+    /// no longer painted are removed, and the redacted copies added.
+    pub(crate) fn resources(&self, document: &Document, resources: &Dicionary) -> Result<Dicionary, String> {
+
+        let mut resources = resources.clone();
+
+        if self.unused_xobjects.is_empty() && self.added_xobjects.is_empty() {
+            return Ok(resources);
+        }
+
+        let mut xobjects = match resources.get(b"/XObject") {
+            Some(xobjects) => document.resolve(xobjects)?.as_dicionary().cloned().unwrap_or_else(|| Dicionary::new(vec![])),
+            None => Dicionary::new(vec![]),
+        };
+
+        for name in &self.unused_xobjects {
+            xobjects.remove(name);
+        }
+
+        for (name, id) in &self.added_xobjects {
+            xobjects.insert(Name::new(name).unwrap(), Object::Reference(Reference::new(*id)));
+        }
+
+        resources.insert(Name::new(b"/XObject").unwrap(), Object::Dicionary(xobjects));
+
+        Ok(resources)
+    }
+}
+
+/// Removes the content of a page that falls inside the given areas.
+///
+/// Operations are copied unchanged unless they paint inside an area.
+/// Images and forms painting inside an area are replaced by redacted
+/// copies, which are numbered from the next object number of the document.
+pub(crate) struct ContentRedactor<'a> {
+    /// The graphics and text state.
+    content: Interpreter<'a>,
+    /// The areas to redact, in default user space.
+    areas: &'a [Rectangle],
+    /// The path under construction.
+    path: Path,
+    /// The redacted content.
+    output: Vec<u8>,
+    /// Counts of the removed content.
+    report: RedactionReport,
+    /// Names of the XObjects still painted.
+    used_xobjects: HashSet<Vec<u8>>,
+    /// Names of the XObjects removed at least once.
+    removed_xobjects: HashSet<Vec<u8>>,
+    /// Names of the redacted copies of XObjects, with their identifiers.
+    added_xobjects: Vec<(Vec<u8>, ObjectId)>,
+    /// The redacted copies.
+    objects: Vec<(ObjectId, Object)>,
+    /// Number of the next redacted copy.
+    next_number: u32,
+}
+
+impl<'a> ContentRedactor<'a> {
+
+    /// Creates a new `ContentRedactor`.
+    pub(crate) fn new(document: &'a Document, resources: &'a Dicionary, areas: &'a [Rectangle]) -> Self {
+        Self::with_interpreter(Interpreter::new(document, resources), areas, document.next_object_number())
+    }
+
+    /// Creates a new `ContentRedactor` for content run by the interpreter,
+    /// numbering its redacted copies from `next_number`.
+    fn with_interpreter(content: Interpreter<'a>, areas: &'a [Rectangle], next_number: u32) -> Self {
+        Self {
+            content,
+            areas,
+            path: Path::default(),
+            output: Vec::new(),
+            report: RedactionReport::default(),
+            used_xobjects: HashSet::new(),
+            removed_xobjects: HashSet::new(),
+            added_xobjects: Vec::new(),
+            objects: Vec::new(),
+            next_number,
+        }
+    }
+
+    /// Redacts the operations of a content stream.
+    pub(crate) fn redact(mut self, operations: &[Operation]) -> Result<RedactedContent, String> {
+
+        for operation in operations {
+            self.process(operation)?;
+        }
+
+        let unused_xobjects = self.removed_xobjects.iter()
+            .filter(|name| !self.used_xobjects.contains(*name))
+            .cloned()
+            .collect();
+
+        Ok(RedactedContent {
+            content: self.output,
+            report: self.report,
+            unused_xobjects,
+            added_xobjects: self.added_xobjects,
+            objects: self.objects,
+        })
+    }
+
+    /// Processes one operation.
+    fn process(&mut self, operation: &Operation) -> Result<(), String> {
+
+        let operator = operation.operator();
+
+        if operator.shows_text() {
+            return self.show_text(operation);
+        }
+
+        if operator.constructs_path() {
+            return self.build_path(operation);
+        }
+
+        if operator.paints_path() {
+            return self.paint_path(operation);
+        }
+
+        match operator {
+            Operator::Clip | Operator::ClipEvenOdd => {
+                self.path.clip = true;
+                self.path.operations.push(operation.as_bytes().to_vec());
+                return Ok(());
+            }
+            Operator::PaintXObject => return self.paint_xobject(operation),
+            Operator::BeginInlineImage if self.intersects(&self.content.state().ctm.transform_rectangle(&unit_square())) => {
+                return self.paint_inline_image(operation);
+            }
+            _ => self.content.update(operation),
+        }
+
+        self.emit(operation.as_bytes());
+
+        Ok(())
+    }
+
+    /// Appends an operation to the output.
+    fn emit(&mut self, bytes: &[u8]) {
+        self.output.extend_from_slice(bytes);
+        self.output.push(b'\n');
+    }
+
+    /// Returns `true` if the box intersects one of the areas.
+    fn intersects(&self, bounding_box: &Rectangle) -> bool {
+        self.areas.iter().any(|area| area.intersects(bounding_box))
+    }
+
+    /// Processes a text-showing operation, removing the glyphs inside the areas.
+    ///
+    /// Removed glyphs are replaced by position adjustments, so that the
+    /// remaining glyphs keep their positions.
+    fn show_text(&mut self, operation: &Operation) -> Result<(), String> {
+
+        let Some(elements) = self.content.begin_show(operation) else {
+            self.emit(operation.as_bytes());
+            return Ok(());
+        };
+
+        let font = self.content.font()?;
+
+        let mut output: Vec<TextElement> = Vec::new();
+        let mut removed = 0;
+
+        for element in elements {
+            match element {
+                Err(adjust) => {
+                    self.content.adjust(&font, adjust);
+                    push_adjust(&mut output, adjust);
+                }
+                Ok(text) => {
+                    for glyph in self.content.show(&font, &text) {
+                        // Glyphs shown at size 0 have an empty box at their origin.
+                        let bounding_box = glyph.bounding_box();
+                        let inside = self.intersects(&bounding_box) || self.areas.iter().any(|area| area.contains(&bounding_box));
+
+                        if inside {
+                            removed += 1;
+
+                            // Adjustments move nothing at size 0, where glyphs are invisible.
+                            if glyph.font_size() != 0.0 {
+                                push_adjust(&mut output, -glyph.advance() * 1000.0 / glyph.font_size());
+                            }
+                        } else {
+                            let code = &glyph.code().to_be_bytes()[4 - glyph.length()..];
+
+                            match output.last_mut() {
+                                Some(TextElement::Text(text)) => text.extend_from_slice(code),
+                                _ => output.push(TextElement::Text(code.to_vec())),
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        if removed == 0 {
+            self.emit(operation.as_bytes());
+            return Ok(());
+        }
+
+        self.report.glyphs += removed;
+
+        // The line moves and spacing of `'` and `"` are kept.
+        let operands = operation.operands();
+
+        let mut replacement = match operation.operator() {
+            Operator::NextLineShowText => b"T* ".to_vec(),
+            Operator::NextLineShowTextSpacing if operands.len() == 3 => {
+                [operands[0].as_bytes(), b" Tw ", operands[1].as_bytes(), b" Tc T* "].concat()
+            }
+            _ => Vec::new(),
+        };
+
+        replacement.push(b'[');
+
+        for (index, element) in output.iter().enumerate() {
+            if index > 0 {
+                replacement.push(b' ');
+            }
+
+            match element {
+                TextElement::Text(text) => replacement.extend_from_slice(HexadecimalString::from_value(text).as_bytes()),
+                TextElement::Adjust(adjust) => replacement.extend_from_slice(format_number(*adjust).as_bytes()),
+            }
+        }
+
+        replacement.extend_from_slice(b"] TJ");
+        self.emit(&replacement);
+
+        Ok(())
+    }
+
+    /// Processes a path construction operation.
+    fn build_path(&mut self, operation: &Operation) -> Result<(), String> {
+
+        let numbers = operation.operands().iter().filter_map(Object::as_f64).collect::<Vec<_>>();
+        let ctm = self.content.state().ctm;
+
+        let points = numbers.chunks_exact(2)
+            .map(|point| ctm.transform_point(point[0], point[1]))
+            .collect::<Vec<_>>();
+
+        let is_axis_aligned = ctm.b == 0.0 && ctm.c == 0.0;
+
+        if *operation.operator() == Operator::Rectangle && numbers.len() == 4 {
+            let rectangle = ctm.transform_rectangle(&Rectangle::new(numbers[0], numbers[1], numbers[0] + numbers[2], numbers[1] + numbers[3]));
+
+            self.path.bounding_box = Some(self.path.bounding_box.map_or(rectangle, |bounding_box| bounding_box.union(&rectangle)));
+
+            match (&mut self.path.rectangles, is_axis_aligned) {
+                (Some(rectangles), true) => rectangles.push(rectangle),
+                (None, true) if self.path.operations.is_empty() => self.path.rectangles = Some(vec![rectangle]),
+                _ => self.path.rectangles = None,
+            }
+        } else {
+            if let Some(bounding_box) = Rectangle::bounding(&points) {
+                self.path.bounding_box = Some(self.path.bounding_box.map_or(bounding_box, |existing| existing.union(&bounding_box)));
+            }
+
+            if *operation.operator() != Operator::ClosePath {
+                self.path.rectangles = None;
+            }
+        }
+
+        self.path.operations.push(operation.as_bytes().to_vec());
+
+        Ok(())
+    }
+
+    /// Processes a path painting operation, removing paths that paint inside the areas.
+    ///
+    /// A path made of rectangles is kept when each rectangle is either
+    /// outside the areas or covers them entirely, as backgrounds do.
+    fn paint_path(&mut self, operation: &Operation) -> Result<(), String> {
+
+        let path = std::mem::take(&mut self.path);
+        let strokes = operation.operator().strokes_path();
+
+        let bounding_box = path.bounding_box.map(|bounding_box| {
+            // A stroke reaches half the line width beyond the path.
+            let scale = (self.content.state().ctm.a * self.content.state().ctm.d - self.content.state().ctm.b * self.content.state().ctm.c).abs().sqrt();
+            let half = if strokes { (self.content.state().line_width * scale).max(MIN_STROKE_WIDTH) / 2.0 } else { 0.0 };
+
+            Rectangle::new(
+                bounding_box.left() - half,
+                bounding_box.bottom() - half,
+                bounding_box.right() + half,
+                bounding_box.top() + half,
+            )
+        });
+
+        let paints_inside = *operation.operator() != Operator::EndPath && match (&path.rectangles, bounding_box) {
+            (Some(rectangles), _) if !strokes => rectangles.iter().any(|rectangle| {
+                self.areas.iter().any(|area| area.intersects(rectangle) && !rectangle.contains(area))
+            }),
+            (_, Some(bounding_box)) => self.intersects(&bounding_box),
+            _ => false,
+        };
+
+        if !paints_inside {
+            for bytes in &path.operations {
+                self.emit(bytes);
+            }
+
+            self.emit(operation.as_bytes());
+            return Ok(());
+        }
+
+        self.report.paths += 1;
+
+        // A clipping path must still be applied, without painting it.
+        if path.clip {
+            for bytes in &path.operations {
+                self.emit(bytes);
+            }
+
+            self.emit(b"n");
+        }
+
+        Ok(())
+    }
+
+    /// Processes an XObject painting operation, replacing XObjects that
+    /// paint inside the areas by redacted copies.
+    fn paint_xobject(&mut self, operation: &Operation) -> Result<(), String> {
+
+        let bytes = operation.as_bytes();
+
+        let Some(name) = operation.name_operand(0).map(|name| name.as_bytes().to_vec()) else {
+            self.emit(bytes);
+            return Ok(());
+        };
+
+        let (id, xobject) = self.content.resource(b"/XObject", &name)?;
+
+        let Some(stream) = xobject.as_stream() else {
+            self.used_xobjects.insert(name);
+            self.emit(bytes);
+            return Ok(());
+        };
+
+        let dicionary = stream.dicionary();
+
+        let replacement = match dicionary.get(b"/Subtype").and_then(Object::as_name).map(|name| name.as_bytes()) {
+            Some(b"/Image") if self.intersects(&self.content.state().ctm.transform_rectangle(&unit_square())) => {
+                let replacement = self.redact_image(stream)?;
+
+                if replacement != Replacement::Keep {
+                    self.report.images += 1;
+                }
+
+                replacement
+            }
+            Some(b"/Form") => {
+                let form_box = dicionary.get(b"/BBox")
+                    .map(|form_box| self.content.document().resolve(form_box))
+                    .transpose()?
+                    .and_then(|form_box| Rectangle::from_object(&form_box));
+
+                let matrix = dicionary.get(b"/Matrix")
+                    .map(|matrix| self.content.document().resolve(matrix))
+                    .transpose()?
+                    .and_then(|matrix| Matrix::from_object(&matrix))
+                    .unwrap_or(Matrix::IDENTITY);
+
+                match form_box {
+                    Some(form_box) if self.intersects(&matrix.multiply(&self.content.state().ctm).transform_rectangle(&form_box)) => {
+                        self.redact_form(id, stream)?
+                    }
+                    _ => Replacement::Keep,
+                }
+            }
+            _ => Replacement::Keep,
+        };
+
+        match replacement {
+            Replacement::Keep => {
+                self.used_xobjects.insert(name);
+                self.emit(bytes);
+            }
+            Replacement::Copy(copy) => {
+                self.removed_xobjects.insert(name);
+                self.paint_copy(copy)?;
+            }
+            Replacement::Remove => {
+                self.removed_xobjects.insert(name);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Processes an inline image painting inside the areas, replacing it
+    /// by a redacted image XObject.
+    fn paint_inline_image(&mut self, operation: &Operation) -> Result<(), String> {
+
+        let Some(image) = operation.inline_image() else {
+            self.emit(operation.as_bytes());
+            return Ok(());
+        };
+
+        let mut stream = image.to_stream();
+
+        // Inline images may name a color space of the resources.
+        if let Some(Object::Name(name)) = stream.dicionary().get(b"/ColorSpace")
+            && let (_, color_space) = self.content.resource(b"/ColorSpace", name.as_bytes())?
+            && !matches!(color_space, Object::Null(_))
+        {
+            let mut dicionary = stream.dicionary().clone();
+            dicionary.insert(Name::new(b"/ColorSpace").unwrap(), color_space);
+            stream = Stream::new(dicionary, image.data().to_vec());
+        }
+
+        match self.redact_image(&stream)? {
+            Replacement::Keep => self.emit(operation.as_bytes()),
+            Replacement::Copy(copy) => {
+                self.report.images += 1;
+                self.paint_copy(copy)?;
+            }
+            Replacement::Remove => self.report.images += 1,
+        }
+
+        Ok(())
+    }
+
+    /// Paints black the pixels of an image under the areas, or leaves them
+    /// unpainted for stencil masks.
+    ///
+    /// The copy is opaque, as soft masks and color key masks are not
+    /// redacted; images that cannot be decoded are removed.
+    fn redact_image(&self, image: &Stream) -> Result<Replacement, String> {
+
+        let Ok(mut raster) = Raster::decode(self.content.document(), image) else {
+            return Ok(Replacement::Remove);
+        };
+
+        let ctm = self.content.state().ctm;
+
+        if self.areas.iter().map(|area| raster.blank(&ctm, area)).sum::<usize>() == 0 {
+            return Ok(Replacement::Keep);
+        }
+
+        Ok(Replacement::Copy(raster.to_stream()?))
+    }
+
+    /// Redacts the content of a form XObject with the current state.
+    ///
+    /// Forms already being redacted (a cycle) and forms that cannot be
+    /// parsed are removed.
+    fn redact_form(&mut self, id: Option<ObjectId>, form: &Stream) -> Result<Replacement, String> {
+
+        let Some(content) = self.content.form_interpreter(id, form)? else {
+            self.report.forms += 1;
+            return Ok(Replacement::Remove);
+        };
+
+        let Ok(operations) = form.decode().and_then(|data| parse_operations(&data)) else {
+            self.report.forms += 1;
+            return Ok(Replacement::Remove);
+        };
+
+        let resources = content.resources().clone();
+
+        let redacted = ContentRedactor::with_interpreter(content, self.areas, self.next_number).redact(&operations)?;
+
+        if redacted.report == RedactionReport::default() {
+            return Ok(Replacement::Keep);
+        }
+
+        let mut dicionary = form.dicionary().clone();
+
+        for key in [b"/Length".as_slice(), b"/Filter", b"/DecodeParms", b"/DL"] {
+            dicionary.remove(key);
+        }
+
+        dicionary.insert(Name::new(b"/Resources").unwrap(), Object::Dicionary(redacted.resources(self.content.document(), &resources)?));
+        dicionary.insert(Name::new(b"/Filter").unwrap(), Object::Name(Name::new(b"/FlateDecode").unwrap()));
+
+        self.report.add(&redacted.report);
+        self.report.forms += 1;
+        self.next_number += redacted.objects.len() as u32;
+        self.objects.extend(redacted.objects);
+
+        Ok(Replacement::Copy(encode_stream(dicionary, &redacted.content)?))
+    }
+
+    /// Adds a redacted copy of an XObject to the resources and paints it.
+    fn paint_copy(&mut self, copy: Stream) -> Result<(), String> {
+
+        let id = ObjectId::new(self.next_number, 0);
+        self.next_number += 1;
+        self.objects.push((id, Object::Stream(copy)));
+
+        let mut number = 1;
+
+        let name = loop {
+            let name = format!("/Redacted{}", number).into_bytes();
+
+            let is_added = self.added_xobjects.iter().any(|(added, _)| *added == name);
+
+            if !is_added && matches!(self.content.resource(b"/XObject", &name)?, (_, Object::Null(_))) {
+                break name;
+            }
+
+            number += 1;
+        };
+
+        self.emit(&[name.as_slice(), b" Do"].concat());
+        self.added_xobjects.push((name, id));
+
+        Ok(())
+    }
+}
+
+/// Appends a position adjustment, merging it with a preceding one.
+fn push_adjust(output: &mut Vec<TextElement>, adjust: f64) {
+
+    match output.last_mut() {
+        Some(TextElement::Adjust(previous)) => *previous += adjust,
+        _ => output.push(TextElement::Adjust(adjust)),
+    }
+}
+
+/// Returns the unit square that images are painted into.
+fn unit_square() -> Rectangle {
+    Rectangle::new(0.0, 0.0, 1.0, 1.0)
+}
+
+/// Formats a number for a content stream.
+pub(crate) fn format_number(value: f64) -> String {
+    String::from_utf8_lossy(Real::from_value(value).as_bytes()).into_owned()
+}
\ No newline at end of file
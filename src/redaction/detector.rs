@@ -0,0 +1,205 @@
+use std::fmt::Debug;
+use std::ops::Range;
+
+use regex::Regex;
+
+/// Detects sensitive data in the text of a page.
+pub trait Detector: Debug + Send + Sync {
+
+    /// Returns the name of the kind of data detected (i.e `email`).
+    fn name(&self) -> &str;
+
+    /// Returns the byte ranges of the data detected in a text.
+    fn detect(&self, text: &str) -> Vec<Range<usize>>;
+}
+
+/// A detector matching a regular expression, with an optional check of
+/// each match (i.e a check digit).
+#[derive(Debug, Clone)]
+pub struct PatternDetector {
+    /// The name of the kind of data detected.
+    name: String,
+    /// The regular expression candidates are matched with.
+    regex: Regex,
+    /// The check a candidate must pass to be detected, if any.
+    check: Option<fn(&str) -> bool>,
+}
+
+impl PatternDetector {
+
+    /// Creates a new `PatternDetector` from a regular expression.
+    pub fn new(name: &str, pattern: &str) -> Result<Self, String> {
+
+        let regex = Regex::new(pattern)
+            .map_err(|error| format!("Invalid regular expression: {}", error))?;
+
+        Ok(Self { name: name.to_string(), regex, check: None })
+    }
+
+    /// Sets the check a candidate must pass to be detected.
+    pub fn with_check(mut self, check: fn(&str) -> bool) -> Self {
+        self.check = Some(check);
+        self
+    }
+
+    /// Detects email addresses.
+    pub fn email() -> Self {
+        Self::built_in("email", r"(?i)\b[A-Z0-9._%+-]+@[A-Z0-9-]+(?:\.[A-Z0-9-]+)*\.[A-Z]{2,}\b")
+    }
+
+    /// Detects international phone numbers (i.e `+81 3-1234-5678`).
+    pub fn phone_number() -> Self {
+        Self::built_in("phone number", r"\+[1-9]\d{0,2}(?:[ .-]?\(?\d{1,4}\)?){2,5}\b")
+            .with_check(|candidate| (8..=15).contains(&digits(candidate).len()))
+    }
+
+    /// Detects payment card numbers, checked with the Luhn algorithm.
+    pub fn card_number() -> Self {
+        Self::built_in("card number", r"\b\d(?:[ -]?\d){12,18}\b")
+            .with_check(|candidate| luhn(&digits(candidate)))
+    }
+
+    /// Detects IBANs, checked with their mod-97 check digits.
+    pub fn iban() -> Self {
+        Self::built_in("iban", r"\b[A-Z]{2}\d{2}(?: ?[A-Z0-9]{4}){2,7}(?: ?[A-Z0-9]{1,3})?\b")
+            .with_check(iban)
+    }
+
+    /// Detects US social security numbers (i.e `123-45-6789`).
+    pub fn us_ssn() -> Self {
+        Self::built_in("us ssn", r"\b\d{3}-\d{2}-\d{4}\b")
+            .with_check(|candidate| {
+                let (area, group, serial) = (&candidate[..3], &candidate[4..6], &candidate[7..]);
+
+                area != "000" && area != "666" && !area.starts_with('9') && group != "00" && serial != "0000"
+            })
+    }
+
+    /// Detects Japanese individual numbers (My Number), checked with their check digit.
+    pub fn my_number() -> Self {
+        Self::built_in("my number", r"\b\d{4}[ -]?\d{4}[ -]?\d{4}\b")
+            .with_check(|candidate| my_number(&digits(candidate)))
+    }
+
+    /// Returns every built-in detector.
+    pub fn built_ins() -> Vec<Self> {
+        vec![
+            Self::email(),
+            Self::phone_number(),
+            Self::card_number(),
+            Self::iban(),
+            Self::us_ssn(),
+            Self::my_number(),
+        ]
+    }
+
+    /// Creates a built-in detector from a pattern known to be valid.
+    fn built_in(name: &str, pattern: &str) -> Self {
+        Self::new(name, pattern).expect("built-in patterns are valid")
+    }
+}
+
+impl Detector for PatternDetector {
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn detect(&self, text: &str) -> Vec<Range<usize>> {
+
+        self.regex.find_iter(text)
+            .filter(|found| self.check.is_none_or(|check| check(found.as_str())))
+            .map(|found| found.range())
+            .collect()
+    }
+}
+
+/// Returns the values of the decimal digits of a text.
+fn digits(text: &str) -> Vec<u8> {
+    text.bytes().filter(u8::is_ascii_digit).map(|digit| digit - b'0').collect()
+}
+
+/// Checks a number with the Luhn algorithm.
+fn luhn(digits: &[u8]) -> bool {
+
+    let sum = digits.iter().rev().enumerate().map(|(index, &digit)| {
+        match index % 2 {
+            0 => digit as u32,
+            _ if digit > 4 => digit as u32 * 2 - 9,
+            _ => digit as u32 * 2,
+        }
+    }).sum::<u32>();
+
+    sum % 10 == 0
+}
+
+/// Checks an IBAN with its mod-97 check digits (ISO 13616).
+fn iban(candidate: &str) -> bool {
+
+    let compact = candidate.bytes().filter(|byte| *byte != b' ').collect::<Vec<_>>();
+
+    if !(15..=34).contains(&compact.len()) {
+        return false;
+    }
+
+    let rearranged = compact[4..].iter().chain(&compact[..4]);
+
+    let remainder = rearranged.fold(0u32, |remainder, &byte| match byte {
+        b'0'..=b'9' => (remainder * 10 + (byte - b'0') as u32) % 97,
+        _ => (remainder * 100 + (byte - b'A') as u32 + 10) % 97,
+    });
+
+    remainder == 1
+}
+
+/// Checks a Japanese individual number with its check digit.
+fn my_number(digits: &[u8]) -> bool {
+
+    let sum = digits[..11].iter().rev().enumerate().map(|(index, &digit)| {
+        let weight = if index < 6 { index + 2 } else { index - 4 };
+        digit as usize * weight
+    }).sum::<usize>();
+
+    let check = match sum % 11 {
+        0 | 1 => 0,
+        remainder => 11 - remainder,
+    };
+
+    digits[11] as usize == check
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Detector, PatternDetector};
+
+    fn detect(detector: &PatternDetector, text: &str) -> Vec<String> {
+        detector.detect(text).into_iter().map(|range| text[range].to_string()).collect()
+    }
+
+    #[test]
+    fn should_detect_emails_and_phone_numbers() {
+        assert_eq!(detect(&PatternDetector::email(), "Mail john.doe@mail.example.com, or a@b."), vec!["john.doe@mail.example.com"]);
+        assert_eq!(detect(&PatternDetector::phone_number(), "Call +81 3-1234-5678 or +1 (555) 010-4477, not +12."), vec!["+81 3-1234-5678", "+1 (555) 010-4477"]);
+    }
+
+    #[test]
+    fn should_check_card_numbers_and_ibans() {
+        assert_eq!(detect(&PatternDetector::card_number(), "4111 1111 1111 1111 and 4111 1111 1111 1112"), vec!["4111 1111 1111 1111"]);
+        assert_eq!(detect(&PatternDetector::iban(), "GB82 WEST 1234 5698 7654 32, DE89370400440532013000, GB82 WEST 1234 5698 7654 33"), vec!["GB82 WEST 1234 5698 7654 32", "DE89370400440532013000"]);
+    }
+
+    #[test]
+    fn should_check_national_ids() {
+        assert_eq!(detect(&PatternDetector::us_ssn(), "123-45-6789, 666-45-6789, 123-00-6789"), vec!["123-45-6789"]);
+        assert_eq!(detect(&PatternDetector::my_number(), "1234 5678 9018 and 123456789017"), vec!["1234 5678 9018"]);
+    }
+
+    #[test]
+    fn should_create_custom_detectors() {
+        let detector = PatternDetector::new("employee id", r"EMP-\d{5}").unwrap().with_check(|candidate| !candidate.ends_with('0'));
+
+        assert_eq!(detector.name(), "employee id");
+        assert_eq!(detect(&detector, "EMP-12345 EMP-12340"), vec!["EMP-12345"]);
+        assert!(PatternDetector::new("broken", "(").is_err());
+    }
+}
\ No newline at end of file
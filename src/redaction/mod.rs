@@ -0,0 +1,305 @@
+//! This module contains the redaction of page content.
+mod content;
+mod detector;
+mod search;
+mod text;
+
+use crate::content::parse_operations;
+use crate::document::Document;
+use crate::filter::encode_stream;
+use crate::geometry::Rectangle;
+use crate::object::{Dicionary, Name, Object, Reference};
+use crate::redaction::content::{format_number as number, ContentRedactor};
+
+pub use crate::redaction::detector::Detector;
+pub use crate::redaction::detector::PatternDetector;
+pub use crate::redaction::search::find_detected;
+pub use crate::redaction::search::find_text;
+pub use crate::redaction::search::TextMatch;
+pub use crate::redaction::search::TextPattern;
+
+/// Counts of the content removed by a redaction.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RedactionReport {
+    /// Number of text glyphs removed.
+    glyphs: usize,
+    /// Number of vector paths removed.
+    paths: usize,
+    /// Number of images redacted or removed, inline images included.
+    images: usize,
+    /// Number of form XObjects redacted or removed.
+    forms: usize,
+}
+
+impl RedactionReport {
+
+    /// Returns the number of text glyphs removed.
+    pub fn glyphs(&self) -> usize {
+        self.glyphs
+    }
+
+    /// Returns the number of vector paths removed.
+    pub fn paths(&self) -> usize {
+        self.paths
+    }
+
+    /// Returns the number of images redacted or removed, inline images included.
+    pub fn images(&self) -> usize {
+        self.images
+    }
+
+    /// Returns the number of form XObjects redacted or removed.
+    pub fn forms(&self) -> usize {
+        self.forms
+    }
+
+    /// Adds the counts of another report to this one.
+    pub fn add(&mut self, other: &RedactionReport) {
+        self.glyphs += other.glyphs;
+        self.paths += other.paths;
+        self.images += other.images;
+        self.forms += other.forms;
+    }
+}
+
+/// Redacts the given areas of a page.
+///
+/// Text glyphs and vector paths that paint inside the areas are
+/// removed from the page content, which is replaced by a new content
+/// stream. The pixels of images under the areas are painted black and
+/// form XObjects are redacted in turn, both being replaced by redacted
+/// copies; those that cannot be decoded are removed. When `fill` is an
+/// RGB color, a box of that color is drawn over each area. Areas are
+/// given in default user space.
+pub fn redact_page(
+    document: &mut Document,
+    index: usize,
+    areas: &[Rectangle],
+    fill: Option<[f64; 3]>,
+) -> Result<RedactionReport, String> {
+
+    let page = document.page(index)
+        .ok_or_else(|| format!("Page index {} is out of range", index))?
+        .clone();
+
+    let content = document.page_content(index)?;
+    let operations = parse_operations(&content)?;
+
+    let redacted = ContentRedactor::new(document, page.resources(), areas).redact(&operations)?;
+
+    // The original content may leave the graphics state changed.
+    let mut bytes = b"q\n".to_vec();
+    bytes.extend_from_slice(&redacted.content);
+    bytes.extend_from_slice(b"Q\n");
+
+    if let Some([red, green, blue]) = fill {
+        for area in areas {
+            bytes.extend_from_slice(format!(
+                "q {} {} {} rg {} {} {} {} re f Q\n",
+                number(red), number(green), number(blue),
+                number(area.left()), number(area.bottom()), number(area.width()), number(area.height()),
+            ).as_bytes());
+        }
+    }
+
+    // Replaced XObjects must not stay reachable from the page.
+    let resources = redacted.resources(document, page.resources())?;
+
+    for (id, object) in redacted.objects {
+        document.set_object(id, object);
+    }
+
+    // The new content stays compressed, as the original most likely was.
+    let mut filter = Dicionary::new(vec![]);
+    filter.insert(Name::new(b"/Filter").unwrap(), Object::Name(Name::new(b"/FlateDecode").unwrap()));
+
+    let contents = document.add_object(Object::Stream(encode_stream(filter, &bytes)?));
+
+    let mut dicionary = page.dicionary().clone();
+    dicionary.insert(Name::new(b"/Contents").unwrap(), Object::Reference(Reference::new(contents)));
+    dicionary.insert(Name::new(b"/Resources").unwrap(), Object::Dicionary(resources));
+
+    document.set_page(index, dicionary)?;
+
+    Ok(redacted.report)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::redact_page;
+    use crate::document::Document;
+    use crate::document::tests::build_pdf;
+    use crate::geometry::Rectangle;
+    use crate::object::{Dicionary, Object};
+
+    pub(crate) fn document(content: &[u8]) -> Document {
+        let content = [format!("<</Length {}>>\nstream\n", content.len()).as_bytes(), content, b"\nendstream"].concat();
+
+        let source = build_pdf(&[
+            b"<</Type /Catalog /Pages 2 0 R>>",
+            b"<</Type /Pages /Kids [3 0 R] /Count 1 /MediaBox [0 0 600 400]>>",
+            b"<</Type /Page /Parent 2 0 R /Contents 4 0 R /Resources <</Font <</F1 5 0 R>> /XObject <</Im1 6 0 R /Im2 6 0 R>>>>>>",
+            &content,
+            b"<</Type /Font /Subtype /Type1 /BaseFont /Helvetica /FirstChar 65 /Widths [500 600 700]>>",
+            b"<</Type /XObject /Subtype /Image /Width 1 /Height 1 /ColorSpace /DeviceGray /BitsPerComponent 8 /Length 1>>\nstream\n\xff\nendstream",
+        ]);
+
+        Document::read(&source).unwrap()
+    }
+
+    fn redact(content: &[u8], area: Rectangle) -> (Document, super::RedactionReport, String) {
+        let mut document = document(content);
+        let report = redact_page(&mut document, 0, &[area], None).unwrap();
+        let content = String::from_utf8(document.page_content(0).unwrap()).unwrap();
+
+        (document, report, content)
+    }
+
+    #[test]
+    fn should_remove_glyphs_and_keep_positions() {
+        let (_, report, content) = redact(b"BT /F1 10 Tf 100 100 Td (ABC) Tj ET", Rectangle::new(106.0, 95.0, 110.0, 110.0));
+
+        assert_eq!(report.glyphs(), 1);
+        assert!(content.contains("[<41> -600 <43>] TJ"));
+        assert!(!content.contains("(ABC)"));
+        assert!(content.starts_with("q\n") && content.ends_with("Q\n"));
+    }
+
+    #[test]
+    fn should_compensate_spacing_and_kerning_in_arrays() {
+        let (_, report, content) = redact(
+            b"BT /F1 10 Tf 2 Tc 100 100 Td [(AB) -100 (C)] TJ 0 -20 Td (AB) ' ET",
+            Rectangle::new(100.0, 70.0, 104.0, 110.0),
+        );
+
+        assert_eq!(report.glyphs(), 2);
+        assert!(content.contains("[-700 <42> -100 <43>] TJ"));
+        assert!(content.contains("T* [-700 <42>] TJ"));
+    }
+
+    #[test]
+    fn should_remove_glyphs_shown_at_size_zero() {
+        let (_, report, content) = redact(b"BT /F1 0 Tf 100 100 Td (ABC) Tj ET", Rectangle::new(95.0, 95.0, 105.0, 105.0));
+
+        assert_eq!(report.glyphs(), 3);
+        assert!(!content.contains("(ABC)"));
+    }
+
+    #[test]
+    fn should_keep_text_outside_areas() {
+        let (_, report, content) = redact(b"BT /F1 10 Tf 100 100 Td (ABC) Tj ET", Rectangle::new(300.0, 300.0, 400.0, 400.0));
+
+        assert_eq!(report, super::RedactionReport::default());
+        assert!(content.contains("(ABC) Tj"));
+    }
+
+    #[test]
+    fn should_remove_paths_inside_areas_but_keep_backgrounds() {
+        let (_, report, content) = redact(
+            b"0 0 600 400 re f 150 150 m 250 250 l S 10 10 20 20 re f 180 180 50 50 re W n",
+            Rectangle::new(190.0, 190.0, 210.0, 210.0),
+        );
+
+        assert_eq!(report.paths(), 1);
+        assert!(content.contains("0 0 600 400 re\nf"));
+        assert!(!content.contains("150 150 m"));
+        assert!(content.contains("10 10 20 20 re\nf"));
+        assert!(content.contains("180 180 50 50 re\nW\nn"));
+    }
+
+    #[test]
+    fn should_replace_images_and_remove_their_resources() {
+        let (document, report, content) = redact(
+            b"q 50 0 0 50 200 200 cm /Im1 Do Q q 10 0 0 10 0 0 cm /Im2 Do BI /W 1 /H 1 /CS /G /BPC 8 ID \x00 EI Q q 50 0 0 50 200 200 cm BI /W 1 /H 1 ID \x00 EI Q",
+            Rectangle::new(220.0, 220.0, 230.0, 230.0),
+        );
+
+        assert_eq!(report.images(), 2);
+        assert!(!content.contains("/Im1 Do"));
+        assert!(content.contains("/Redacted1 Do"));
+        assert!(content.contains("/Im2 Do"));
+        assert_eq!(content.matches("BI").count(), 1);
+
+        let xobjects = document.page(0).unwrap().resources().get(b"/XObject").unwrap().as_dicionary().unwrap().clone();
+        assert!(xobjects.get(b"/Im1").is_none());
+        assert!(xobjects.get(b"/Im2").is_some());
+        assert!(xobjects.get(b"/Redacted1").is_some());
+    }
+
+    #[test]
+    fn should_redact_forms_and_image_pixels() {
+        let form = b"BT /F1 10 Tf 10 10 Td (AB) Tj ET q 40 0 0 40 50 50 cm /Im1 Do Q BI /W 2 /H 1 /CS /G /BPC 8 ID \x80\x80 EI";
+        let content = b"q 1 0 0 1 100 100 cm /Fm1 Do Q /Fm1 Do";
+
+        let source = build_pdf(&[
+            b"<</Type /Catalog /Pages 2 0 R>>",
+            b"<</Type /Pages /Kids [3 0 R] /Count 1 /MediaBox [0 0 600 400]>>",
+            b"<</Type /Page /Parent 2 0 R /Contents 4 0 R /Resources <</XObject <</Fm1 5 0 R>>>>>>",
+            &[format!("<</Length {}>>\nstream\n", content.len()).as_bytes(), content, b"\nendstream"].concat(),
+            &[
+                format!("<</Type /XObject /Subtype /Form /BBox [0 0 100 100] /Resources <</Font <</F1 7 0 R>> /XObject <</Im1 6 0 R>>>> /Length {}>>\nstream\n", form.len()).as_bytes(),
+                form,
+                b"\nendstream",
+            ].concat(),
+            b"<</Type /XObject /Subtype /Image /Width 2 /Height 2 /ColorSpace /DeviceGray /BitsPerComponent 8 /Length 4>>\nstream\n\xff\xff\xff\xff\nendstream",
+            b"<</Type /Font /Subtype /Type1 /BaseFont /Helvetica /FirstChar 65 /Widths [500 600]>>",
+        ]);
+
+        let mut document = Document::read(&source).unwrap();
+        let areas = [Rectangle::new(150.0, 150.0, 165.0, 165.0), Rectangle::new(108.0, 105.0, 114.0, 120.0), Rectangle::new(100.0, 100.0, 100.5, 100.5)];
+        let report = redact_page(&mut document, 0, &areas, None).unwrap();
+
+        assert_eq!((report.glyphs(), report.images(), report.forms()), (1, 2, 1));
+
+        let content = String::from_utf8(document.page_content(0).unwrap()).unwrap();
+        assert!(content.contains("/Redacted1 Do\nQ\n/Fm1 Do"));
+
+        let resolve = |dicionary: &Dicionary, name: &[u8]| {
+            let xobjects = document.resolve(dicionary.get(b"/XObject").unwrap()).unwrap();
+            document.resolve(xobjects.as_dicionary().unwrap().get(name).unwrap()).unwrap()
+        };
+
+        let form = resolve(document.page(0).unwrap().resources(), b"/Redacted1");
+        let form = form.as_stream().unwrap();
+        let resources = form.dicionary().get(b"/Resources").unwrap().as_dicionary().unwrap();
+        let operations = String::from_utf8(form.decode().unwrap()).unwrap();
+
+        assert!(operations.contains("[-500 <42>] TJ"));
+        assert!(operations.contains("/Redacted1 Do") && operations.contains("/Redacted2 Do"));
+        assert!(resources.get(b"/XObject").unwrap().as_dicionary().unwrap().get(b"/Im1").is_none());
+
+        let image = resolve(resources, b"/Redacted1");
+        assert_eq!(image.as_stream().unwrap().decode().unwrap(), [255, 255, 0, 255]);
+
+        let inline = resolve(resources, b"/Redacted2");
+        assert_eq!(inline.as_stream().unwrap().decode().unwrap(), [0, 128]);
+    }
+
+    #[test]
+    fn should_keep_stencil_masks_unpainted_under_areas() {
+        let (document, report, content) = redact(
+            b"q 20 0 0 20 100 100 cm BI /W 2 /H 2 /IM true ID \x00\x00 EI Q",
+            Rectangle::new(100.0, 100.0, 105.0, 105.0),
+        );
+
+        assert_eq!(report.images(), 1);
+        assert!(content.contains("/Redacted1 Do"));
+
+        let xobjects = document.page(0).unwrap().resources().get(b"/XObject").unwrap().as_dicionary().unwrap().clone();
+        let mask = document.resolve(xobjects.get(b"/Redacted1").unwrap()).unwrap();
+        let mask = mask.as_stream().unwrap();
+
+        assert!(matches!(mask.dicionary().get(b"/ImageMask"), Some(Object::Boolean(mask)) if mask.as_bool()));
+        assert_eq!(mask.decode().unwrap(), [0b0000_0000, 0b1000_0000]);
+    }
+
+    #[test]
+    fn should_draw_fill_boxes() {
+        let mut document = document(b"");
+        redact_page(&mut document, 0, &[Rectangle::new(10.0, 20.0, 40.0, 60.0)], Some([1.0, 0.0, 0.0])).unwrap();
+
+        let content = document.page_content(0).unwrap();
+        assert!(content.ends_with(b"q 1 0 0 rg 10 20 30 40 re f Q\n"));
+        assert!(redact_page(&mut document, 1, &[], None).is_err());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,194 @@
+use std::ops::Range;
+use std::sync::Arc;
+
+use regex::Regex;
+
+use crate::document::Document;
+use crate::geometry::Rectangle;
+use crate::redaction::detector::Detector;
+use crate::redaction::text::PageText;
+
+/// A pattern searched for in the text of pages.
+#[derive(Debug, Clone)]
+pub enum TextPattern {
+    /// Literal text. Any run of whitespace matches any run of
+    /// whitespace, so that text broken across lines is found.
+    Literal(String),
+    /// A regular expression.
+    Regex(Regex),
+}
+
+impl TextPattern {
+
+    /// Creates a pattern matching literal text.
+    pub fn literal(text: &str) -> Self {
+        TextPattern::Literal(text.to_string())
+    }
+
+    /// Creates a pattern from a regular expression.
+    pub fn regex(pattern: &str) -> Result<Self, String> {
+
+        Regex::new(pattern)
+            .map(TextPattern::Regex)
+            .map_err(|error| format!("Invalid regular expression: {}", error))
+    }
+
+    /// Returns the regular expression the pattern is matched with.
+    fn to_regex(&self) -> Result<Regex, String> {
+
+        match self {
+            TextPattern::Regex(regex) => Ok(regex.clone()),
+            TextPattern::Literal(text) => {
+                let words = text.split_whitespace().map(regex::escape).collect::<Vec<_>>();
+
+                if words.is_empty() {
+                    return Err("The literal pattern is empty".to_string());
+                }
+
+                Regex::new(&words.join(r"\s+")).map_err(|error| error.to_string())
+            }
+        }
+    }
+}
+
+/// A match of a pattern in the text of a page.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextMatch {
+    /// Index of the page.
+    page: usize,
+    /// The matched text.
+    text: String,
+    /// Bounding boxes of the matched text, one per line.
+    areas: Vec<Rectangle>,
+    /// Name of the detector that found the match, if any.
+    detector: Option<String>,
+}
+
+impl TextMatch {
+
+    /// Creates a new `TextMatch`.
+    pub fn new(page: usize, text: String, areas: Vec<Rectangle>) -> Self {
+        Self { page, text, areas, detector: None }
+    }
+
+    /// Sets the name of the detector that found the match.
+    pub fn with_detector(mut self, detector: &str) -> Self {
+        self.detector = Some(detector.to_string());
+        self
+    }
+
+    /// Returns the index of the page.
+    pub fn page(&self) -> usize {
+        self.page
+    }
+
+    /// Returns the matched text.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the bounding boxes of the matched text, one per line.
+    pub fn areas(&self) -> &[Rectangle] {
+        &self.areas
+    }
+
+    /// Returns the name of the detector that found the match, if any.
+    pub fn detector(&self) -> Option<&str> {
+        self.detector.as_deref()
+    }
+}
+
+/// Finds the matches of a pattern in the text of every page.
+pub fn find_text(document: &Document, pattern: &TextPattern) -> Result<Vec<TextMatch>, String> {
+
+    let regex = pattern.to_regex()?;
+
+    find_ranges(document, |text| regex.find_iter(text).map(|found| (found.range(), None)).collect())
+}
+
+/// Finds the data detected by any of the detectors in the text of every page.
+pub fn find_detected(document: &Document, detectors: &[Arc<dyn Detector>]) -> Result<Vec<TextMatch>, String> {
+
+    find_ranges(document, |text| {
+        let mut ranges = detectors.iter()
+            .flat_map(|detector| detector.detect(text).into_iter().map(|range| (range, Some(detector.name()))))
+            .collect::<Vec<_>>();
+
+        ranges.sort_by_key(|(range, _)| (range.start, range.end));
+        ranges
+    })
+}
+
+/// Turns the byte ranges found in the text of every page into matches.
+fn find_ranges<'a, F>(document: &Document, mut find: F) -> Result<Vec<TextMatch>, String>
+where
+    F: FnMut(&str) -> Vec<(Range<usize>, Option<&'a str>)>,
+{
+    let mut matches = Vec::new();
+
+    for page in 0..document.pages().len() {
+
+        let text = PageText::extract(document, page)?;
+
+        for (range, detector) in find(text.text()) {
+
+            let areas = text.bounding_boxes(range.clone());
+
+            // A match made only of inserted separators covers nothing.
+            if areas.is_empty() {
+                continue;
+            }
+
+            let found = TextMatch::new(page, text.text()[range].to_string(), areas);
+
+            matches.push(match detector {
+                Some(detector) => found.with_detector(detector),
+                None => found,
+            });
+        }
+    }
+
+    Ok(matches)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::{find_detected, find_text, TextPattern};
+    use crate::redaction::detector::{Detector, PatternDetector};
+    use crate::redaction::tests::document;
+
+    #[test]
+    fn should_find_literal_text_across_lines() {
+        let document = document(b"BT /F1 10 Tf 100 100 Td (AB) Tj (C) Tj 0 -20 Td [(A) 50 (B)] TJ ET");
+        let matches = find_text(&document, &TextPattern::literal("C  A")).unwrap();
+
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].page(), 0);
+        assert_eq!(matches[0].text(), "C\nA");
+        assert_eq!(matches[0].areas().len(), 2);
+    }
+
+    #[test]
+    fn should_find_regex_matches() {
+        let document = document(b"BT /F1 10 Tf 100 100 Td (ABBA CAB) Tj ET");
+        let matches = find_text(&document, &TextPattern::regex("B+").unwrap()).unwrap();
+
+        assert_eq!(matches.iter().map(|m| m.text()).collect::<Vec<_>>(), vec!["BB", "B"]);
+        assert!(TextPattern::regex("(").is_err());
+        assert!(find_text(&document, &TextPattern::literal(" ")).is_err());
+    }
+
+    #[test]
+    fn should_find_detected_data() {
+        let document = document(b"BT /F1 10 Tf 100 100 Td (Mail a@b.example, SSN 123-45-6789) Tj ET");
+        let detectors: Vec<Arc<dyn Detector>> = vec![Arc::new(PatternDetector::us_ssn()), Arc::new(PatternDetector::email())];
+        let matches = find_detected(&document, &detectors).unwrap();
+
+        assert_eq!(matches.iter().map(|m| (m.text(), m.detector())).collect::<Vec<_>>(), vec![
+            ("a@b.example", Some("email")),
+            ("123-45-6789", Some("us ssn")),
+        ]);
+    }
+}
\ No newline at end of file
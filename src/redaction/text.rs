@@ -0,0 +1,145 @@
+use std::ops::Range;
+
+use crate::content::{parse_operations, Interpreter};
+use crate::document::Document;
+use crate::geometry::Rectangle;
+
+/// Share of the font height a gap between glyphs must exceed to read as a space.
+const WORD_GAP: f64 = 0.25;
+
+/// The text of a page, with the bounding box of each character.
+///
+/// Spaces between words and line breaks that are not drawn as glyphs
+/// are inserted from the glyph positions, so that text spanning
+/// several operations or lines reads naturally.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PageText {
+    /// The extracted text.
+    text: String,
+    /// Byte range in `text` and bounding box of each character.
+    /// Inserted spaces and line breaks have no bounding box.
+    characters: Vec<(Range<usize>, Option<Rectangle>)>,
+}
+
+impl PageText {
+
+    /// Extracts the text of the page at the given index, form XObjects included.
+    pub fn extract(document: &Document, index: usize) -> Result<Self, String> {
+
+        let page = document.page(index)
+            .ok_or_else(|| format!("Page index {} is out of range", index))?;
+
+        let content = document.page_content(index)?;
+        let glyphs = Interpreter::new(document, page.resources()).run(&parse_operations(&content)?)?;
+
+        let mut text = PageText::default();
+
+        for glyph in glyphs {
+            text.push_glyph(glyph.text(), glyph.bounding_box());
+        }
+
+        Ok(text)
+    }
+
+    /// Returns the extracted text.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the bounding boxes of the characters in a byte range of the text.
+    ///
+    /// Boxes are merged per line, so that a range spanning several
+    /// lines gives one box per line.
+    pub fn bounding_boxes(&self, range: Range<usize>) -> Vec<Rectangle> {
+
+        let mut boxes: Vec<Rectangle> = Vec::new();
+        let mut new_line = true;
+
+        for (character, bounding_box) in &self.characters {
+
+            if character.end <= range.start || character.start >= range.end {
+                continue;
+            }
+
+            match bounding_box {
+                Some(bounding_box) => match boxes.last_mut() {
+                    Some(last) if !new_line => *last = last.union(bounding_box),
+                    _ => {
+                        boxes.push(*bounding_box);
+                        new_line = false;
+                    }
+                },
+                None if self.text[character.clone()].contains('\n') => new_line = true,
+                None => {}
+            }
+        }
+
+        boxes
+    }
+
+    /// Appends the text of a glyph, preceded by an inserted separator if needed.
+    fn push_glyph(&mut self, string: &str, bounding_box: Rectangle) {
+
+        let previous = self.characters.iter().rev().find_map(|(range, bounding_box)| bounding_box.map(|b| (range.clone(), b)));
+
+        if let Some((range, previous)) = previous {
+
+            let height = previous.height().max(bounding_box.height());
+            let center = (bounding_box.bottom() + bounding_box.top()) / 2.0;
+            let previous_center = (previous.bottom() + previous.top()) / 2.0;
+
+            let separator = if (center - previous_center).abs() > height / 2.0 {
+                Some("\n")
+            } else if bounding_box.left() - previous.right() > height * WORD_GAP
+                && !self.text[range].ends_with(char::is_whitespace)
+                && !string.starts_with(char::is_whitespace)
+            {
+                Some(" ")
+            } else {
+                None
+            };
+
+            if let Some(separator) = separator.filter(|_| !self.text.ends_with('\n')) {
+                self.push(separator, None);
+            }
+        }
+
+        self.push(string, Some(bounding_box));
+    }
+
+    /// Appends text with its bounding box.
+    fn push(&mut self, string: &str, bounding_box: Option<Rectangle>) {
+
+        let start = self.text.len();
+        self.text.push_str(string);
+
+        self.characters.push((start..self.text.len(), bounding_box));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PageText;
+    use crate::geometry::Rectangle;
+    use crate::redaction::tests::document;
+
+    #[test]
+    fn should_join_operations_and_insert_separators() {
+        let document = document(b"BT /F1 10 Tf 100 100 Td (AB) Tj (C) Tj [(A) -1000 (B)] TJ 0 -20 Td (AB) Tj ET");
+        let text = PageText::extract(&document, 0).unwrap();
+
+        assert_eq!(text.text(), "ABCA B\nAB");
+    }
+
+    #[test]
+    fn should_return_boxes_per_line() {
+        let document = document(b"BT /F1 10 Tf 100 100 Td (ABC) Tj 0 -20 Td (AB) Tj ET");
+        let text = PageText::extract(&document, 0).unwrap();
+
+        assert_eq!(text.bounding_boxes(1..3), vec![Rectangle::new(105.0, 97.93, 118.0, 107.18)]);
+        assert_eq!(text.bounding_boxes(2..5), vec![
+            Rectangle::new(111.0, 97.93, 118.0, 107.18),
+            Rectangle::new(100.0, 77.93, 105.0, 87.18),
+        ]);
+    }
+}
\ No newline at end of file
@@ -1,6 +1,7 @@
 /// Validates if the given bytes represent a valid PDF Boolean.
 /// 
 /// Valid boolean bytes are "true" and "false".
+#[allow(dead_code)]
 pub fn validate_boolean_bytes(bytes: &[u8]) -> Result<(), String> {
     if bytes == b"true" || bytes == b"false" {
         Ok(())
@@ -23,6 +24,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::nonminimal_bool)]
     fn should_invalidate_boolean_bytes() {
         let invalid_bytes: &[&[u8]] = &[b"True", b"FALSE", b"yes", b"no", b"1", b"0", b"", b" "];
 
@@ -58,6 +58,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::nonminimal_bool)]
     fn should_invalidate_integer_bytes() {
 
         let invalid_bytes: &[&[u8]] = &[
@@ -1,6 +1,7 @@
 /// Checks if the given byte is a valid name character in a PDF Name object.
 /// 
 /// A valid name character is any byte outside the range `0x21` to `0x7E` (inclusive).
+#[allow(clippy::manual_range_contains)]
 pub fn validate_name_char(byte: &u8) -> Result<(), String> {
     
     if &0x21 <= byte && byte <= &0x7E {
@@ -12,6 +13,7 @@ pub fn validate_name_char(byte: &u8) -> Result<(), String> {
 }
 
 /// Validates if the given byte slice is a valid PDF Name representation.
+#[allow(clippy::useless_format, clippy::question_mark, clippy::op_ref)]
 pub fn validate_name_bytes(bytes: &[u8]) -> Result<(), String> {
     
     // empty bytes
@@ -45,6 +47,62 @@ pub fn validate_name_bytes(bytes: &[u8]) -> Result<(), String> {
     Ok(())
 }
 
+/// Decodes the `#xx` escapes of a PDF Name.
+/// 
+/// The given bytes must not contain the leading solidus.
+pub fn decode_name_escapes(bytes: &[u8]) -> Result<Vec<u8>, String> {
+
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+
+        if bytes[index] != b'#' {
+            decoded.push(bytes[index]);
+            index += 1;
+            continue;
+        }
+
+        let digits = bytes.get(index + 1..index + 3)
+            .filter(|d| d.iter().all(u8::is_ascii_hexdigit))
+            .ok_or_else(|| format!("Invalid escape sequence in name: {:?}", bytes))?;
+
+        // Safe because both digits are validated above.
+        let value = u8::from_str_radix(std::str::from_utf8(digits).unwrap(), 16).unwrap();
+
+        if value == 0 {
+            return Err("Name cannot contain a null character".to_string());
+        }
+
+        decoded.push(value);
+        index += 3;
+    }
+
+    Ok(decoded)
+}
+
+/// Encodes the given decoded name value with `#xx` escapes where required.
+/// 
+/// The returned bytes do not contain the leading solidus.
+pub fn encode_name_escapes(value: &[u8]) -> Vec<u8> {
+
+    let mut encoded = Vec::with_capacity(value.len());
+
+    for &byte in value {
+
+        let needs_escape = validate_name_char(&byte).is_err()
+            || matches!(byte, b'#' | b'/' | b'(' | b')' | b'<' | b'>' | b'[' | b']' | b'{' | b'}' | b'%');
+
+        if needs_escape {
+            encoded.extend_from_slice(format!("#{:02X}", byte).as_bytes());
+        } else {
+            encoded.push(byte);
+        }
+    }
+
+    encoded
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -79,4 +137,18 @@ mod tests {
             assert!(super::validate_name_bytes(name).is_err(), "Name {:?} should be invalid", name);
         }
     }
+
+    #[test]
+    fn should_decode_and_encode_name_escapes() {
+
+        assert_eq!(super::decode_name_escapes(b"A#20B#23").unwrap(), b"A B#");
+        assert_eq!(super::decode_name_escapes(b"Plain").unwrap(), b"Plain");
+        assert!(super::decode_name_escapes(b"A#2").is_err());
+        assert!(super::decode_name_escapes(b"A#ZZ").is_err());
+        assert!(super::decode_name_escapes(b"A#00").is_err());
+
+        assert_eq!(super::encode_name_escapes(b"A B#"), b"A#20B#23");
+        assert_eq!(super::encode_name_escapes(b"Paired()"), b"Paired#28#29");
+        assert_eq!(super::encode_name_escapes(&[0xE3, 0x81]), b"#E3#81");
+    }
 }
\ No newline at end of file
@@ -1,4 +1,5 @@
 /// Checks if the given bytes represent a valid real number in PDF format.
+#[allow(clippy::useless_format)]
 pub fn validate_real_number_bytes(bytes: &[u8]) -> Result<(), String> {
 
     if bytes.is_empty() {
@@ -8,6 +8,7 @@ pub fn is_valid_byte_marker_value(bytes: &[u8]) -> bool {
 
 #[cfg(test)]
 mod tests {
+    #[allow(unused_imports)]
     use super::is_valid_byte_marker_value;
 
     #[test]
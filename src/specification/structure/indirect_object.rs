@@ -0,0 +1,51 @@
+/// Validates if the given number is a valid PDF object number.
+/// 
+/// An object number must be a positive integer. The object
+/// number `0` is reserved for the head of the free list.
+pub fn validate_object_number(number: i64) -> Result<(), String> {
+
+    if number <= 0 || number > u32::MAX as i64 {
+        return Err(format!("Invalid object number: {}", number));
+    }
+
+    Ok(())
+}
+
+/// Validates if the given number is a valid PDF generation number.
+/// 
+/// A generation number must be a non-negative integer not greater than `65535`.
+pub fn validate_generation_number(number: i64) -> Result<(), String> {
+
+    if !(0..=u16::MAX as i64).contains(&number) {
+        return Err(format!("Invalid generation number: {}", number));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{validate_generation_number, validate_object_number};
+
+    #[test]
+    fn should_validate_object_numbers() {
+        for number in [1, 42, u32::MAX as i64] {
+            assert!(validate_object_number(number).is_ok(), "Expected valid: {}", number);
+        }
+
+        for number in [0, -1, u32::MAX as i64 + 1] {
+            assert!(validate_object_number(number).is_err(), "Expected invalid: {}", number);
+        }
+    }
+
+    #[test]
+    fn should_validate_generation_numbers() {
+        for number in [0, 1, 65535] {
+            assert!(validate_generation_number(number).is_ok(), "Expected valid: {}", number);
+        }
+
+        for number in [-1, 65536] {
+            assert!(validate_generation_number(number).is_err(), "Expected invalid: {}", number);
+        }
+    }
+}
\ No newline at end of file
@@ -1,6 +1,7 @@
 /// Checks if the given major and minor version numbers represent a valid PDF version.
 /// 
 /// Valid versions are 1.0 to 1.7 and 2.0.
+#[allow(clippy::match_like_matches_macro)]
 pub fn is_valid_version(major: u8, minor: u8) -> bool {
     match (major, minor) {
         (1, 0..=7) => true,
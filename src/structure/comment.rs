@@ -1,6 +1,7 @@
 use crate::structure::eof::EOF;
 use crate::structure::header::Header;
 
+#[allow(dead_code, clippy::upper_case_acronyms)]
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum StructuralComment {
     EOF(EOF),
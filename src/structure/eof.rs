@@ -1,5 +1,6 @@
 
 /// A PDF End-Of-File (EOF) marker representation.
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct EOF;
 
@@ -14,4 +15,11 @@ impl EOF {
     pub fn as_bytes(&self) -> &[u8] {
         b"%%EOF"
     }
+}
+
+impl Default for EOF {
+
+    fn default() -> Self {
+        Self::new()
+    }
 }
\ No newline at end of file
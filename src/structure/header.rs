@@ -8,17 +8,24 @@ pub struct Header {
     version: Version,
     /// Byte marker of the header.
     byte_marker: ByteMarker,
+    /// Byte representation of the header.
+    bytes: Vec<u8>,
 }
 
 impl Header {
 
     /// Creates a new `Header` with the given PDF version.
     pub fn new(version: Version) -> Self {
-        Self {
-            version,
-            // Traditional byte marker "%âãÏÓ"
-            byte_marker: ByteMarker::new(b"\xE2\xE3\xCF\xD3".to_vec()),
-        }
+
+        // Traditional byte marker "%âãÏÓ"
+        let byte_marker = ByteMarker::new(b"\xE2\xE3\xCF\xD3".to_vec());
+
+        let mut bytes = b"%PDF-".to_vec();
+        bytes.extend_from_slice(version.as_bytes());
+        bytes.push(b'\n');
+        bytes.extend_from_slice(byte_marker.as_bytes());
+
+        Self { version, byte_marker, bytes }
     }
 
     /// Returns the PDF version of the header.
@@ -31,5 +38,22 @@ impl Header {
 
         &self.byte_marker
     }
+
+    /// Returns the byte representation of the header: the version line
+    /// and the byte marker comment line.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::Header;
+    use crate::structure::Version;
+
+    #[test]
+    fn should_return_header_bytes() {
+        let header = Header::new(Version::new(1, 7));
+        assert_eq!(header.as_bytes(), b"%PDF-1.7\n%\xE2\xE3\xCF\xD3");
+    }
+}
\ No newline at end of file
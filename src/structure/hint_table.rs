@@ -0,0 +1,314 @@
+/// The hint tables of a linearized file: a page offset hint table
+/// followed by a shared object hint table.
+///
+/// Offsets are given as if the hint streams were not in the file. The
+/// content stream items of the page offset table describe each whole
+/// page, and every shared object group holds a single object.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct HintTables {
+    /// Offset of the first page object.
+    pub(crate) first_page_offset: u32,
+    /// The page entries, in page order.
+    pub(crate) pages: Vec<PageHint>,
+    /// Number of the first object of the shared objects section, or 0 if there is none.
+    pub(crate) first_shared_object: u32,
+    /// Offset of the first object of the shared objects section, or 0 if there is none.
+    pub(crate) first_shared_offset: u32,
+    /// Number of groups of the first page section, which come first.
+    pub(crate) first_page_groups: u32,
+    /// The shared object groups, as lengths and object counts.
+    pub(crate) groups: Vec<(u32, u32)>,
+}
+
+/// A page entry of the page offset hint table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PageHint {
+    /// Number of objects of the page.
+    pub(crate) objects: u32,
+    /// Length of the page, from its first object to the end of its last one.
+    pub(crate) length: u32,
+    /// Indexes in the shared object hint table of the groups the page uses.
+    pub(crate) shared: Vec<u32>,
+}
+
+impl HintTables {
+
+    /// Returns the hint stream data of the tables, and the offset of the
+    /// shared object hint table in it (`/S`).
+    pub(crate) fn to_bytes(&self) -> (Vec<u8>, usize) {
+
+        let mut writer = BitWriter::default();
+
+        let objects = self.pages.iter().map(|page| page.objects).collect::<Vec<_>>();
+        let lengths = self.pages.iter().map(|page| page.length).collect::<Vec<_>>();
+        let counts = self.pages.iter().map(|page| page.shared.len() as u32).collect::<Vec<_>>();
+        let shared = self.pages.iter().flat_map(|page| page.shared.iter().copied()).collect::<Vec<_>>();
+
+        let (least_objects, object_bits) = range(&objects);
+        let (least_length, length_bits) = range(&lengths);
+        let count_bits = width(counts.iter().copied().max().unwrap_or(0));
+        let shared_bits = width(shared.iter().copied().max().unwrap_or(0));
+
+        writer.write(least_objects, 32);
+        writer.write(self.first_page_offset, 32);
+        writer.write(object_bits, 16);
+        writer.write(least_length, 32);
+        writer.write(length_bits, 16);
+        // Content streams start each page and span it.
+        writer.write(0, 32);
+        writer.write(0, 16);
+        writer.write(least_length, 32);
+        writer.write(length_bits, 16);
+        writer.write(count_bits, 16);
+        writer.write(shared_bits, 16);
+        // Fractional positions of shared objects are not given.
+        writer.write(0, 16);
+        writer.write(1, 16);
+
+        for (values, least, bits) in [
+            (&objects, least_objects, object_bits),
+            (&lengths, least_length, length_bits),
+            (&counts, 0, count_bits),
+            (&shared, 0, shared_bits),
+            (&lengths, least_length, length_bits),
+        ] {
+            for value in values {
+                writer.write(value - least, bits);
+            }
+
+            writer.align();
+        }
+
+        let offset = writer.output.len();
+
+        let group_lengths = self.groups.iter().map(|(length, _)| *length).collect::<Vec<_>>();
+        let group_objects = self.groups.iter().map(|(_, objects)| objects.saturating_sub(1)).collect::<Vec<_>>();
+
+        let (least_group_length, group_length_bits) = range(&group_lengths);
+        let group_object_bits = width(group_objects.iter().copied().max().unwrap_or(0));
+
+        writer.write(self.first_shared_object, 32);
+        writer.write(self.first_shared_offset, 32);
+        writer.write(self.first_page_groups, 32);
+        writer.write(self.groups.len() as u32, 32);
+        writer.write(group_object_bits, 16);
+        writer.write(least_group_length, 32);
+        writer.write(group_length_bits, 16);
+
+        for length in &group_lengths {
+            writer.write(length - least_group_length, group_length_bits);
+        }
+
+        writer.align();
+
+        // No group has an MD5 signature.
+        for _ in &self.groups {
+            writer.write(0, 1);
+        }
+
+        writer.align();
+
+        for objects in &group_objects {
+            writer.write(*objects, group_object_bits);
+        }
+
+        writer.align();
+
+        (writer.output, offset)
+    }
+
+    /// Parses the hint tables of a file of `page_count` pages from the
+    /// hint stream data, the shared object hint table being at `shared_offset`.
+    pub(crate) fn parse(data: &[u8], page_count: usize, shared_offset: usize) -> Result<Self, String> {
+
+        let mut reader = BitReader { data, position: 0 };
+
+        let least_objects = reader.read(32)?;
+        let first_page_offset = reader.read(32)?;
+        let object_bits = reader.read(16)?;
+        let least_length = reader.read(32)?;
+        let length_bits = reader.read(16)?;
+        let _least_content_offset = reader.read(32)?;
+        let content_offset_bits = reader.read(16)?;
+        let _least_content_length = reader.read(32)?;
+        let content_length_bits = reader.read(16)?;
+        let count_bits = reader.read(16)?;
+        let shared_bits = reader.read(16)?;
+        let numerator_bits = reader.read(16)?;
+        let _denominator = reader.read(16)?;
+
+        let objects = reader.read_all(page_count, object_bits)?;
+        let lengths = reader.read_all(page_count, length_bits)?;
+        let counts = reader.read_all(page_count, count_bits)?;
+
+        let mut pages = Vec::with_capacity(page_count);
+
+        for ((objects, length), count) in objects.into_iter().zip(lengths).zip(counts.iter()) {
+            let shared = (0..*count).map(|_| reader.read(shared_bits)).collect::<Result<Vec<_>, _>>()?;
+            pages.push(PageHint { objects: least_objects.saturating_add(objects), length: least_length.saturating_add(length), shared });
+        }
+
+        reader.align();
+
+        let references = counts.iter().sum::<u32>() as usize;
+        reader.read_all(references, numerator_bits)?;
+        reader.read_all(page_count, content_offset_bits)?;
+        reader.read_all(page_count, content_length_bits)?;
+
+        if shared_offset > data.len() {
+            return Err(format!("The shared object hint table offset {} is beyond the hint data", shared_offset));
+        }
+
+        reader.position = shared_offset * 8;
+
+        let first_shared_object = reader.read(32)?;
+        let first_shared_offset = reader.read(32)?;
+        let first_page_groups = reader.read(32)?;
+        let group_count = reader.read(32)? as usize;
+        let group_object_bits = reader.read(16)?;
+        let least_group_length = reader.read(32)?;
+        let group_length_bits = reader.read(16)?;
+
+        if group_count > data.len() * 8 {
+            return Err(format!("Invalid shared object group count {}", group_count));
+        }
+
+        let group_lengths = reader.read_all(group_count, group_length_bits)?;
+
+        if reader.read_all(group_count, 1)?.contains(&1) {
+            return Err("MD5 signatures of shared object groups are not supported".to_string());
+        }
+
+        let group_objects = reader.read_all(group_count, group_object_bits)?;
+
+        let groups = group_lengths.into_iter()
+            .zip(group_objects)
+            .map(|(length, objects)| (least_group_length.saturating_add(length), objects.saturating_add(1)))
+            .collect();
+
+        Ok(Self { first_page_offset, pages, first_shared_object, first_shared_offset, first_page_groups, groups })
+    }
+}
+
+/// Returns the least value and the number of bits needed for the
+/// difference between the greatest and the least value.
+fn range(values: &[u32]) -> (u32, u32) {
+
+    let least = values.iter().copied().min().unwrap_or(0);
+    let greatest = values.iter().copied().max().unwrap_or(0);
+
+    (least, width(greatest - least))
+}
+
+/// Returns the number of bits needed to represent the value.
+fn width(value: u32) -> u32 {
+    u32::BITS - value.leading_zeros()
+}
+
+/// Reads values from the most significant bit first.
+struct BitReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl BitReader<'_> {
+
+    /// Reads a value of the given width, up to 32 bits.
+    fn read(&mut self, width: u32) -> Result<u32, String> {
+
+        if width > 32 || self.position + width as usize > self.data.len() * 8 {
+            return Err("The hint table is truncated".to_string());
+        }
+
+        let mut value = 0u32;
+
+        for _ in 0..width {
+            let bit = (self.data[self.position / 8] >> (7 - self.position % 8)) & 1;
+            value = (value << 1) | bit as u32;
+            self.position += 1;
+        }
+
+        Ok(value)
+    }
+
+    /// Reads `count` values of the given width, then skips to the next byte.
+    fn read_all(&mut self, count: usize, width: u32) -> Result<Vec<u32>, String> {
+
+        let values = (0..count).map(|_| self.read(width)).collect();
+        self.align();
+
+        values
+    }
+
+    /// Skips to the next byte.
+    fn align(&mut self) {
+        self.position = self.position.div_ceil(8) * 8;
+    }
+}
+
+/// Writes values from the most significant bit first.
+#[derive(Default)]
+struct BitWriter {
+    output: Vec<u8>,
+    buffer: u64,
+    bits: u32,
+}
+
+impl BitWriter {
+
+    /// Writes a value of the given width, up to 32 bits.
+    fn write(&mut self, value: u32, width: u32) {
+
+        if width == 0 {
+            return;
+        }
+
+        self.buffer = (self.buffer << width) | (value as u64 & ((1 << width) - 1));
+        self.bits += width;
+
+        while self.bits >= 8 {
+            self.bits -= 8;
+            self.output.push((self.buffer >> self.bits) as u8);
+        }
+
+        self.buffer &= (1 << self.bits) - 1;
+    }
+
+    /// Pads the last byte with zero bits.
+    fn align(&mut self) {
+
+        if self.bits > 0 {
+            self.output.push((self.buffer << (8 - self.bits)) as u8);
+            self.buffer = 0;
+            self.bits = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HintTables, PageHint};
+
+    #[test]
+    fn should_encode_hint_tables_reversibly() {
+        let tables = HintTables {
+            first_page_offset: 1200,
+            pages: vec![
+                PageHint { objects: 4, length: 900, shared: vec![1] },
+                PageHint { objects: 2, length: 310, shared: vec![1, 4] },
+                PageHint { objects: 1, length: 95, shared: vec![] },
+            ],
+            first_shared_object: 9,
+            first_shared_offset: 2600,
+            first_page_groups: 4,
+            groups: vec![(120, 1), (400, 1), (85, 1), (295, 1), (60, 1)],
+        };
+
+        let (data, offset) = tables.to_bytes();
+
+        assert_eq!(&data[..4], &[0, 0, 0, 1]);
+        assert_eq!(&data[offset..offset + 4], &[0, 0, 0, 9]);
+        assert_eq!(HintTables::parse(&data, 3, offset).unwrap(), tables);
+        assert!(HintTables::parse(&data[..offset], 3, offset).is_err());
+    }
+}
\ No newline at end of file
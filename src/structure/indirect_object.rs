@@ -0,0 +1,77 @@
+use crate::object::{Object, ObjectId};
+use crate::parser::ObjectParser;
+
+/// PDF Indirect Object representation (i.e `1 0 obj ... endobj`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndirectObject {
+    /// Identifier of the object.
+    id: ObjectId,
+    /// The object itself.
+    object: Object,
+    /// Byte representation of the indirect object.
+    bytes: Vec<u8>,
+}
+
+impl IndirectObject {
+
+    /// Creates a new `IndirectObject` with the given identifier and object.
+    pub fn new(id: ObjectId, object: Object) -> Self {
+
+        let mut bytes = format!("{} {} obj\n", id.number(), id.generation()).into_bytes();
+        bytes.extend_from_slice(object.as_bytes());
+        bytes.extend_from_slice(b"\nendobj");
+
+        Self { id, object, bytes }
+    }
+
+    /// Parses an `IndirectObject` from the given bytes.
+    pub fn parse(bytes: &[u8]) -> Result<Self, String> {
+
+        ObjectParser::new(bytes).parse_indirect_object()
+    }
+
+    /// Returns the identifier of the indirect object.
+    pub fn id(&self) -> ObjectId {
+        self.id
+    }
+
+    /// Returns the object.
+    pub fn object(&self) -> &Object {
+        &self.object
+    }
+
+    /// Consumes the indirect object and returns the object.
+    pub fn into_object(self) -> Object {
+        self.object
+    }
+
+    /// Returns the byte representation of the indirect object.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IndirectObject;
+    use crate::object::{Dicionary, DicionaryEntry, Name, Object, ObjectId, Reference};
+
+    #[test]
+    fn should_create_indirect_object_and_return_bytes() {
+        let catalog = Dicionary::new(vec![
+            DicionaryEntry {
+                key: Name::new(b"/Type").unwrap(),
+                value: Object::Name(Name::new(b"/Catalog").unwrap()),
+            },
+            DicionaryEntry {
+                key: Name::new(b"/Pages").unwrap(),
+                value: Object::Reference(Reference::new(ObjectId::new(2, 0))),
+            },
+        ]);
+
+        let indirect_object = IndirectObject::new(ObjectId::new(1, 0), Object::Dicionary(catalog));
+
+        assert_eq!(indirect_object.as_bytes(), b"1 0 obj\n<</Type /Catalog /Pages 2 0 R>>\nendobj");
+        assert_eq!(IndirectObject::parse(indirect_object.as_bytes()).unwrap(), indirect_object);
+    }
+}
\ No newline at end of file
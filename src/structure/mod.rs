@@ -5,9 +5,18 @@ mod header;
 mod version;
 mod byte_marker;
 mod eof;
+mod indirect_object;
+mod hint_table;
+mod object_stream;
+pub mod xref;
 
+#[allow(unused_imports)]
 pub use crate::structure::comment::StructuralComment;
 pub use crate::structure::header::Header;
 pub use crate::structure::version::Version;
+#[allow(unused_imports)]
 pub use crate::structure::byte_marker::ByteMarker;
-pub use crate::structure::eof::EOF;
\ No newline at end of file
+pub use crate::structure::eof::EOF;
+pub use crate::structure::indirect_object::IndirectObject;
+pub use crate::structure::object_stream::ObjectStream;
+pub(crate) use crate::structure::hint_table::{HintTables, PageHint};
\ No newline at end of file
@@ -0,0 +1,178 @@
+use crate::object::{Object, Stream};
+use crate::parser::ObjectParser;
+use crate::token::TokenKind;
+
+/// PDF object stream representation (`/Type /ObjStm`).
+///
+/// It holds the decoded payload of the stream and the position of
+/// each compressed object in it. Compressed objects always have
+/// generation number zero.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjectStream {
+    /// The decoded payload.
+    data: Vec<u8>,
+    /// Object number and position in `data` of each object.
+    objects: Vec<(u32, usize)>,
+}
+
+impl ObjectStream {
+
+    /// Parses an `ObjectStream` from the given stream.
+    ///
+    /// The header of `/N` pairs of object numbers and offsets is read,
+    /// offsets being relative to `/First`.
+    pub fn parse(stream: &Stream) -> Result<Self, String> {
+
+        let dicionary = stream.dicionary();
+
+        if dicionary.get(b"/Type").and_then(Object::as_name).map(|name| name.as_bytes()) != Some(b"/ObjStm") {
+            return Err("Expected an object stream".to_string());
+        }
+
+        let count = dicionary.get(b"/N")
+            .and_then(Object::as_i64)
+            .and_then(|count| usize::try_from(count).ok())
+            .ok_or_else(|| "Invalid /N entry in object stream".to_string())?;
+
+        let first = dicionary.get(b"/First")
+            .and_then(Object::as_i64)
+            .and_then(|first| usize::try_from(first).ok())
+            .ok_or_else(|| "Invalid /First entry in object stream".to_string())?;
+
+        let data = stream.decode()?;
+
+        if first > data.len() {
+            return Err(format!("/First {} is beyond the end of the object stream", first));
+        }
+
+        let mut parser = ObjectParser::new(&data[..first]);
+        let mut objects = Vec::with_capacity(count);
+
+        for _ in 0..count {
+
+            let number = read_header_number(&mut parser)?;
+            let offset = read_header_number(&mut parser)?;
+
+            let position = first.checked_add(offset as usize)
+                .filter(|position| *position <= data.len())
+                .ok_or_else(|| format!("Object {} is beyond the end of the object stream", number))?;
+
+            objects.push((number, position));
+        }
+
+        Ok(Self { data, objects })
+    }
+
+    /// Returns the number of objects in the object stream.
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    /// Returns `true` if the object stream holds no objects.
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+
+    /// Returns the object numbers in the order they are stored.
+    pub fn numbers(&self) -> impl Iterator<Item = u32> + '_ {
+        self.objects.iter().map(|(number, _)| *number)
+    }
+
+    /// Returns the index of the given object number.
+    pub fn find(&self, number: u32) -> Option<usize> {
+        self.objects.iter().position(|(stored, _)| *stored == number)
+    }
+
+    /// Parses the object with the given number, expected at the given index.
+    ///
+    /// The object is searched by number when the index is wrong.
+    pub fn get(&self, number: u32, index: usize) -> Result<Object, String> {
+
+        let index = match self.objects.get(index) {
+            Some((stored, _)) if *stored == number => index,
+            _ => self.find(number)
+                .ok_or_else(|| format!("Object {} is not in the object stream", number))?,
+        };
+
+        Ok(self.object(index)?.1)
+    }
+
+    /// Parses the object at the given index and returns it with its object number.
+    pub fn object(&self, index: usize) -> Result<(u32, Object), String> {
+
+        let (number, position) = *self.objects.get(index)
+            .ok_or_else(|| format!("No object at index {} of the object stream", index))?;
+
+        // An object ends where the next one starts, so that the parser
+        // cannot read into its neighbour.
+        let end = self.objects.iter()
+            .map(|(_, start)| *start)
+            .filter(|start| *start > position)
+            .min()
+            .unwrap_or(self.data.len());
+
+        let object = ObjectParser::new(&self.data[position..end]).parse_object()?;
+
+        if matches!(object, Object::Stream(_)) {
+            return Err(format!("Object {} in an object stream must not be a stream", number));
+        }
+
+        Ok((number, object))
+    }
+}
+
+/// Reads a non-negative integer of the object stream header.
+fn read_header_number(parser: &mut ObjectParser) -> Result<u32, String> {
+
+    match parser.next_token()? {
+        Some(token) => match *token.kind() {
+            TokenKind::Integer(bytes) => bytes.iter()
+                .try_fold(0u32, |value, byte| match byte {
+                    b'0'..=b'9' => value.checked_mul(10)?.checked_add((byte - b'0') as u32),
+                    _ => None,
+                })
+                .ok_or_else(|| format!("Invalid number in object stream header at offset {}", token.offset())),
+            _ => Err(format!("Expected an integer in object stream header at offset {}", token.offset())),
+        },
+        None => Err("Unexpected end of object stream header".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ObjectStream;
+    use crate::object::{Object, Stream};
+
+    fn object_stream(dicionary: &[u8], data: &[u8]) -> Stream {
+        Stream::new(Object::parse(dicionary).unwrap().as_dicionary().unwrap().clone(), data.to_vec())
+    }
+
+    #[test]
+    fn should_extract_objects() {
+        let stream = object_stream(b"<</Type /ObjStm /N 3 /First 14>>", b"4 0 5 8 6 10 \n<</A 1>>\n7\n8 9 R");
+        let object_stream = ObjectStream::parse(&stream).unwrap();
+
+        assert_eq!(object_stream.len(), 3);
+        assert_eq!(object_stream.numbers().collect::<Vec<_>>(), vec![4, 5, 6]);
+        assert_eq!(object_stream.find(6), Some(2));
+
+        let (number, object) = object_stream.object(0).unwrap();
+        assert_eq!(number, 4);
+        assert_eq!(object.as_bytes(), b"<</A 1>>");
+
+        // The integer is not read as the start of the following reference.
+        assert_eq!(object_stream.object(1).unwrap().1.as_i64(), Some(7));
+        assert_eq!(object_stream.object(2).unwrap().1.as_bytes(), b"8 9 R");
+        assert!(object_stream.object(3).is_err());
+
+        assert_eq!(object_stream.get(6, 0).unwrap().as_bytes(), b"8 9 R");
+        assert!(object_stream.get(9, 0).is_err());
+    }
+
+    #[test]
+    fn should_reject_invalid_object_streams() {
+        assert!(ObjectStream::parse(&object_stream(b"<</Type /XRef /N 1 /First 4>>", b"1 0 true")).is_err());
+        assert!(ObjectStream::parse(&object_stream(b"<</Type /ObjStm /N 2 /First 4>>", b"1 0 true")).is_err());
+        assert!(ObjectStream::parse(&object_stream(b"<</Type /ObjStm /N 1 /First 40>>", b"1 0 true")).is_err());
+    }
+}
\ No newline at end of file
@@ -26,6 +26,24 @@ impl Version {
         }
     }
 
+    /// Parses a version from its byte representation (i.e `1.7`).
+    ///
+    /// Returns `None` when the bytes are not a valid PDF version.
+    pub fn parse(bytes: &[u8]) -> Option<Self> {
+
+        match bytes {
+            [major @ b'0'..=b'9', b'.', minor @ b'0'..=b'9'] if is_valid_version(major - b'0', minor - b'0') => {
+                Some(Self::new(major - b'0', minor - b'0'))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if this version is later than the other one.
+    pub fn is_later_than(&self, other: &Version) -> bool {
+        (self.major, self.minor) > (other.major, other.minor)
+    }
+
     /// Returns the major version number.
     pub fn major(&self) -> u8 {
         self.major
@@ -53,4 +71,14 @@ mod tests {
         assert_eq!(version.minor(), 7);
         assert_eq!(version.as_bytes(), b"1.7");
     }
+
+    #[test]
+    fn should_parse_versions() {
+        assert_eq!(Version::parse(b"1.4"), Some(Version::new(1, 4)));
+        assert_eq!(Version::parse(b"2.0"), Some(Version::new(2, 0)));
+        assert_eq!(Version::parse(b"1.8"), None);
+        assert_eq!(Version::parse(b"1.10"), None);
+        assert!(Version::new(2, 0).is_later_than(&Version::new(1, 7)));
+        assert!(!Version::new(1, 4).is_later_than(&Version::new(1, 4)));
+    }
 }
\ No newline at end of file
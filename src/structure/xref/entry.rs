@@ -0,0 +1,88 @@
+/// PDF cross-reference entry representation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum XrefEntry {
+    /// An entry for a free object (`f`).
+    Free {
+        /// Object number of the next free object.
+        next: u32,
+        /// Generation number to use if the object number is reused.
+        generation: u16,
+    },
+    /// An entry for an object in use (`n`).
+    InUse {
+        /// Byte offset of the object from the start of the file.
+        offset: usize,
+        /// Generation number of the object.
+        generation: u16,
+    },
+    /// An entry for an object stored in an object stream (type 2 in a
+    /// cross-reference stream).
+    Compressed {
+        /// Object number of the object stream holding the object.
+        stream: u32,
+        /// Index of the object within the object stream.
+        index: u32,
+    },
+}
+
+impl XrefEntry {
+
+    /// Returns the generation number of the entry.
+    ///
+    /// Objects in object streams always have generation number zero.
+    pub fn generation(&self) -> u16 {
+        match self {
+            XrefEntry::Free { generation, .. } => *generation,
+            XrefEntry::InUse { generation, .. } => *generation,
+            XrefEntry::Compressed { .. } => 0,
+        }
+    }
+
+    /// Returns `true` if the entry is for an object in use.
+    pub fn is_in_use(&self) -> bool {
+        !matches!(self, XrefEntry::Free { .. })
+    }
+
+    /// Returns the 20-byte representation of the entry in a cross-reference table.
+    ///
+    /// A classic table cannot point into object streams, so compressed
+    /// entries are written as free entries, as hybrid-reference files do.
+    pub fn as_bytes(&self) -> [u8; 20] {
+
+        let (value, generation, kind) = match self {
+            XrefEntry::Free { next, generation } => (*next as usize, *generation, 'f'),
+            XrefEntry::InUse { offset, generation } => (*offset, *generation, 'n'),
+            XrefEntry::Compressed { .. } => (0, 0, 'f'),
+        };
+
+        let mut bytes = [0; 20];
+        bytes.copy_from_slice(format!("{:010} {:05} {}\r\n", value, generation, kind).as_bytes());
+
+        bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::XrefEntry;
+
+    #[test]
+    fn should_return_twenty_byte_entries() {
+        let in_use = XrefEntry::InUse { offset: 17, generation: 0 };
+        let free = XrefEntry::Free { next: 0, generation: 65535 };
+
+        assert_eq!(&in_use.as_bytes(), b"0000000017 00000 n\r\n");
+        assert_eq!(&free.as_bytes(), b"0000000000 65535 f\r\n");
+        assert!(in_use.is_in_use());
+        assert!(!free.is_in_use());
+    }
+
+    #[test]
+    fn should_write_compressed_entries_as_free() {
+        let compressed = XrefEntry::Compressed { stream: 7, index: 3 };
+
+        assert_eq!(&compressed.as_bytes(), b"0000000000 00000 f\r\n");
+        assert_eq!(compressed.generation(), 0);
+        assert!(compressed.is_in_use());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,812 @@
+//! This module contains the cross-reference structures of a PDF file.
+mod entry;
+mod repair;
+mod revision;
+mod section;
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+
+use crate::byte_source::ByteSource;
+use crate::object::{Dicionary, DicionaryEntry, Integer, Name, Object, ObjectId, Reference};
+use crate::parser::ObjectParser;
+use crate::parser::search::{find, rfind};
+use crate::structure::xref::repair::scan_objects;
+use crate::structure::ObjectStream;
+use crate::token::TokenKind;
+
+pub use crate::structure::xref::entry::XrefEntry;
+pub use crate::structure::xref::repair::RepairReport;
+pub(crate) use crate::structure::xref::repair::clean_trailer;
+pub use crate::structure::xref::revision::Revision;
+pub use crate::structure::xref::section::XrefSection;
+
+/// Number of bytes at the end of the file searched for `startxref`.
+const STARTXREF_SEARCH_LENGTH: usize = 1024;
+
+/// Number of bytes at the start of the file searched for the header.
+const HEADER_SEARCH_LENGTH: usize = 1024;
+
+/// PDF cross-reference representation.
+///
+/// It holds the cross-reference sections of a file and an index
+/// from `ObjectId` to the byte position of each object in use.
+///
+/// ```rs
+/// let xref = Xref::read(&source)?;
+/// let catalog = xref.read_object(&source, root_id)?;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Xref {
+    /// Offset of the `%PDF-` header. Recorded offsets are relative to it.
+    base: usize,
+    /// Offset given by the last `startxref` keyword.
+    startxref: usize,
+    /// Cross-reference sections.
+    sections: Vec<XrefSection>,
+    /// Entries of all sections by object number.
+    entries: BTreeMap<u32, XrefEntry>,
+    /// The trailer dictionary.
+    trailer: Dicionary,
+    /// Byte positions of the objects in use.
+    index: HashMap<ObjectId, usize>,
+}
+
+impl Xref {
+
+    /// Reads the cross-reference table of the given source.
+    ///
+    /// The table is located through the `startxref` keyword near the
+    /// end of the file, and may be a classic table or a cross-reference
+    /// stream. Older sections of incrementally updated files are read
+    /// by following the `/Prev` entries of the trailers. When data
+    /// precedes the `%PDF-` header, offsets are taken relative to the
+    /// header if they do not match otherwise.
+    pub fn read(source: &Arc<dyn ByteSource>) -> Result<Self, String> {
+
+        let bytes = source.slice(0..source.len());
+
+        let base = find_header(bytes).unwrap_or(0);
+        let startxref = find_startxref(bytes)?;
+
+        let (base, position) = [0, base].into_iter()
+            .map(|base| (base, startxref + base))
+            .find(|(_, position)| {
+                starts_with_keyword(bytes, *position, b"xref") || starts_with_object(bytes, *position)
+            })
+            .ok_or_else(|| format!("No cross-reference table at offset {}", startxref))?;
+
+        let mut sections = vec![read_section(source, base, position, startxref)?];
+        let mut visited = HashSet::from([startxref]);
+
+        while let Some(previous) = sections.last().and_then(|section| section.previous()) {
+
+            // A `/Prev` chain that loops back is cut where it repeats.
+            if !visited.insert(previous) {
+                break;
+            }
+
+            sections.push(read_section(source, base, base + previous, previous)?);
+        }
+
+        Ok(Self::new(base, startxref, sections))
+    }
+
+    /// Reads the cross-reference table of the given source, repairing it when damaged.
+    ///
+    /// A table that cannot be read, or whose entries do not point at
+    /// the objects they describe, is rebuilt with `repair`. The report
+    /// of the repair is returned along with the rebuilt table.
+    pub fn read_or_repair(source: &Arc<dyn ByteSource>) -> Result<(Self, Option<RepairReport>), String> {
+
+        let cause = match Self::read(source) {
+            Ok(xref) => match xref.validate(source) {
+                Ok(()) => return Ok((xref, None)),
+                Err(cause) => cause,
+            },
+            Err(cause) => cause,
+        };
+
+        let (xref, report) = Self::repair(source, cause)?;
+
+        Ok((xref, Some(report)))
+    }
+
+    /// Rebuilds the cross-reference table by scanning the whole source.
+    ///
+    /// Every `N G obj` header is indexed, and objects of the object
+    /// streams found are added as compressed entries. The trailer is
+    /// the last `trailer` dictionary or cross-reference stream whose
+    /// `/Root` can be resolved, or is rebuilt around the last catalog
+    /// found. `cause` is recorded in the report.
+    pub fn repair(source: &Arc<dyn ByteSource>, cause: String) -> Result<(Self, RepairReport), String> {
+
+        let bytes = source.slice(0..source.len());
+        let mut fixes = Vec::new();
+
+        let mut entries = scan_objects(bytes);
+
+        if entries.is_empty() {
+            return Err(format!("No objects found while repairing the file: {}", cause));
+        }
+
+        fixes.push(format!("Rebuilt the object index from {} object headers", entries.len()));
+
+        let scanned = Self::new(0, 0, vec![XrefSection::new(0, entries.clone(), Dicionary::new(vec![]))]);
+
+        let mut trailers = Vec::new();
+        let mut catalogs = Vec::new();
+        let mut compressed = BTreeMap::new();
+
+        for (number, entry) in scanned.entries() {
+
+            let XrefEntry::InUse { offset, generation } = *entry else {
+                continue;
+            };
+
+            let id = ObjectId::new(*number, generation);
+
+            let Ok(object) = scanned.read_object(source, id) else {
+                fixes.push(format!("Skipped unreadable object {} {}", id.number(), id.generation()));
+                continue;
+            };
+
+            let Some(dicionary) = object.as_dicionary() else {
+                continue;
+            };
+
+            match dicionary.get(b"/Type").and_then(Object::as_name).map(|name| name.as_bytes()) {
+                Some(b"/Catalog") => catalogs.push((offset, id)),
+                Some(b"/XRef") => trailers.push((offset, dicionary.clone())),
+                Some(b"/ObjStm") => {
+                    let Ok(object_stream) = scanned.read_object_stream(source, *number) else {
+                        fixes.push(format!("Skipped unreadable object stream {}", number));
+                        continue;
+                    };
+
+                    for (index, stored) in object_stream.numbers().enumerate() {
+                        compressed.insert(stored, XrefEntry::Compressed { stream: *number, index: index as u32 });
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        // Objects found directly in the file take precedence over compressed ones.
+        let recovered = compressed.into_iter()
+            .filter(|(number, _)| !entries.contains_key(number))
+            .collect::<Vec<_>>();
+
+        if !recovered.is_empty() {
+            fixes.push(format!("Recovered {} objects from object streams", recovered.len()));
+            entries.extend(recovered);
+        }
+
+        trailers.extend(find_trailers(bytes));
+        trailers.sort_by_key(|(offset, _)| *offset);
+
+        let objects = entries.len();
+        let scanned = Self::new(0, 0, vec![XrefSection::new(0, entries.clone(), Dicionary::new(vec![]))]);
+
+        let resolves_root = |trailer: &Dicionary| {
+            trailer.get(b"/Root")
+                .and_then(Object::as_reference)
+                .and_then(|root| scanned.read_object(source, root).ok())
+                .is_some_and(|root| root.as_dicionary().is_some())
+        };
+
+        let mut trailer = match trailers.into_iter().rev().find(|(_, trailer)| resolves_root(trailer)) {
+            Some((offset, trailer)) => {
+                fixes.push(format!("Used the trailer found at offset {}", offset));
+                trailer
+            }
+            None => {
+                let (_, root) = catalogs.last()
+                    .ok_or_else(|| format!("No trailer or catalog found while repairing the file: {}", cause))?;
+
+                fixes.push(format!("Rebuilt the trailer around catalog {} {}", root.number(), root.generation()));
+
+                Dicionary::new(vec![DicionaryEntry {
+                    key: Name::new(b"/Root").unwrap(),
+                    value: Object::Reference(Reference::new(*root)),
+                }])
+            }
+        };
+
+        let size = entries.keys().next_back().map_or(0, |number| number.saturating_add(1));
+        clean_trailer(&mut trailer, size);
+
+        let startxref = find_startxref(bytes).unwrap_or(0);
+        let xref = Self::new(0, startxref, vec![XrefSection::new(startxref, entries, trailer)]);
+
+        Ok((xref, RepairReport::new(cause, objects, fixes)))
+    }
+
+    /// Checks that the table describes the file.
+    ///
+    /// Every object in use must start at its recorded offset, and
+    /// the trailer must have a `/Root` entry.
+    pub fn validate(&self, source: &Arc<dyn ByteSource>) -> Result<(), String> {
+
+        if self.trailer.get(b"/Root").and_then(Object::as_reference).is_none() {
+            return Err("The trailer has no /Root entry".to_string());
+        }
+
+        for (id, offset) in &self.index {
+
+            let mut parser = ObjectParser::from_shared_source(source);
+            parser.seek(*offset);
+
+            if parser.parse_object_header().ok() != Some(*id) {
+                return Err(format!(
+                    "Object {} {} is not at offset {}",
+                    id.number(), id.generation(), offset
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Creates a new `Xref` from the given sections, newest first.
+    ///
+    /// `base` is added to every recorded offset.
+    pub fn new(base: usize, startxref: usize, sections: Vec<XrefSection>) -> Self {
+
+        let mut entries = BTreeMap::new();
+
+        // Entries of newer sections take precedence over older ones.
+        for section in sections.iter().rev() {
+            entries.extend(section.entries().iter().map(|(number, entry)| (*number, *entry)));
+        }
+
+        let trailer = sections.first()
+            .map(|section| section.trailer().clone())
+            .unwrap_or_else(|| Dicionary::new(vec![]));
+
+        let index = entries.iter()
+            .filter_map(|(number, entry)| match entry {
+                XrefEntry::InUse { offset, generation } if *number > 0 => {
+                    Some((ObjectId::new(*number, *generation), base + offset))
+                }
+                _ => None,
+            })
+            .collect();
+
+        Self { base, startxref, sections, entries, trailer, index }
+    }
+
+    /// Returns the offset of the `%PDF-` header that offsets are relative to.
+    pub fn base(&self) -> usize {
+        self.base
+    }
+
+    /// Returns the offset given by the last `startxref` keyword.
+    pub fn startxref(&self) -> usize {
+        self.startxref
+    }
+
+    /// Returns the cross-reference sections, newest first.
+    pub fn sections(&self) -> &[XrefSection] {
+        &self.sections
+    }
+
+    /// Returns the entries of all sections by object number.
+    pub fn entries(&self) -> &BTreeMap<u32, XrefEntry> {
+        &self.entries
+    }
+
+    /// Returns the trailer dictionary.
+    pub fn trailer(&self) -> &Dicionary {
+        &self.trailer
+    }
+
+    /// Returns the byte positions of the objects in use.
+    pub fn index(&self) -> &HashMap<ObjectId, usize> {
+        &self.index
+    }
+
+    /// Returns the byte position of the given object.
+    pub fn offset(&self, id: ObjectId) -> Option<usize> {
+        self.index.get(&id).copied()
+    }
+
+    /// Returns the revisions of the file, oldest first.
+    ///
+    /// Each revision ends with the first `%%EOF` marker after its
+    /// cross-reference section and starts where the previous one ends.
+    pub fn revisions(&self, source: &Arc<dyn ByteSource>) -> Vec<Revision> {
+
+        let bytes = source.slice(0..source.len());
+
+        let mut ends = self.sections.iter()
+            .map(|section| {
+                let position = (self.base + section.offset()).min(bytes.len());
+
+                let end = find(&bytes[position..], b"%%EOF")
+                    .map(|eof| skip_end_of_line(bytes, position + eof + b"%%EOF".len()))
+                    .unwrap_or(bytes.len());
+
+                (end, section)
+            })
+            .collect::<Vec<_>>();
+
+        ends.sort_by_key(|(end, _)| *end);
+
+        let mut start = 0;
+
+        ends.into_iter()
+            .map(|(end, section)| {
+                let revision = Revision::new(start..end.max(start), section.offset(), section.entries().clone());
+                start = end.max(start);
+                revision
+            })
+            .collect()
+    }
+
+    /// Returns the cross-reference table as of the given revision.
+    ///
+    /// `index` counts revisions oldest first, as returned by `revisions`.
+    /// Objects are then resolved as they were before later updates.
+    pub fn at_revision(&self, index: usize) -> Option<Self> {
+
+        let newest = self.sections.len().checked_sub(index + 1)?;
+        let sections = self.sections[newest..].to_vec();
+
+        Some(Self::new(self.base, sections[0].offset(), sections))
+    }
+
+    /// Returns the entry of the given object number.
+    pub fn entry(&self, number: u32) -> Option<&XrefEntry> {
+        self.entries.get(&number)
+    }
+
+    /// Reads the given object from the source.
+    ///
+    /// A `/Length` entry of a stream that is an indirect reference
+    /// is resolved through this cross-reference table. Objects stored
+    /// in object streams are extracted from their decoded stream.
+    pub fn read_object(&self, source: &Arc<dyn ByteSource>, id: ObjectId) -> Result<Object, String> {
+
+        if let Some(XrefEntry::Compressed { stream, index }) = self.entry(id.number())
+            && id.generation() == 0
+        {
+            return self.read_object_stream(source, *stream)?.get(id.number(), *index as usize);
+        }
+
+        let offset = self.offset(id)
+            .ok_or_else(|| format!("Object {} {} is not in the cross-reference table", id.number(), id.generation()))?;
+
+        let resolver = |length_id: ObjectId| self.read_length(source, length_id);
+
+        let mut parser = ObjectParser::from_shared_source(source).with_length_resolver(&resolver);
+        parser.seek(offset);
+
+        let object = parser.parse_indirect_object()?;
+
+        if object.id() != id {
+            return Err(format!(
+                "Expected object {} {} at offset {}, found {} {}",
+                id.number(), id.generation(), offset, object.id().number(), object.id().generation()
+            ));
+        }
+
+        Ok(object.into_object())
+    }
+
+    /// Reads and decodes the given object stream.
+    pub fn read_object_stream(&self, source: &Arc<dyn ByteSource>, number: u32) -> Result<ObjectStream, String> {
+
+        // Object streams cannot be stored in other object streams.
+        let Some(XrefEntry::InUse { generation, .. }) = self.entry(number) else {
+            return Err(format!("Object stream {} is not in the cross-reference table", number));
+        };
+
+        match self.read_object(source, ObjectId::new(number, *generation))? {
+            Object::Stream(stream) => ObjectStream::parse(&stream),
+            _ => Err(format!("Object {} is not an object stream", number)),
+        }
+    }
+
+    /// Reads an integer object used as a stream length.
+    fn read_length(&self, source: &Arc<dyn ByteSource>, id: ObjectId) -> Option<usize> {
+
+        let mut parser = ObjectParser::from_shared_source(source);
+        parser.seek(self.offset(id)?);
+
+        let object = parser.parse_indirect_object().ok()?;
+
+        usize::try_from(object.object().as_i64()?).ok()
+    }
+}
+
+/// Returns the offset of the `%PDF-` header near the start of the file.
+pub fn find_header(bytes: &[u8]) -> Option<usize> {
+
+    find(&bytes[..bytes.len().min(HEADER_SEARCH_LENGTH)], b"%PDF-")
+}
+
+/// Returns the offset given by the last `startxref` keyword of the file.
+pub fn find_startxref(bytes: &[u8]) -> Result<usize, String> {
+
+    let tail_start = bytes.len().saturating_sub(STARTXREF_SEARCH_LENGTH);
+
+    // Fall back to the whole file when trailing garbage hides the keyword.
+    let position = rfind(&bytes[tail_start..], b"startxref")
+        .map(|position| tail_start + position)
+        .or_else(|| rfind(bytes, b"startxref"))
+        .ok_or_else(|| "The `startxref` keyword was not found".to_string())?;
+
+    let mut parser = ObjectParser::new(bytes);
+    parser.seek(position + b"startxref".len());
+
+    match parser.next_token()? {
+        Some(token) => match *token.kind() {
+            TokenKind::Integer(bytes) => {
+                let offset = Integer::new(bytes)?.as_i64();
+
+                usize::try_from(offset)
+                    .map_err(|_| format!("Invalid `startxref` offset: {}", offset))
+            }
+            _ => Err(format!("Expected an offset after `startxref` at offset {}", position)),
+        },
+        None => Err("Unexpected end of file after `startxref`".to_string()),
+    }
+}
+
+/// Returns the position after an end-of-line marker at the position, if any.
+fn skip_end_of_line(bytes: &[u8], position: usize) -> usize {
+
+    match bytes.get(position..(position + 2).min(bytes.len())) {
+        Some([b'\r', b'\n']) => position + 2,
+        Some([b'\r' | b'\n', ..]) => position + 1,
+        _ => position,
+    }
+}
+
+/// Reads the cross-reference section at the given position.
+///
+/// For a table whose trailer has an `/XRefStm` entry (a hybrid-reference
+/// file), the entries of that stream are merged into the section.
+fn read_section(source: &Arc<dyn ByteSource>, base: usize, position: usize, offset: usize) -> Result<XrefSection, String> {
+
+    let bytes = source.slice(0..source.len());
+
+    if !starts_with_keyword(bytes, position, b"xref") {
+        return read_stream_section(source, position, offset);
+    }
+
+    let mut section = XrefSection::parse_table(bytes, position, offset)?;
+
+    let stream_offset = section.trailer().get(b"/XRefStm")
+        .and_then(Object::as_i64)
+        .and_then(|offset| usize::try_from(offset).ok());
+
+    if let Some(stream_offset) = stream_offset {
+        section.merge_stream(&read_stream_section(source, base + stream_offset, stream_offset)?);
+    }
+
+    Ok(section)
+}
+
+/// Reads the cross-reference stream at the given position.
+fn read_stream_section(source: &Arc<dyn ByteSource>, position: usize, offset: usize) -> Result<XrefSection, String> {
+
+    let mut parser = ObjectParser::from_shared_source(source);
+    parser.seek(position);
+
+    match parser.parse_indirect_object()?.into_object() {
+        Object::Stream(stream) => XrefSection::parse_stream(&stream, offset),
+        _ => Err(format!("Expected a cross-reference stream at offset {}", offset)),
+    }
+}
+
+/// Returns `true` if an indirect object header starts at the position.
+fn starts_with_object(bytes: &[u8], position: usize) -> bool {
+
+    let mut parser = ObjectParser::new(bytes);
+    parser.seek(position);
+
+    parser.parse_object_header().is_ok()
+}
+
+/// Returns the dictionaries following the `trailer` keywords of the file, with their offsets.
+fn find_trailers(bytes: &[u8]) -> Vec<(usize, Dicionary)> {
+
+    let mut trailers = Vec::new();
+    let mut position = 0;
+
+    while let Some(found) = find(&bytes[position..], b"trailer") {
+
+        let offset = position + found;
+        position = offset + b"trailer".len();
+
+        let mut parser = ObjectParser::new(bytes);
+        parser.seek(position);
+
+        if let Ok(Object::Dicionary(trailer)) = parser.parse_object() {
+            trailers.push((offset, trailer));
+        }
+    }
+
+    trailers
+}
+
+/// Returns `true` if the given keyword starts at the position, after optional whitespace.
+fn starts_with_keyword(bytes: &[u8], position: usize, keyword: &[u8]) -> bool {
+
+    let mut parser = ObjectParser::new(bytes);
+    parser.seek(position);
+
+    match parser.lexer().next_token() {
+        Some(Ok(token)) => token.as_bytes() == keyword,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::io::Write;
+    use std::sync::Arc;
+
+    use flate2::Compression;
+    use flate2::write::ZlibEncoder;
+
+    use super::{find_startxref, Xref, XrefEntry};
+    use crate::byte_source::ByteSource;
+    use crate::byte_source::memory::MemoryByteSource;
+    use crate::object::{Object, ObjectId};
+
+    fn test_pdf() -> Arc<dyn ByteSource> {
+        Arc::new(MemoryByteSource::new(include_bytes!("../../../tests/pdf/test.pdf").to_vec()))
+    }
+
+    fn compress(data: &[u8]) -> Vec<u8> {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn append_stream(pdf: &mut Vec<u8>, header: &str, data: &[u8]) {
+        pdf.extend_from_slice(format!("{} /Length {}>>\nstream\n", header, data.len()).as_bytes());
+        pdf.extend_from_slice(data);
+        pdf.extend_from_slice(b"\nendstream\nendobj\n");
+    }
+
+    /// Appends an incremental update to `test.pdf` that replaces the
+    /// content stream 6 and frees the font widths array 8.
+    pub(crate) fn updated_pdf() -> (Arc<dyn ByteSource>, usize) {
+        let mut pdf = include_bytes!("../../../tests/pdf/test.pdf").to_vec();
+        let original_length = pdf.len();
+        let base = super::find_header(&pdf).unwrap();
+
+        let object_offset = pdf.len() - base;
+        pdf.extend_from_slice(b"6 1 obj\n<</Length 5>>\nstream\nBT ET\nendstream\nendobj\n");
+
+        let table_offset = pdf.len() - base;
+        pdf.extend_from_slice(format!(
+            "xref\n0 1\n0000000005 65535 f\r\n5 2\n0000000008 00001 f\r\n{:010} 00001 n\r\n\
+            8 1\n0000000000 00001 f\r\ntrailer\n<</Size 10 /Root 1 0 R /Prev 4248>>\n\
+            startxref\n{}\n%%EOF\n",
+            object_offset, table_offset
+        ).as_bytes());
+
+        (Arc::new(MemoryByteSource::new(pdf)), original_length)
+    }
+
+    /// Builds a PDF 1.5 file whose catalog and page tree live in object
+    /// stream 3, indexed by the cross-reference stream 4. A hybrid file
+    /// also has a classic table that only lists the uncompressed objects.
+    pub(crate) fn compressed_pdf(hybrid: bool) -> Arc<dyn ByteSource> {
+        let mut pdf = b"%PDF-1.5\n".to_vec();
+
+        let objects = b"1 0 2 32 <</Type /Catalog /Pages 2 0 R>> <</Type /Pages /Kids [] /Count 0>>";
+        let object_stream_offset = pdf.len();
+        append_stream(&mut pdf, "3 0 obj\n<</Type /ObjStm /N 2 /First 9 /Filter /FlateDecode", &compress(objects));
+
+        let xref_stream_offset = pdf.len();
+        let mut records = vec![0, 0, 0, 255];
+        records.extend_from_slice(&[2, 0, 3, 0, 2, 0, 3, 1]);
+        records.extend_from_slice(&[1, (object_stream_offset >> 8) as u8, object_stream_offset as u8, 0]);
+        records.extend_from_slice(&[1, (xref_stream_offset >> 8) as u8, xref_stream_offset as u8, 0]);
+        append_stream(
+            &mut pdf,
+            "4 0 obj\n<</Type /XRef /Size 5 /W [1 2 1] /Root 1 0 R /Filter /FlateDecode",
+            &compress(&records),
+        );
+
+        let startxref = if hybrid {
+            let table_offset = pdf.len();
+            pdf.extend_from_slice(format!(
+                "xref\n0 1\n0000000000 65535 f\r\n3 2\n{:010} 00000 n\r\n{:010} 00000 n\r\n\
+                trailer\n<</Size 5 /Root 1 0 R /XRefStm {}>>\n",
+                object_stream_offset, xref_stream_offset, xref_stream_offset
+            ).as_bytes());
+            table_offset
+        } else {
+            xref_stream_offset
+        };
+
+        pdf.extend_from_slice(format!("startxref\n{}\n%%EOF\n", startxref).as_bytes());
+
+        Arc::new(MemoryByteSource::new(pdf))
+    }
+
+    #[test]
+    fn should_find_startxref() {
+        assert_eq!(find_startxref(b"...startxref\n1234\n%%EOF\n").unwrap(), 1234);
+        assert!(find_startxref(b"...startxref\n%%EOF").is_err());
+        assert!(find_startxref(b"no keyword").is_err());
+    }
+
+    #[test]
+    fn should_read_xref_and_trailer() {
+        let source = test_pdf();
+        let xref = Xref::read(&source).unwrap();
+
+        assert!(xref.base() > 0);
+        assert_eq!(xref.startxref(), 4248);
+        assert_eq!(xref.trailer().get(b"/Size").unwrap().as_i64(), Some(10));
+        assert_eq!(xref.trailer().get(b"/Root").unwrap().as_reference(), Some(ObjectId::new(1, 0)));
+        assert_eq!(xref.index().len(), 8);
+        assert_eq!(xref.offset(ObjectId::new(1, 0)), Some(xref.base() + 20));
+        assert_eq!(xref.offset(ObjectId::new(5, 0)), None);
+    }
+
+    #[test]
+    fn should_read_objects_through_index() {
+        let source = test_pdf();
+        let xref = Xref::read(&source).unwrap();
+
+        let catalog = xref.read_object(&source, ObjectId::new(1, 0)).unwrap();
+        assert_eq!(catalog.as_dicionary().unwrap().get(b"/Type").unwrap().as_bytes(), b"/Catalog");
+
+        let Object::Stream(content) = xref.read_object(&source, ObjectId::new(6, 0)).unwrap() else {
+            panic!("Expected a stream");
+        };
+
+        assert!(content.data().as_bytes().starts_with(b"BT"));
+        assert!(xref.read_object(&source, ObjectId::new(5, 0)).is_err());
+    }
+
+    #[test]
+    fn should_read_objects_from_object_streams() {
+        for hybrid in [false, true] {
+            let source = compressed_pdf(hybrid);
+            let xref = Xref::read(&source).unwrap();
+
+            assert_eq!(xref.entry(1), Some(&XrefEntry::Compressed { stream: 3, index: 0 }));
+            assert_eq!(xref.trailer().get(b"/Root").unwrap().as_reference(), Some(ObjectId::new(1, 0)));
+
+            let catalog = xref.read_object(&source, ObjectId::new(1, 0)).unwrap();
+            assert_eq!(catalog.as_dicionary().unwrap().get(b"/Type").unwrap().as_bytes(), b"/Catalog");
+
+            let pages = xref.read_object(&source, ObjectId::new(2, 0)).unwrap();
+            assert_eq!(pages.as_dicionary().unwrap().get(b"/Count").unwrap().as_i64(), Some(0));
+
+            assert_eq!(xref.read_object_stream(&source, 3).unwrap().len(), 2);
+            assert!(xref.read_object(&source, ObjectId::new(1, 1)).is_err());
+            assert!(xref.read_object_stream(&source, 1).is_err());
+        }
+    }
+
+    #[test]
+    fn should_follow_prev_through_incremental_updates() {
+        let (source, _) = updated_pdf();
+        let xref = Xref::read(&source).unwrap();
+
+        assert_eq!(xref.sections().len(), 2);
+        assert_eq!(xref.sections()[1].offset(), 4248);
+        assert_eq!(xref.offset(ObjectId::new(6, 0)), None);
+        assert!(xref.offset(ObjectId::new(6, 1)).is_some());
+        assert!(!xref.entry(8).unwrap().is_in_use());
+        assert!(xref.entry(1).unwrap().is_in_use());
+
+        let Object::Stream(content) = xref.read_object(&source, ObjectId::new(6, 1)).unwrap() else {
+            panic!("Expected a stream");
+        };
+        assert_eq!(content.data().as_bytes(), b"BT ET");
+    }
+
+    #[test]
+    fn should_list_revisions_with_ranges_and_changes() {
+        let (source, original_length) = updated_pdf();
+        let xref = Xref::read(&source).unwrap();
+        let revisions = xref.revisions(&source);
+
+        assert_eq!(revisions.len(), 2);
+        assert_eq!(revisions[0].range(), 0..original_length);
+        assert_eq!(revisions[1].range(), original_length..source.len());
+        assert_eq!(revisions[0].changed_objects().count(), 9);
+        assert_eq!(revisions[1].changed_objects().collect::<Vec<_>>(), vec![5, 6, 8]);
+        assert_eq!(revisions[1].freed_objects().collect::<Vec<_>>(), vec![5, 8]);
+
+        // The replaced content still exists in the original revision.
+        let original = xref.at_revision(0).unwrap();
+        let Object::Stream(content) = original.read_object(&source, ObjectId::new(6, 0)).unwrap() else {
+            panic!("Expected a stream");
+        };
+        assert!(content.data().as_bytes().starts_with(b"BT"));
+        assert!(xref.at_revision(2).is_none());
+    }
+
+    #[test]
+    fn should_stop_at_looping_prev_chains() {
+        let mut pdf = b"%PDF-1.4\n".to_vec();
+        let table_offset = pdf.len();
+        pdf.extend_from_slice(format!(
+            "xref\n0 1\n0000000000 65535 f\r\ntrailer\n<</Size 1 /Prev {}>>\nstartxref\n{}\n%%EOF",
+            table_offset, table_offset
+        ).as_bytes());
+
+        let source: Arc<dyn ByteSource> = Arc::new(MemoryByteSource::new(pdf));
+        assert_eq!(Xref::read(&source).unwrap().sections().len(), 1);
+    }
+
+    #[test]
+    fn should_not_repair_valid_files() {
+        let (xref, report) = Xref::read_or_repair(&test_pdf()).unwrap();
+
+        assert!(report.is_none());
+        assert_eq!(xref.startxref(), 4248);
+    }
+
+    #[test]
+    fn should_repair_wrong_offsets() {
+        let mut pdf = include_bytes!("../../../tests/pdf/test.pdf").to_vec();
+        let position = pdf.len() - b"4248\r\n%%EOF\r\n".len();
+        pdf[position..position + 4].copy_from_slice(b"4000");
+
+        let source: Arc<dyn ByteSource> = Arc::new(MemoryByteSource::new(pdf));
+        let (xref, report) = Xref::read_or_repair(&source).unwrap();
+        let report = report.unwrap();
+
+        assert_eq!(report.objects(), 8);
+        assert!(report.fixes().iter().any(|fix| fix.starts_with("Used the trailer")));
+        assert_eq!(xref.trailer().get(b"/Size").unwrap().as_i64(), Some(10));
+        assert!(xref.trailer().get(b"/ID").is_some());
+
+        let catalog = xref.read_object(&source, ObjectId::new(1, 0)).unwrap();
+        assert_eq!(catalog.as_dicionary().unwrap().get(b"/Type").unwrap().as_bytes(), b"/Catalog");
+    }
+
+    #[test]
+    fn should_repair_truncated_files_around_the_catalog() {
+        let pdf = include_bytes!("../../../tests/pdf/test.pdf");
+        let source: Arc<dyn ByteSource> = Arc::new(MemoryByteSource::new(pdf[..4100].to_vec()));
+
+        let (xref, report) = Xref::read_or_repair(&source).unwrap();
+        let report = report.unwrap();
+
+        assert!(report.cause().contains("startxref"));
+        assert!(report.fixes().iter().any(|fix| fix == "Rebuilt the trailer around catalog 1 0"));
+        assert_eq!(xref.trailer().get(b"/Root").unwrap().as_reference(), Some(ObjectId::new(1, 0)));
+
+        let Object::Stream(content) = xref.read_object(&source, ObjectId::new(6, 0)).unwrap() else {
+            panic!("Expected a stream");
+        };
+        assert!(content.data().as_bytes().starts_with(b"BT"));
+    }
+
+    #[test]
+    fn should_repair_compressed_files() {
+        let source = compressed_pdf(false);
+        let bytes = source.slice(0..source.len());
+        let mut pdf = bytes[..bytes.len() - b"%%EOF\n".len()].to_vec();
+        pdf.extend_from_slice(b"garbage");
+
+        let source: Arc<dyn ByteSource> = Arc::new(MemoryByteSource::new(pdf));
+        let (xref, report) = Xref::repair(&source, "test".to_string()).unwrap();
+
+        assert_eq!(report.cause(), "test");
+        assert_eq!(report.objects(), 4);
+        assert_eq!(xref.entry(2), Some(&XrefEntry::Compressed { stream: 3, index: 1 }));
+        assert_eq!(xref.trailer().get(b"/W"), None);
+
+        let pages = xref.read_object(&source, ObjectId::new(2, 0)).unwrap();
+        assert_eq!(pages.as_dicionary().unwrap().get(b"/Type").unwrap().as_bytes(), b"/Pages");
+
+        let empty: Arc<dyn ByteSource> = Arc::new(MemoryByteSource::new(b"%PDF-1.7".to_vec()));
+        assert!(Xref::repair(&empty, String::new()).is_err());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,129 @@
+use std::collections::BTreeMap;
+
+use crate::object::{Dicionary, Integer, Name, Object};
+use crate::parser::{ObjectParser, is_regular, is_whitespace};
+use crate::parser::search::find;
+use crate::structure::xref::XrefEntry;
+
+/// Report of a cross-reference table rebuilt by scanning a damaged file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepairReport {
+    /// Why the cross-reference table of the file could not be used.
+    cause: String,
+    /// Number of objects found by scanning.
+    objects: usize,
+    /// Descriptions of the fixes applied, in order.
+    fixes: Vec<String>,
+}
+
+impl RepairReport {
+
+    /// Creates a new `RepairReport`.
+    pub fn new(cause: String, objects: usize, fixes: Vec<String>) -> Self {
+        Self { cause, objects, fixes }
+    }
+
+    /// Returns why the cross-reference table of the file could not be used.
+    pub fn cause(&self) -> &str {
+        &self.cause
+    }
+
+    /// Returns the number of objects found by scanning.
+    pub fn objects(&self) -> usize {
+        self.objects
+    }
+
+    /// Returns descriptions of the fixes applied, in order.
+    pub fn fixes(&self) -> &[String] {
+        &self.fixes
+    }
+}
+
+/// Scans the bytes for indirect object headers (`N G obj`).
+///
+/// Offsets are absolute. When an object number appears more than
+/// once, the last occurrence wins, as incremental updates append
+/// newer versions at the end of the file.
+pub fn scan_objects(bytes: &[u8]) -> BTreeMap<u32, XrefEntry> {
+
+    let mut entries = BTreeMap::new();
+    let mut position = 0;
+
+    while let Some(found) = find(&bytes[position..], b"obj") {
+
+        let keyword = position + found;
+        position = keyword + b"obj".len();
+
+        // Skip keywords such as `objstm` that merely start with `obj`.
+        if bytes.get(position).is_some_and(|byte| is_regular(*byte)) {
+            continue;
+        }
+
+        let Some(start) = header_start(bytes, keyword) else {
+            continue;
+        };
+
+        let mut parser = ObjectParser::new(bytes);
+        parser.seek(start);
+
+        if let Ok(id) = parser.parse_object_header() {
+            entries.insert(id.number(), XrefEntry::InUse { offset: start, generation: id.generation() });
+        }
+    }
+
+    entries
+}
+
+/// Returns the start of the `N G` numbers preceding an `obj` keyword.
+fn header_start(bytes: &[u8], keyword: usize) -> Option<usize> {
+
+    let mut position = keyword;
+    let is_digit = |byte: u8| byte.is_ascii_digit();
+
+    for predicate in [is_whitespace, is_digit, is_whitespace, is_digit] {
+
+        let end = position;
+
+        while position > 0 && predicate(bytes[position - 1]) {
+            position -= 1;
+        }
+
+        if position == end {
+            return None;
+        }
+    }
+
+    match position {
+        0 => Some(0),
+        _ if is_regular(bytes[position - 1]) => None,
+        _ => Some(position),
+    }
+}
+
+/// Removes the entries that only describe the damaged cross-reference
+/// data from a recovered trailer, and sets its `/Size`.
+pub fn clean_trailer(trailer: &mut Dicionary, size: u32) {
+
+    for key in [b"/Prev".as_slice(), b"/XRefStm", b"/Type", b"/W", b"/Index", b"/Filter", b"/DecodeParms", b"/Length"] {
+        trailer.remove(key);
+    }
+
+    trailer.insert(Name::new(b"/Size").unwrap(), Object::Integer(Integer::from_value(size as i64)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::scan_objects;
+    use crate::structure::xref::XrefEntry;
+
+    #[test]
+    fn should_scan_object_headers() {
+        let bytes = b"1 0 obj\n<<>>\nendobj\n 2 3 obj[]endobj x1 0 obj 4 0 objstm 12 0 obj (old) endobj 12 1 obj (new)";
+        let entries = scan_objects(bytes);
+
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[&1], XrefEntry::InUse { offset: 0, generation: 0 });
+        assert_eq!(entries[&2], XrefEntry::InUse { offset: 21, generation: 3 });
+        assert_eq!(entries[&12], XrefEntry::InUse { offset: 79, generation: 1 });
+    }
+}
\ No newline at end of file
@@ -0,0 +1,55 @@
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use crate::structure::xref::XrefEntry;
+
+/// A revision of a PDF file: the original file or one incremental update.
+///
+/// Content replaced or freed by a later revision still exists in the
+/// byte range of the revision that introduced it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Revision {
+    /// Byte range of the revision in the file, up to and including its `%%EOF` marker.
+    range: Range<usize>,
+    /// Byte offset of the revision's cross-reference section as recorded in the file.
+    xref_offset: usize,
+    /// Entries of the revision's cross-reference section by object number.
+    entries: BTreeMap<u32, XrefEntry>,
+}
+
+impl Revision {
+
+    /// Creates a new `Revision`.
+    pub fn new(range: Range<usize>, xref_offset: usize, entries: BTreeMap<u32, XrefEntry>) -> Self {
+        Self { range, xref_offset, entries }
+    }
+
+    /// Returns the byte range of the revision in the file.
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+
+    /// Returns the byte offset of the revision's cross-reference section.
+    pub fn xref_offset(&self) -> usize {
+        self.xref_offset
+    }
+
+    /// Returns the entries of the revision's cross-reference section.
+    pub fn entries(&self) -> &BTreeMap<u32, XrefEntry> {
+        &self.entries
+    }
+
+    /// Returns the numbers of the objects the revision added, changed or freed.
+    ///
+    /// Object number 0, the head of the free list, is not included.
+    pub fn changed_objects(&self) -> impl Iterator<Item = u32> + '_ {
+        self.entries.keys().copied().filter(|number| *number > 0)
+    }
+
+    /// Returns the numbers of the objects the revision freed.
+    pub fn freed_objects(&self) -> impl Iterator<Item = u32> + '_ {
+        self.entries.iter()
+            .filter(|(number, entry)| **number > 0 && !entry.is_in_use())
+            .map(|(number, _)| *number)
+    }
+}
\ No newline at end of file
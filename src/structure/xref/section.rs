@@ -0,0 +1,332 @@
+use std::collections::BTreeMap;
+
+use crate::object::{Dicionary, Integer, Object, Stream};
+use crate::parser::ObjectParser;
+use crate::token::{Keyword, TokenKind};
+use crate::structure::xref::XrefEntry;
+
+/// PDF cross-reference section representation.
+///
+/// A section is either a classic `xref` table followed by its
+/// `trailer` dictionary, or a cross-reference stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XrefSection {
+    /// Byte offset of the section as given by `startxref` or `/Prev`.
+    offset: usize,
+    /// Entries of the section by object number.
+    entries: BTreeMap<u32, XrefEntry>,
+    /// The trailer dictionary of the section.
+    trailer: Dicionary,
+}
+
+impl XrefSection {
+
+    /// Creates a new `XrefSection`.
+    pub fn new(offset: usize, entries: BTreeMap<u32, XrefEntry>, trailer: Dicionary) -> Self {
+        Self { offset, entries, trailer }
+    }
+
+    /// Parses a classic cross-reference table and its trailer.
+    ///
+    /// `position` is where the `xref` keyword starts in `bytes`, and `offset`
+    /// is the offset recorded for the section. Entries are read token by
+    /// token, so tables whose entries are not exactly 20 bytes long (a
+    /// common writer bug) are accepted as well.
+    pub fn parse_table(bytes: &[u8], position: usize, offset: usize) -> Result<Self, String> {
+
+        let mut parser = ObjectParser::new(bytes);
+        parser.seek(position);
+
+        match parser.next_token()? {
+            Some(token) if token.is_keyword(Keyword::Xref) => {}
+            _ => return Err(format!("Expected `xref` keyword at offset {}", position)),
+        }
+
+        let mut entries = BTreeMap::new();
+
+        loop {
+            let token = parser.next_token()?
+                .ok_or_else(|| "Unexpected end of cross-reference table".to_string())?;
+
+            if token.is_keyword(Keyword::Trailer) {
+                break;
+            }
+
+            let TokenKind::Integer(first) = *token.kind() else {
+                return Err(format!("Expected a subsection header at offset {}", token.offset()));
+            };
+
+            let first = parse_u32(first)?;
+            let count = parse_u32(expect_integer(&mut parser)?)?;
+
+            for index in 0..count {
+
+                let value = expect_integer(&mut parser)?;
+                let generation = expect_integer(&mut parser)?;
+
+                let token = parser.next_token()?
+                    .ok_or_else(|| "Unexpected end of cross-reference table".to_string())?;
+
+                let generation = u16::try_from(Integer::new(generation)?.as_i64())
+                    .map_err(|_| format!("Invalid generation number at offset {}", token.offset()))?;
+
+                let entry = match token.kind() {
+                    TokenKind::Keyword(Keyword::Other(b"n")) => XrefEntry::InUse {
+                        offset: usize::try_from(Integer::new(value)?.as_i64())
+                            .map_err(|_| format!("Invalid object offset at offset {}", token.offset()))?,
+                        generation,
+                    },
+                    TokenKind::Keyword(Keyword::Other(b"f")) => XrefEntry::Free {
+                        next: parse_u32(value)?,
+                        generation,
+                    },
+                    _ => return Err(format!("Invalid cross-reference entry type at offset {}", token.offset())),
+                };
+
+                let number = first.checked_add(index)
+                    .ok_or_else(|| "Object number overflows in cross-reference table".to_string())?;
+
+                entries.insert(number, entry);
+            }
+        }
+
+        let trailer = match parser.parse_object()? {
+            Object::Dicionary(dicionary) => dicionary,
+            _ => return Err("Trailer must be a dictionary".to_string()),
+        };
+
+        Ok(Self::new(offset, entries, trailer))
+    }
+
+    /// Parses a cross-reference stream (`/Type /XRef`).
+    ///
+    /// Entries are decoded with the field widths of `/W` for the object
+    /// number ranges of `/Index`. The stream dictionary doubles as the
+    /// trailer of the section.
+    pub fn parse_stream(stream: &Stream, offset: usize) -> Result<Self, String> {
+
+        let dicionary = stream.dicionary();
+
+        if dicionary.get(b"/Type").and_then(Object::as_name).map(|name| name.as_bytes()) != Some(b"/XRef") {
+            return Err(format!("Expected a cross-reference stream at offset {}", offset));
+        }
+
+        let widths = dicionary.get(b"/W")
+            .and_then(Object::as_array)
+            .map(|array| array.as_objects().iter()
+                .map(|width| width.as_i64().and_then(|width| usize::try_from(width).ok()).filter(|width| *width <= 8))
+                .collect::<Option<Vec<_>>>())
+            .and_then(|widths| widths.filter(|widths| widths.len() == 3))
+            .ok_or_else(|| "Invalid /W entry in cross-reference stream".to_string())?;
+
+        let record_length: usize = widths.iter().sum();
+
+        if record_length == 0 {
+            return Err("Empty records in cross-reference stream".to_string());
+        }
+
+        let size = dicionary.get(b"/Size")
+            .and_then(Object::as_i64)
+            .and_then(|size| u32::try_from(size).ok())
+            .ok_or_else(|| "Invalid /Size entry in cross-reference stream".to_string())?;
+
+        let ranges = match dicionary.get(b"/Index") {
+            Some(index) => index.as_array()
+                .map(|array| array.as_objects().iter()
+                    .map(|value| value.as_i64().and_then(|value| u32::try_from(value).ok()))
+                    .collect::<Option<Vec<_>>>())
+                .and_then(|values| values.filter(|values| values.len() % 2 == 0))
+                .map(|values| values.chunks(2).map(|pair| (pair[0], pair[1])).collect())
+                .ok_or_else(|| "Invalid /Index entry in cross-reference stream".to_string())?,
+            None => vec![(0, size)],
+        };
+
+        let data = stream.decode()?;
+        let mut records = data.chunks_exact(record_length);
+        let mut entries = BTreeMap::new();
+
+        for (first, count) in ranges {
+            for index in 0..count {
+
+                let record = records.next()
+                    .ok_or_else(|| "Unexpected end of cross-reference stream".to_string())?;
+
+                let (kind, record) = record.split_at(widths[0]);
+                let (second, third) = record.split_at(widths[1]);
+
+                // The type defaults to 1 when its field is absent.
+                let kind = if widths[0] == 0 { 1 } else { read_field(kind) };
+                let second = read_field(second);
+                let third = read_field(third);
+
+                let entry = match kind {
+                    0 => XrefEntry::Free {
+                        next: field_to(second)?,
+                        generation: field_to(third)?,
+                    },
+                    1 => XrefEntry::InUse {
+                        offset: field_to(second)?,
+                        generation: field_to(third)?,
+                    },
+                    2 => XrefEntry::Compressed {
+                        stream: field_to(second)?,
+                        index: field_to(third)?,
+                    },
+                    // Unknown types are references to the null object.
+                    _ => continue,
+                };
+
+                let number = first.checked_add(index)
+                    .ok_or_else(|| "Object number overflows in cross-reference stream".to_string())?;
+
+                entries.insert(number, entry);
+            }
+        }
+
+        Ok(Self::new(offset, entries, dicionary.clone()))
+    }
+
+    /// Adds the entries of a hybrid-reference file's cross-reference stream
+    /// (`/XRefStm`) to this section.
+    ///
+    /// Entries of the table take precedence, except free ones, which
+    /// hide objects from readers that only understand tables.
+    pub fn merge_stream(&mut self, stream: &XrefSection) {
+
+        for (number, entry) in stream.entries() {
+            match self.entries.get(number) {
+                Some(existing) if existing.is_in_use() => {}
+                _ => {
+                    self.entries.insert(*number, *entry);
+                }
+            }
+        }
+    }
+
+    /// Returns the byte offset of the section.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the entries of the section by object number.
+    pub fn entries(&self) -> &BTreeMap<u32, XrefEntry> {
+        &self.entries
+    }
+
+    /// Returns the trailer dictionary of the section.
+    pub fn trailer(&self) -> &Dicionary {
+        &self.trailer
+    }
+
+    /// Returns the offset of the previous section given by `/Prev`, if any.
+    pub fn previous(&self) -> Option<usize> {
+        self.trailer.get(b"/Prev")
+            .and_then(Object::as_i64)
+            .and_then(|offset| usize::try_from(offset).ok())
+    }
+}
+
+/// Reads the next token, which must be an integer.
+fn expect_integer<'a>(parser: &mut ObjectParser<'a>) -> Result<&'a [u8], String> {
+
+    match parser.next_token()? {
+        Some(token) => match *token.kind() {
+            TokenKind::Integer(bytes) => Ok(bytes),
+            _ => Err(format!("Expected an integer at offset {}", token.offset())),
+        },
+        None => Err("Unexpected end of cross-reference table".to_string()),
+    }
+}
+
+/// Reads a big-endian field of a cross-reference stream record.
+fn read_field(bytes: &[u8]) -> u64 {
+
+    bytes.iter().fold(0, |value, byte| (value << 8) | *byte as u64)
+}
+
+/// Converts a cross-reference stream field to the entry's integer type.
+fn field_to<T: TryFrom<u64>>(value: u64) -> Result<T, String> {
+
+    T::try_from(value).map_err(|_| format!("Field value out of range in cross-reference stream: {}", value))
+}
+
+/// Parses integer bytes as a `u32`.
+fn parse_u32(bytes: &[u8]) -> Result<u32, String> {
+
+    u32::try_from(Integer::new(bytes)?.as_i64())
+        .map_err(|_| format!("Invalid number in cross-reference table: {:?}", String::from_utf8_lossy(bytes)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::XrefSection;
+    use crate::object::{Object, Stream};
+    use crate::structure::xref::XrefEntry;
+
+    fn xref_stream(dicionary: &[u8], data: Vec<u8>) -> Stream {
+        Stream::new(Object::parse(dicionary).unwrap().as_dicionary().unwrap().clone(), data)
+    }
+
+    #[test]
+    fn should_parse_classic_table() {
+        let bytes = b"xref\n0 2\n0000000000 65535 f\r\n0000000015 00000 n\r\n5 1\n0000000099 00002 n \ntrailer\n<</Size 6>>";
+        let section = XrefSection::parse_table(bytes, 0, 0).unwrap();
+
+        assert_eq!(section.entries().len(), 3);
+        assert_eq!(section.entries()[&0], XrefEntry::Free { next: 0, generation: 65535 });
+        assert_eq!(section.entries()[&1], XrefEntry::InUse { offset: 15, generation: 0 });
+        assert_eq!(section.entries()[&5], XrefEntry::InUse { offset: 99, generation: 2 });
+        assert_eq!(section.trailer().as_bytes(), b"<</Size 6>>");
+    }
+
+    #[test]
+    fn should_error_on_malformed_table() {
+        assert!(XrefSection::parse_table(b"xref\n0 2\n0000000000 65535 f\r\ntrailer<<>>", 0, 0).is_err());
+        assert!(XrefSection::parse_table(b"xref\n0 1\n0000000000 65535 x\r\ntrailer<<>>", 0, 0).is_err());
+        assert!(XrefSection::parse_table(b"0 1 obj", 0, 0).is_err());
+    }
+
+    #[test]
+    fn should_parse_xref_stream_with_widths_and_index() {
+        let data = vec![
+            0, 0, 0, 255, 255,
+            1, 0, 15, 0, 0,
+            2, 0, 9, 0, 3,
+            1, 1, 0, 0, 2,
+        ];
+        let stream = xref_stream(b"<</Type /XRef /Size 12 /W [1 2 2] /Index [0 3 10 1]>>", data);
+        let section = XrefSection::parse_stream(&stream, 40).unwrap();
+
+        assert_eq!(section.offset(), 40);
+        assert_eq!(section.entries().len(), 4);
+        assert_eq!(section.entries()[&0], XrefEntry::Free { next: 0, generation: 65535 });
+        assert_eq!(section.entries()[&1], XrefEntry::InUse { offset: 15, generation: 0 });
+        assert_eq!(section.entries()[&2], XrefEntry::Compressed { stream: 9, index: 3 });
+        assert_eq!(section.entries()[&10], XrefEntry::InUse { offset: 256, generation: 2 });
+        assert_eq!(section.trailer().get(b"/Size").unwrap().as_i64(), Some(12));
+    }
+
+    #[test]
+    fn should_default_type_field_and_reject_invalid_streams() {
+        let stream = xref_stream(b"<</Type /XRef /Size 2 /W [0 1 0]>>", vec![7, 9]);
+        let section = XrefSection::parse_stream(&stream, 0).unwrap();
+
+        assert_eq!(section.entries()[&1], XrefEntry::InUse { offset: 9, generation: 0 });
+
+        assert!(XrefSection::parse_stream(&xref_stream(b"<</Type /XRef /Size 2 /W [0 1 0]>>", vec![7]), 0).is_err());
+        assert!(XrefSection::parse_stream(&xref_stream(b"<</Type /XRef /Size 1 /W [1 2]>>", vec![0, 0, 0]), 0).is_err());
+        assert!(XrefSection::parse_stream(&xref_stream(b"<</Type /ObjStm /Size 1 /W [1 1 1]>>", vec![0, 0, 0]), 0).is_err());
+    }
+
+    #[test]
+    fn should_merge_hybrid_stream_entries() {
+        let mut table = XrefSection::parse_table(b"xref\n0 3\n0000000000 65535 f\r\n0000000015 00000 n\r\n0000000000 00000 f\r\ntrailer\n<<>>", 0, 0).unwrap();
+        let stream = xref_stream(b"<</Type /XRef /Size 4 /W [1 1 1]>>", vec![0, 0, 0, 1, 99, 0, 2, 3, 0, 2, 3, 1]);
+
+        table.merge_stream(&XrefSection::parse_stream(&stream, 0).unwrap());
+
+        assert_eq!(table.entries()[&1], XrefEntry::InUse { offset: 15, generation: 0 });
+        assert_eq!(table.entries()[&2], XrefEntry::Compressed { stream: 3, index: 0 });
+        assert_eq!(table.entries()[&3], XrefEntry::Compressed { stream: 3, index: 1 });
+    }
+}
\ No newline at end of file
@@ -1,7 +1,5 @@
-use std::ops::Deref;
-
 /// PDF Delimiter characters.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Delimiter {
     /// Indicates the start of a PDF string.
     /// 
@@ -46,6 +44,23 @@ pub enum Delimiter {
 }
 
 impl Delimiter {
+
+    /// Returns the `Delimiter` represented by the given byte, if any.
+    pub fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            b'(' => Some(Delimiter::LeftParen),
+            b')' => Some(Delimiter::RightParen),
+            b'<' => Some(Delimiter::LeftAngle),
+            b'>' => Some(Delimiter::RightAngle),
+            b'[' => Some(Delimiter::LeftSquare),
+            b']' => Some(Delimiter::RightSquare),
+            b'{' => Some(Delimiter::LeftCurlyBracket),
+            b'}' => Some(Delimiter::RightCurlyBracket),
+            b'/' => Some(Delimiter::Solidus),
+            b'%' => Some(Delimiter::PercentSign),
+            _ => None,
+        }
+    }
     
     /// Returns the byte representation of the Delimiter.
     pub fn as_byte(&self) -> &u8 {
@@ -71,6 +86,7 @@ impl PartialEq<u8> for Delimiter {
     }
 }
 
+#[cfg(test)]
 mod tests {
     use super::Delimiter;
 
@@ -87,4 +103,14 @@ mod tests {
         assert_eq!(Delimiter::RightParen, b')');
         assert_eq!(Delimiter::LeftCurlyBracket, b'{');
     }
+
+    #[test]
+    fn should_create_from_byte() {
+        for byte in b"()<>[]{}/%" {
+            assert_eq!(Delimiter::from_byte(*byte).unwrap(), *byte);
+        }
+
+        assert!(Delimiter::from_byte(b'a').is_none());
+        assert!(Delimiter::from_byte(b' ').is_none());
+    }
 }
\ No newline at end of file
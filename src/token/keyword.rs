@@ -0,0 +1,91 @@
+/// PDF keyword representation.
+///
+/// Any run of regular characters that is neither a number nor
+/// a well-known keyword is kept as `Other`, which covers the
+/// operators found in content streams.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Keyword<'a> {
+    /// Boolean keyword `true`.
+    True,
+    /// Boolean keyword `false`.
+    False,
+    /// Null keyword `null`.
+    Null,
+    /// Start of an indirect object, `obj`.
+    Obj,
+    /// End of an indirect object, `endobj`.
+    EndObj,
+    /// Start of stream data, `stream`.
+    Stream,
+    /// End of stream data, `endstream`.
+    EndStream,
+    /// Indirect reference marker, `R`.
+    R,
+    /// Start of a cross-reference table, `xref`.
+    Xref,
+    /// Start of a trailer dictionary, `trailer`.
+    Trailer,
+    /// Offset of the last cross-reference section, `startxref`.
+    StartXref,
+    /// Any other keyword, such as a content stream operator.
+    Other(&'a [u8]),
+}
+
+impl<'a> Keyword<'a> {
+
+    /// Creates a new `Keyword` from the given bytes.
+    pub fn new(bytes: &'a [u8]) -> Self {
+        match bytes {
+            b"true" => Keyword::True,
+            b"false" => Keyword::False,
+            b"null" => Keyword::Null,
+            b"obj" => Keyword::Obj,
+            b"endobj" => Keyword::EndObj,
+            b"stream" => Keyword::Stream,
+            b"endstream" => Keyword::EndStream,
+            b"R" => Keyword::R,
+            b"xref" => Keyword::Xref,
+            b"trailer" => Keyword::Trailer,
+            b"startxref" => Keyword::StartXref,
+            _ => Keyword::Other(bytes),
+        }
+    }
+
+    /// Returns the byte representation of the Keyword.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        match self {
+            Keyword::True => b"true",
+            Keyword::False => b"false",
+            Keyword::Null => b"null",
+            Keyword::Obj => b"obj",
+            Keyword::EndObj => b"endobj",
+            Keyword::Stream => b"stream",
+            Keyword::EndStream => b"endstream",
+            Keyword::R => b"R",
+            Keyword::Xref => b"xref",
+            Keyword::Trailer => b"trailer",
+            Keyword::StartXref => b"startxref",
+            Keyword::Other(bytes) => bytes,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Keyword;
+
+    #[test]
+    fn should_create_keyword_from_bytes() {
+        let keywords: &[&[u8]] = &[
+            b"true", b"false", b"null", b"obj", b"endobj", b"stream",
+            b"endstream", b"R", b"xref", b"trailer", b"startxref", b"BT", b"Tj",
+        ];
+
+        for bytes in keywords {
+            assert_eq!(Keyword::new(bytes).as_bytes(), *bytes);
+        }
+
+        assert_eq!(Keyword::new(b"obj"), Keyword::Obj);
+        assert_eq!(Keyword::new(b"Tf"), Keyword::Other(b"Tf"));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,77 @@
+use crate::token::{Delimiter, Keyword};
+
+/// Kind of a PDF token.
+///
+/// Every variant borrows its bytes from the lexed input,
+/// so no token owns a copy of the source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind<'a> {
+    /// Integer number (i.e `42`, `-7`).
+    Integer(&'a [u8]),
+    /// Real number (i.e `3.14`, `-.5`).
+    Real(&'a [u8]),
+    /// Name including the leading solidus (i.e `/Type`).
+    ///
+    /// `#xx` escapes are kept as they appear in the source.
+    Name(&'a [u8]),
+    /// Literal string contents without the enclosing parentheses.
+    ///
+    /// Escape sequences are kept as they appear in the source.
+    LiteralString(&'a [u8]),
+    /// Hexadecimal string contents without the enclosing angle brackets.
+    HexadecimalString(&'a [u8]),
+    /// Start of a dictionary, `<<`.
+    DictionaryStart,
+    /// End of a dictionary, `>>`.
+    DictionaryEnd,
+    /// Array or procedure delimiter (`[`, `]`, `{` or `}`).
+    Delimiter(Delimiter),
+    /// Keyword such as `obj`, `R` or a content stream operator.
+    Keyword(Keyword<'a>),
+    /// Comment text without the leading percent sign.
+    Comment(&'a [u8]),
+}
+
+/// PDF token with its position in the source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token<'a> {
+    /// Kind of the token.
+    kind: TokenKind<'a>,
+    /// Byte offset of the first byte of the token.
+    offset: usize,
+    /// Raw bytes of the whole token.
+    bytes: &'a [u8],
+}
+
+impl<'a> Token<'a> {
+
+    /// Creates a new `Token`.
+    pub fn new(kind: TokenKind<'a>, offset: usize, bytes: &'a [u8]) -> Self {
+        Self { kind, offset, bytes }
+    }
+
+    /// Returns the kind of the token.
+    pub fn kind(&self) -> &TokenKind<'a> {
+        &self.kind
+    }
+
+    /// Returns the byte offset of the token in the source.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the byte offset just past the end of the token.
+    pub fn end(&self) -> usize {
+        self.offset + self.bytes.len()
+    }
+
+    /// Returns the raw bytes of the token as they appear in the source.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Returns `true` if the token is the given keyword.
+    pub fn is_keyword(&self, keyword: Keyword) -> bool {
+        matches!(self.kind, TokenKind::Keyword(k) if k == keyword)
+    }
+}
\ No newline at end of file
@@ -1,5 +1,10 @@
+//! This module contains PDF token representations.
 mod delimiter;
+mod keyword;
+mod kind;
 mod whitespace;
 
-pub(crate) use crate::token::delimiter::Delimiter;
-pub(crate) use crate::token::whitespace::Whitespace;
\ No newline at end of file
+pub use crate::token::delimiter::Delimiter;
+pub use crate::token::keyword::Keyword;
+pub use crate::token::kind::{Token, TokenKind};
+pub use crate::token::whitespace::Whitespace;
@@ -1,5 +1,3 @@
-use std::ops::Deref;
-
 /// PDF Whitespace characters representation.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Whitespace {
@@ -30,7 +28,20 @@ pub enum Whitespace {
 }
 
 impl Whitespace {
-    
+
+    /// Returns the `Whitespace` represented by the given byte, if any.
+    pub fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0x00 => Some(Whitespace::Null),
+            0x20 => Some(Whitespace::Space),
+            0x09 => Some(Whitespace::Tab),
+            0x0A => Some(Whitespace::LineFeed),
+            0x0C => Some(Whitespace::FormFeed),
+            0x0D => Some(Whitespace::CarriageReturn),
+            _ => None,
+        }
+    }
+
     /// Returns the byte representation of the Whitespace character.
     pub fn as_byte(&self) -> &u8 {
         match self {
@@ -64,4 +75,13 @@ mod tests {
         assert_eq!(Whitespace::FormFeed, 0x0C);
         assert_eq!(Whitespace::CarriageReturn, 0x0D);
     }
+
+    #[test]
+    fn should_create_whitespace_from_byte() {
+        for byte in [0x00, 0x20, 0x09, 0x0A, 0x0C, 0x0D] {
+            assert_eq!(Whitespace::from_byte(byte).unwrap(), byte);
+        }
+
+        assert!(Whitespace::from_byte(b'a').is_none());
+    }
 }
\ No newline at end of file
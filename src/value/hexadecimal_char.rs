@@ -21,4 +21,11 @@ impl HexadecimalChar {
 
         &self.0
     }
+
+    /// Returns the byte value of the Hexadecimal character sequence.
+    pub fn as_byte(&self) -> u8 {
+
+        // Safe because the digits are validated on creation.
+        u8::from_str_radix(std::str::from_utf8(&self.0).unwrap(), 16).unwrap()
+    }
 }
\ No newline at end of file
@@ -5,6 +5,7 @@ pub struct Ascii(Vec<u8>);
 impl Ascii {
     
     /// Creates a new `Ascii` character sequence from a byte vector.
+    #[allow(clippy::bool_comparison)]
     pub fn new(char: u8) -> Self {
 
         if char.is_ascii() == false {
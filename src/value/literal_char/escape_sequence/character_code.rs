@@ -1,4 +1,4 @@
-use crate::specification::value::literal_char::escape_sequence::{validate_escape_sequence_bytes, validate_escaped_char_code};
+use crate::specification::value::literal_char::escape_sequence::validate_escaped_char_code;
 
 /// PDF escaped character code representation.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -41,6 +41,16 @@ impl CharacterCode {
 
         value
     }
+
+    /// Returns the byte value of the character code.
+    /// 
+    /// The digits are interpreted as an octal number and
+    /// high-order overflow is ignored.
+    pub fn as_byte(&self) -> u8 {
+
+        self.0[1..].iter()
+            .fold(0u32, |value, &byte| value * 8 + (byte - b'0') as u32) as u8
+    }
 }
 
 #[cfg(test)]
@@ -52,5 +62,6 @@ mod tests {
         let char_code = CharacterCode::new(b"\\123".to_vec());
         assert_eq!(char_code.as_bytes(), b"\\123");
         assert_eq!(char_code.as_u32(), 123);
+        assert_eq!(char_code.as_byte(), 0o123);
     }
 }
\ No newline at end of file
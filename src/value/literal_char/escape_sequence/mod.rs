@@ -1,6 +1,6 @@
 mod character_code;
 
-use character_code::CharacterCode;
+pub use character_code::CharacterCode;
 
 /// PDF escape sequence representation.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -40,7 +40,7 @@ impl EscapeSequence {
             EscapeSequence::LineFeed => br"\n",
             EscapeSequence::CarriageReturn => br"\r",
             EscapeSequence::Tab => br"\t",
-            EscapeSequence::Backspace => br"\\",
+            EscapeSequence::Backspace => br"\b",
             EscapeSequence::FormFeed => br"\f",
             EscapeSequence::LeftParenthesis => br"\(",
             EscapeSequence::RightParenthesis => br"\)",
@@ -50,4 +50,24 @@ impl EscapeSequence {
             EscapeSequence::EndOfLine => b"\n",
         }
     }
+
+    /// Returns the byte value that the escape sequence stands for.
+    /// 
+    /// `Empty` stands for no byte, because a backslash that does not
+    /// start a valid escape sequence is ignored.
+    pub fn value(&self) -> Option<u8> {
+        match self {
+            EscapeSequence::LineFeed => Some(b'\n'),
+            EscapeSequence::CarriageReturn => Some(b'\r'),
+            EscapeSequence::Tab => Some(b'\t'),
+            EscapeSequence::Backspace => Some(0x08),
+            EscapeSequence::FormFeed => Some(0x0C),
+            EscapeSequence::LeftParenthesis => Some(b'('),
+            EscapeSequence::RightParenthesis => Some(b')'),
+            EscapeSequence::Backslash => Some(b'\\'),
+            EscapeSequence::CharacterCode(code) => Some(code.as_byte()),
+            EscapeSequence::Empty => None,
+            EscapeSequence::EndOfLine => Some(b'\n'),
+        }
+    }
 }
\ No newline at end of file
@@ -2,7 +2,7 @@ mod ascii;
 mod escape_sequence;
 
 pub use ascii::Ascii;
-pub use escape_sequence::EscapeSequence;
+pub use escape_sequence::{CharacterCode, EscapeSequence};
 
 /// PDF Literal string character representation.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -6,4 +6,5 @@ mod hexadecimal_char;
 pub use literal_char::LiteralChar;
 pub use literal_char::Ascii;
 pub use literal_char::EscapeSequence;
+pub use literal_char::CharacterCode;
 pub use hexadecimal_char::HexadecimalChar;
\ No newline at end of file
@@ -0,0 +1,492 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::{Error, ErrorKind, Write};
+
+use crate::object::{Array, Dicionary, Integer, Name, Object, ObjectId, Stream};
+use crate::structure::xref::XrefEntry;
+use crate::structure::{Header, HintTables, IndirectObject, PageHint, Version};
+use crate::writer::Writer;
+use crate::writer::rewrite::{collect_references, renumber, renumber_dicionary};
+use crate::writer::xref::table_bytes;
+
+/// Maximum number of times a linearized file is laid out before the
+/// length of its first part must have settled.
+const MAX_LAYOUT_PASSES: usize = 16;
+
+impl<W: Write> Writer<W> {
+
+    /// Writes the objects of a full rewrite as a linearized file.
+    ///
+    /// `objects` are numbered from 1 and `pages` holds the numbers of the
+    /// page objects, in page order. The file starts with the linearization
+    /// parameter dictionary, the first-page cross-reference table, the
+    /// catalog, the primary hint stream and the objects of the first page.
+    /// The other pages follow with the objects only they use, then the
+    /// objects shared between pages, the objects of no page, the overflow
+    /// hint stream if any, and the main cross-reference table.
+    pub(crate) fn write_linearized(&mut self, version: Version, objects: Vec<Object>, trailer: Dicionary, pages: &[u32]) -> std::io::Result<()> {
+
+        let catalog = trailer.get(b"/Root")
+            .and_then(Object::as_reference)
+            .map(|id| id.number())
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, "The trailer has no /Root entry"))?;
+
+        if pages.is_empty() {
+            return Err(Error::new(ErrorKind::InvalidData, "A document without pages cannot be linearized"));
+        }
+
+        let objects = objects.into_iter().map(|object| self.prepare(object)).collect::<Result<Vec<_>, _>>()?;
+        let sections = Sections::new(&objects, catalog, pages);
+        let limit = self.options.primary_hint_limit();
+
+        let mut layout = Layout::new(&objects, &sections, &trailer, false);
+
+        if limit > 0 && layout.hint_tables(0).to_bytes().0.len() > limit {
+            layout = Layout::new(&objects, &sections, &trailer, true);
+        }
+
+        let header = [Header::new(version).as_bytes(), b"\n"].concat();
+
+        // The first part of the file gives offsets of the rest, so it is
+        // laid out again until its length settles.
+        let mut marks = (0, 0);
+
+        for _ in 0..MAX_LAYOUT_PASSES {
+
+            let file = layout.render(&header, marks, limit);
+
+            if file.marks == marks {
+                self.entries = file.entries;
+                return self.write_bytes(&file.bytes);
+            }
+
+            marks = file.marks;
+        }
+
+        Err(Error::new(ErrorKind::InvalidData, "The layout of the linearized file did not settle"))
+    }
+}
+
+/// The objects of a document sorted into the parts of a linearized file,
+/// by their numbers in the rewrite.
+struct Sections {
+    /// The catalog.
+    catalog: u32,
+    /// The objects of the first page, its page object first.
+    first_page: Vec<u32>,
+    /// The objects only used by each other page, its page object first.
+    pages: Vec<Vec<u32>>,
+    /// The objects used by several pages, outside of the first page.
+    shared: Vec<u32>,
+    /// The objects used by no page.
+    other: Vec<u32>,
+    /// The objects used by each page that other pages use too.
+    references: Vec<Vec<u32>>,
+}
+
+impl Sections {
+
+    /// Sorts the objects from the pages they are used by.
+    fn new(objects: &[Object], catalog: u32, pages: &[u32]) -> Self {
+
+        let page_numbers = pages.iter().copied().collect::<HashSet<_>>();
+        let used = pages.iter().map(|page| page_objects(objects, *page, catalog, &page_numbers)).collect::<Vec<_>>();
+
+        let mut owners = HashMap::<u32, usize>::new();
+
+        for number in used.iter().flatten() {
+            *owners.entry(*number).or_default() += 1;
+        }
+
+        let is_shared = |number: &u32| owners[number] > 1;
+
+        let first_page = used[0].clone();
+        let in_first_page = first_page.iter().copied().collect::<HashSet<_>>();
+
+        let other_pages = used[1..].iter()
+            .map(|numbers| numbers.iter().filter(|number| !is_shared(number) && !in_first_page.contains(number)).copied().collect())
+            .collect();
+
+        let mut seen = HashSet::new();
+        let shared = used[1..].iter()
+            .flatten()
+            .filter(|number| is_shared(number) && !in_first_page.contains(number) && seen.insert(**number))
+            .copied()
+            .collect::<Vec<_>>();
+
+        let references = used.iter()
+            .map(|numbers| numbers.iter().filter(|number| is_shared(number)).copied().collect())
+            .collect();
+
+        let other = (1..=objects.len() as u32)
+            .filter(|number| *number != catalog && !owners.contains_key(number))
+            .collect();
+
+        Self { catalog, first_page, pages: other_pages, shared, other, references }
+    }
+}
+
+/// Returns the objects a page uses, the page object first, in the order
+/// they are found. The catalog, the page tree and other pages are not
+/// followed.
+fn page_objects(objects: &[Object], page: u32, catalog: u32, pages: &HashSet<u32>) -> Vec<u32> {
+
+    let mut found = vec![page];
+    let mut seen = HashSet::from([page]);
+    let mut index = 0;
+
+    while let Some(number) = found.get(index).copied() {
+
+        let mut references = Vec::new();
+        collect_references(&objects[number as usize - 1], &mut references);
+
+        for reference in references {
+
+            let number = reference.number();
+
+            let Some(object) = objects.get((number as usize).wrapping_sub(1)) else {
+                continue;
+            };
+
+            let is_page_tree = object.as_dicionary()
+                .and_then(|dicionary| dicionary.get(b"/Type"))
+                .is_some_and(|kind| kind.as_bytes() == b"/Pages");
+
+            if number != catalog && !pages.contains(&number) && !is_page_tree && seen.insert(number) {
+                found.push(number);
+            }
+        }
+
+        index += 1;
+    }
+
+    found
+}
+
+/// The objects of a linearized file, renumbered and serialized.
+///
+/// Objects after the first page are numbered from 1 in file order and
+/// are listed in the main cross-reference table. The linearization
+/// parameter dictionary, the catalog, the objects of the first page and
+/// the primary hint stream are numbered after them, in the first-page
+/// cross-reference table.
+struct Layout {
+    /// Number of the linearization parameter dictionary, and of entries
+    /// of the main cross-reference table.
+    main_size: u32,
+    /// Number of the overflow hint stream, if any.
+    overflow: Option<u32>,
+    /// The trailer entries, renumbered.
+    trailer: Dicionary,
+    /// The catalog.
+    catalog: Vec<u8>,
+    /// The objects of the first page section.
+    first_page: Vec<Vec<u8>>,
+    /// The objects only used by each other page.
+    pages: Vec<Vec<Vec<u8>>>,
+    /// The objects used by several pages, outside of the first page.
+    shared: Vec<Vec<u8>>,
+    /// The objects used by no page.
+    other: Vec<Vec<u8>>,
+    /// Indexes in the shared object hint table of the objects each page uses.
+    references: Vec<Vec<u32>>,
+}
+
+/// A linearized file as laid out for the given marks.
+struct Rendered {
+    /// The whole file.
+    bytes: Vec<u8>,
+    /// The entries of every object.
+    entries: BTreeMap<u32, XrefEntry>,
+    /// Offsets of the catalog and of the first-page cross-reference table.
+    marks: (usize, usize),
+}
+
+impl Layout {
+
+    /// Renumbers and serializes the objects, with an overflow hint stream or not.
+    fn new(objects: &[Object], sections: &Sections, trailer: &Dicionary, overflow: bool) -> Self {
+
+        let mut numbers = HashMap::new();
+        let mut next = 1;
+
+        for number in sections.pages.iter().flatten().chain(&sections.shared).chain(&sections.other) {
+            numbers.insert(ObjectId::new(*number, 0), next);
+            next += 1;
+        }
+
+        let overflow = overflow.then(|| {
+            next += 1;
+            next - 1
+        });
+
+        let main_size = next;
+        numbers.insert(ObjectId::new(sections.catalog, 0), main_size + 1);
+
+        for (index, number) in sections.first_page.iter().enumerate() {
+            numbers.insert(ObjectId::new(*number, 0), main_size + 2 + index as u32);
+        }
+
+        let serialize = |number: &u32| {
+            let id = ObjectId::new(numbers[&ObjectId::new(*number, 0)], 0);
+            object_bytes(&IndirectObject::new(id, renumber(&objects[*number as usize - 1], &numbers)))
+        };
+
+        let indexes = sections.first_page.iter()
+            .chain(&sections.shared)
+            .enumerate()
+            .map(|(index, number)| (*number, index as u32))
+            .collect::<HashMap<_, _>>();
+
+        Self {
+            main_size,
+            overflow,
+            trailer: renumber_dicionary(trailer, &numbers),
+            catalog: serialize(&sections.catalog),
+            first_page: sections.first_page.iter().map(serialize).collect(),
+            pages: sections.pages.iter().map(|numbers| numbers.iter().map(serialize).collect()).collect(),
+            shared: sections.shared.iter().map(serialize).collect(),
+            other: sections.other.iter().map(serialize).collect(),
+            references: sections.references.iter()
+                .map(|numbers| numbers.iter().filter_map(|number| indexes.get(number).copied()).collect())
+                .collect(),
+        }
+    }
+
+    /// Returns the hint tables of the file whose catalog is at `start`.
+    ///
+    /// Offsets are given as if the hint streams were not in the file.
+    fn hint_tables(&self, start: usize) -> HintTables {
+
+        let length = |objects: &[Vec<u8>]| objects.iter().map(Vec::len).sum::<usize>();
+
+        let first_page_offset = start + self.catalog.len();
+
+        let mut pages = vec![PageHint {
+            objects: self.first_page.len() as u32,
+            length: length(&self.first_page) as u32,
+            shared: self.references[0].clone(),
+        }];
+
+        for (objects, shared) in self.pages.iter().zip(&self.references[1..]) {
+            pages.push(PageHint { objects: objects.len() as u32, length: length(objects) as u32, shared: shared.clone() });
+        }
+
+        let shared_offset = first_page_offset + pages.iter().map(|page| page.length as usize).sum::<usize>();
+        let (first_shared_object, first_shared_offset) = match self.shared.is_empty() {
+            true => (0, 0),
+            false => (self.pages.iter().map(Vec::len).sum::<usize>() as u32 + 1, shared_offset as u32),
+        };
+
+        HintTables {
+            first_page_offset: first_page_offset as u32,
+            pages,
+            first_shared_object,
+            first_shared_offset,
+            first_page_groups: self.first_page.len() as u32,
+            groups: self.first_page.iter().chain(&self.shared).map(|object| (object.len() as u32, 1)).collect(),
+        }
+    }
+
+    /// Lays out the file, given the offsets of the catalog and of the
+    /// first-page cross-reference table, and returns it with the offsets
+    /// they turned out to have.
+    fn render(&self, header: &[u8], marks: (usize, usize), limit: usize) -> Rendered {
+
+        let (start, first_xref) = marks;
+        let integer = |value: usize| Object::Integer(Integer::from_value(value as i64));
+
+        let linearization_number = self.main_size;
+        let hint_number = self.main_size + 2 + self.first_page.len() as u32;
+
+        let (data, shared_offset) = self.hint_tables(start).to_bytes();
+        let split = if self.overflow.is_some() { limit.min(data.len()) } else { data.len() };
+
+        let mut dicionary = Dicionary::new(vec![]);
+        dicionary.insert(Name::new(b"/S").unwrap(), integer(shared_offset));
+        let primary = hint_stream(hint_number, dicionary, &data[..split]);
+
+        let mut entries = BTreeMap::new();
+        let mut body = Vec::new();
+
+        let mut place = |number: u32, bytes: &[u8], body: &mut Vec<u8>| {
+            entries.insert(number, XrefEntry::InUse { offset: start + body.len(), generation: 0 });
+            body.extend_from_slice(bytes);
+        };
+
+        place(self.main_size + 1, &self.catalog, &mut body);
+
+        let primary_offset = start + body.len();
+        place(hint_number, &primary, &mut body);
+
+        for (index, object) in self.first_page.iter().enumerate() {
+            place(self.main_size + 2 + index as u32, object, &mut body);
+        }
+
+        let first_page_end = start + body.len();
+
+        for (index, object) in self.pages.iter().flatten().chain(&self.shared).chain(&self.other).enumerate() {
+            place(index as u32 + 1, object, &mut body);
+        }
+
+        let mut hints = vec![integer(primary_offset), integer(primary.len())];
+
+        if let Some(number) = self.overflow {
+            let overflow = hint_stream(number, Dicionary::new(vec![]), &data[split..]);
+
+            hints.extend([integer(start + body.len()), integer(overflow.len())]);
+            place(number, &overflow, &mut body);
+        }
+
+        let main_xref = start + body.len();
+
+        let mut main = entries.range(..self.main_size).map(|(number, entry)| (*number, *entry)).collect::<BTreeMap<_, _>>();
+        main.insert(0, XrefEntry::Free { next: 0, generation: 65535 });
+
+        let mut main_trailer = Dicionary::new(vec![]);
+        main_trailer.insert(Name::new(b"/Size").unwrap(), integer(self.main_size as usize));
+
+        body.extend_from_slice(&table_bytes(&main));
+        body.extend_from_slice(b"trailer\n");
+        body.extend_from_slice(main_trailer.as_bytes());
+        body.extend_from_slice(format!("\nstartxref\n{}\n%%EOF\n", first_xref).as_bytes());
+
+        let mut parameters = Dicionary::new(vec![]);
+        parameters.insert(Name::new(b"/Linearized").unwrap(), integer(1));
+        parameters.insert(Name::new(b"/L").unwrap(), integer(start + body.len()));
+        parameters.insert(Name::new(b"/H").unwrap(), Object::Array(Array::new(hints)));
+        parameters.insert(Name::new(b"/O").unwrap(), integer(self.main_size as usize + 2));
+        parameters.insert(Name::new(b"/E").unwrap(), integer(first_page_end));
+        parameters.insert(Name::new(b"/N").unwrap(), integer(self.references.len()));
+        // The white-space before the entry of object 0.
+        parameters.insert(Name::new(b"/T").unwrap(), integer(main_xref + format!("xref\n0 {}", self.main_size).len()));
+
+        let mut bytes = header.to_vec();
+        entries.insert(linearization_number, XrefEntry::InUse { offset: bytes.len(), generation: 0 });
+        bytes.extend_from_slice(&object_bytes(&IndirectObject::new(ObjectId::new(linearization_number, 0), Object::Dicionary(parameters))));
+
+        let first_page_xref = bytes.len();
+
+        let mut trailer = Dicionary::new(vec![]);
+        trailer.insert(Name::new(b"/Size").unwrap(), integer(hint_number as usize + 1));
+
+        for (key, value) in self.trailer.iter() {
+            trailer.insert(key.clone(), value.clone());
+        }
+
+        trailer.insert(Name::new(b"/Prev").unwrap(), integer(main_xref));
+
+        let first = entries.range(self.main_size..).map(|(number, entry)| (*number, *entry)).collect::<BTreeMap<_, _>>();
+
+        bytes.extend_from_slice(&table_bytes(&first));
+        bytes.extend_from_slice(b"trailer\n");
+        bytes.extend_from_slice(trailer.as_bytes());
+        bytes.extend_from_slice(b"\nstartxref\n0\n%%EOF\n");
+
+        let marks = (bytes.len(), first_page_xref);
+
+        bytes.extend_from_slice(&body);
+        entries.insert(0, XrefEntry::Free { next: 0, generation: 65535 });
+
+        Rendered { bytes, entries, marks }
+    }
+}
+
+/// Returns a hint stream object, its data left unfiltered so that its
+/// length does not depend on the offsets it holds.
+fn hint_stream(number: u32, dicionary: Dicionary, data: &[u8]) -> Vec<u8> {
+    object_bytes(&IndirectObject::new(ObjectId::new(number, 0), Object::Stream(Stream::new(dicionary, data.to_vec()))))
+}
+
+/// Returns an indirect object as the writer writes it.
+fn object_bytes(object: &IndirectObject) -> Vec<u8> {
+    [object.as_bytes(), b"\n"].concat()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::sync::Arc;
+
+    use crate::byte_source::ByteSource;
+    use crate::byte_source::memory::MemoryByteSource;
+    use crate::document::{Document, Linearization};
+    use crate::document::tests::build_pdf;
+    use crate::writer::{WriteOptions, Writer};
+
+    /// Returns a file of three pages: the first two share a font, the
+    /// last two share another one.
+    pub(crate) fn pages_pdf() -> Arc<dyn ByteSource> {
+        build_pdf(&[
+            b"<</Type /Catalog /Pages 2 0 R /Outlines 11 0 R>>",
+            b"<</Type /Pages /Kids [3 0 R 5 0 R 7 0 R] /Count 3 /MediaBox [0 0 612 792]>>",
+            b"<</Type /Page /Parent 2 0 R /Contents 4 0 R /Resources <</Font <</F1 9 0 R>>>>>>",
+            b"<</Length 34>>\nstream\nBT /F1 12 Tf 72 720 Td (One) Tj ET\nendstream",
+            b"<</Type /Page /Parent 2 0 R /Contents 6 0 R /Resources <</Font <</F1 9 0 R /F2 10 0 R>>>>>>",
+            b"<</Length 34>>\nstream\nBT /F2 12 Tf 72 720 Td (Two) Tj ET\nendstream",
+            b"<</Type /Page /Parent 2 0 R /Contents 8 0 R /Resources <</Font <</F2 10 0 R>>>>>>",
+            b"<</Length 36>>\nstream\nBT /F2 12 Tf 72 720 Td (Three) Tj ET\nendstream",
+            b"<</Type /Font /Subtype /Type1 /BaseFont /Helvetica>>",
+            b"<</Type /Font /Subtype /Type1 /BaseFont /Courier>>",
+            b"<</Type /Outlines /Count 0>>",
+        ])
+    }
+
+    /// Returns the document written from the source with the options.
+    pub(crate) fn linearized(source: &Arc<dyn ByteSource>, options: WriteOptions) -> Document {
+        let mut writer = Writer::with_options(Vec::new(), options.with_linearization(true));
+        writer.rewrite_document(&Document::read(source).unwrap()).unwrap();
+
+        let source: Arc<dyn ByteSource> = Arc::new(MemoryByteSource::new(writer.into_inner()));
+        Document::read(&source).unwrap()
+    }
+
+    #[test]
+    fn should_write_first_page_first() {
+        let source = pages_pdf();
+        let document = Document::read(&source).unwrap();
+        let written = linearized(&source, WriteOptions::new());
+
+        assert!(written.repair_report().is_none());
+        assert_eq!(written.xref().sections().len(), 2);
+        assert_eq!(written.pages().len(), 3);
+
+        for index in 0..3 {
+            assert_eq!(written.page_content(index).unwrap(), document.page_content(index).unwrap());
+        }
+
+        let bytes = written.source().slice(0..written.source().len());
+        let position = |needle: &[u8]| bytes.windows(needle.len()).position(|window| window == needle).unwrap();
+
+        assert!(position(b"/Linearized 1") < position(b"/Type /Catalog"));
+        assert!(position(b"(One)") < position(b"/Helvetica"));
+        assert!(position(b"/Helvetica") < position(b"(Two)"));
+        assert!(position(b"(Three)") < position(b"/Courier"));
+        assert!(position(b"/Courier") < position(b"/Type /Outlines"));
+
+        let linearization = Linearization::read(&written).unwrap().unwrap();
+        assert_eq!(linearization.length(), bytes.len());
+        assert_eq!(linearization.page_count(), 3);
+        assert_eq!(linearization.first_page(), written.page(0).unwrap().id().number());
+        assert_eq!(linearization.hint_streams().len(), 1);
+        assert!(linearization.first_page_end() < position(b"(Two)"));
+        assert!(linearization.check(&written).is_empty());
+    }
+
+    #[test]
+    fn should_write_overflow_hint_stream() {
+        let written = linearized(&pages_pdf(), WriteOptions::compact().with_primary_hint_limit(16));
+
+        let linearization = Linearization::read(&written).unwrap().unwrap();
+        assert_eq!(linearization.hint_streams().len(), 2);
+        assert!(linearization.hint_streams()[1].0 > linearization.first_page_end());
+        assert!(linearization.hint_streams()[1].0 < linearization.main_xref());
+        assert!(linearization.check(&written).is_empty());
+    }
+
+    #[test]
+    fn should_refuse_documents_without_pages() {
+        let source = build_pdf(&[b"<</Type /Catalog /Pages 2 0 R>>", b"<</Type /Pages /Kids [] /Count 0>>"]);
+
+        let mut writer = Writer::with_options(Vec::new(), WriteOptions::new().with_linearization(true));
+        assert!(writer.rewrite_document(&Document::read(&source).unwrap()).is_err());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,726 @@
+//! This module contains the serialization of documents into PDF files.
+pub(crate) mod linearize;
+mod options;
+mod rewrite;
+mod xref;
+
+use std::collections::BTreeMap;
+use std::io::{Error, ErrorKind, Write};
+
+use crate::document::Document;
+use crate::filter::encode_flate_with_level;
+use crate::object::{Array, Dicionary, Integer, Name, Object, ObjectId, Stream};
+use crate::structure::xref::{clean_trailer, XrefEntry};
+use crate::structure::{Header, IndirectObject, Version, EOF};
+use crate::writer::rewrite::Rewrite;
+use crate::writer::xref::{stream_records, subsections, table_bytes};
+
+pub use crate::writer::options::WriteOptions;
+pub use crate::writer::rewrite::RewriteReport;
+pub use crate::writer::xref::XrefStyle;
+
+/// Trailer entries carried over from the document being written.
+const TRAILER_KEYS: [&[u8]; 3] = [b"/Root", b"/Info", b"/ID"];
+
+/// A writer of PDF files to any `std::io::Write` output.
+///
+/// Objects are written one after another while their offsets are
+/// recorded, and the cross-reference data closes the file.
+///
+/// ```rs
+/// let mut writer = Writer::new(File::create("redacted.pdf")?);
+/// writer.rewrite_document(&document)?;
+/// ```
+///
+/// To append the changes of a document to its file instead:
+///
+/// ```rs
+/// let mut writer = Writer::new(File::create("updated.pdf")?);
+/// writer.update_document(&document, XrefStyle::of(&document))?;
+/// ```
+#[derive(Debug)]
+pub struct Writer<W: Write> {
+    /// The output.
+    output: W,
+    /// Number of bytes written so far.
+    position: usize,
+    /// Position of the `%PDF-` header, which recorded offsets are relative to.
+    base: usize,
+    /// Entries of the objects written or freed so far, by object number.
+    entries: BTreeMap<u32, XrefEntry>,
+    /// Options of the files written.
+    options: WriteOptions,
+}
+
+impl<W: Write> Writer<W> {
+
+    /// Creates a new `Writer` on the given output, with the default options.
+    pub fn new(output: W) -> Self {
+        Self::with_options(output, WriteOptions::default())
+    }
+
+    /// Creates a new `Writer` on the given output, with the given options.
+    pub fn with_options(output: W, options: WriteOptions) -> Self {
+        Self { output, position: 0, base: 0, entries: BTreeMap::new(), options }
+    }
+
+    /// Returns the options of the files written.
+    pub fn options(&self) -> &WriteOptions {
+        &self.options
+    }
+
+    /// Returns the number of bytes written so far.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns the entries of the objects written or freed so far.
+    pub fn entries(&self) -> &BTreeMap<u32, XrefEntry> {
+        &self.entries
+    }
+
+    /// Consumes the writer and returns the output.
+    pub fn into_inner(self) -> W {
+        self.output
+    }
+
+    /// Writes the header: the version line and the binary marker comment.
+    pub fn write_header(&mut self, header: &Header) -> std::io::Result<()> {
+
+        self.write_bytes(header.as_bytes())?;
+        self.write_bytes(b"\n")
+    }
+
+    /// Writes an indirect object and records its offset.
+    pub fn write_object(&mut self, object: &IndirectObject) -> std::io::Result<()> {
+
+        let id = object.id();
+        self.entries.insert(id.number(), XrefEntry::InUse { offset: self.offset(), generation: id.generation() });
+
+        self.write_bytes(object.as_bytes())?;
+        self.write_bytes(b"\n")
+    }
+
+    /// Records a free object number, with the generation to use if the
+    /// number is reused.
+    pub fn free_object(&mut self, number: u32, generation: u16) {
+        self.entries.insert(number, XrefEntry::Free { next: 0, generation });
+    }
+
+    /// Writes the cross-reference table of the recorded objects, the
+    /// trailer, `startxref` and `%%EOF`, and returns the offset of the table.
+    ///
+    /// Object numbers without an entry are written as free, and free
+    /// entries are linked from object 0. `/Size` is set on the trailer.
+    pub fn write_xref_table(&mut self, trailer: &Dicionary) -> std::io::Result<usize> {
+
+        let (entries, size) = self.full_entries();
+
+        self.write_xref(XrefStyle::Table, entries, size, trailer.clone())
+    }
+
+    /// Appends the changes of a document to its file as an incremental update.
+    ///
+    /// The original file is copied unchanged, so signatures over its
+    /// bytes stay valid. Only the changed and added objects follow,
+    /// closed by a cross-reference section in the given style whose
+    /// trailer points back to the original one with `/Prev`. Without
+    /// changes, the file is copied as is.
+    pub fn update_document(&mut self, document: &Document, style: XrefStyle) -> std::io::Result<()> {
+
+        if document.trailer().contains_key(b"/Encrypt") {
+            return Err(Error::new(ErrorKind::Unsupported, "Encrypted documents cannot be written"));
+        }
+
+        if style == XrefStyle::Stream && !document.version().is_later_than(&Version::new(1, 4)) {
+            return Err(Error::new(ErrorKind::Unsupported, "Cross-reference streams need PDF 1.5 or later"));
+        }
+
+        let source = document.source();
+        let original = source.slice(0..source.len());
+
+        self.write_bytes(original)?;
+
+        if document.changes().is_empty() {
+            return Ok(());
+        }
+
+        if !matches!(original.last(), Some(b'\n' | b'\r')) {
+            self.write_bytes(b"\n")?;
+        }
+
+        self.base = document.xref().base();
+        self.entries.clear();
+
+        for (id, object) in document.changes() {
+            let object = self.prepare(object.clone())?;
+            self.write_object(&IndirectObject::new(*id, object))?;
+        }
+
+        let size = [
+            document.trailer().get(b"/Size").and_then(Object::as_i64).and_then(|size| u32::try_from(size).ok()),
+            document.xref().entries().keys().next_back().map(|number| number + 1),
+            self.entries.keys().next_back().map(|number| number + 1),
+        ].into_iter().flatten().max().unwrap_or(1);
+
+        let mut trailer = document.trailer().clone();
+        clean_trailer(&mut trailer, size);
+        trailer.insert(Name::new(b"/Prev").unwrap(), Object::Integer(Integer::from_value(document.xref().startxref() as i64)));
+
+        let entries = self.entries.clone();
+        self.write_xref(style, entries, size, trailer)?;
+
+        Ok(())
+    }
+
+    /// Writes a whole document as a single revision.
+    ///
+    /// Every object in use is written with its current value, changes
+    /// included, under its own number. The object and cross-reference
+    /// streams of the document are dropped, and their objects are
+    /// written as the options say.
+    pub fn write_document(&mut self, document: &Document) -> std::io::Result<()> {
+
+        if document.trailer().contains_key(b"/Encrypt") {
+            return Err(Error::new(ErrorKind::Unsupported, "Encrypted documents cannot be written"));
+        }
+
+        let mut ids = document.xref().entries().iter()
+            .filter(|(number, entry)| **number > 0 && entry.is_in_use())
+            .map(|(number, entry)| (*number, ObjectId::new(*number, entry.generation())))
+            .collect::<BTreeMap<_, _>>();
+
+        ids.extend(document.changes().keys().map(|id| (id.number(), *id)));
+
+        let mut objects = Vec::with_capacity(ids.len());
+
+        for id in ids.values() {
+
+            let object = document.get(*id).map_err(|message| Error::new(ErrorKind::InvalidData, message))?;
+
+            if !is_cross_reference_container(&object) {
+                objects.push((*id, object));
+            }
+        }
+
+        for (number, entry) in document.xref().entries() {
+            if let XrefEntry::Free { generation, .. } = entry && *number > 0 && !ids.contains_key(number) {
+                self.free_object(*number, *generation);
+            }
+        }
+
+        self.write_file(document.version(), objects, carried_trailer(document.trailer()))
+    }
+
+    /// Writes a document as a new file holding only what it still uses.
+    ///
+    /// Only the objects reachable from the trailer's `/Root` and `/Info`
+    /// are written, renumbered from 1, in a single revision. Unreachable
+    /// objects, earlier revisions and free entries are all discarded, so
+    /// nothing removed from the document can be recovered from the file.
+    ///
+    /// The file is linearized if the options say so.
+    pub fn rewrite_document(&mut self, document: &Document) -> std::io::Result<RewriteReport> {
+
+        if document.trailer().contains_key(b"/Encrypt") {
+            return Err(Error::new(ErrorKind::Unsupported, "Encrypted documents cannot be written"));
+        }
+
+        let rewrite = Rewrite::new(document).map_err(|message| Error::new(ErrorKind::InvalidData, message))?;
+
+        if self.options.linearizes() {
+
+            let pages = document.pages().iter()
+                .filter_map(|page| rewrite.numbers.get(&page.id()).copied())
+                .collect::<Vec<_>>();
+
+            self.write_linearized(document.version(), rewrite.objects, rewrite.trailer, &pages)?;
+
+            return Ok(rewrite.report);
+        }
+
+        let objects = rewrite.objects.into_iter()
+            .enumerate()
+            .map(|(index, object)| (ObjectId::new(index as u32 + 1, 0), object))
+            .collect();
+
+        self.write_file(document.version(), objects, rewrite.trailer)?;
+
+        Ok(rewrite.report)
+    }
+
+    /// Writes a cross-reference section of the given entries, then
+    /// `startxref` and `%%EOF`, and returns the offset of the section.
+    ///
+    /// `size` is one more than the highest object number. A stream
+    /// section takes that number itself, and the trailer entries go in
+    /// its dictionary.
+    fn write_xref(&mut self, style: XrefStyle, mut entries: BTreeMap<u32, XrefEntry>, size: u32, mut trailer: Dicionary) -> std::io::Result<usize> {
+
+        let offset = self.offset();
+        let integer = |value: usize| Object::Integer(Integer::from_value(value as i64));
+
+        match style {
+            XrefStyle::Table => {
+                trailer.insert(Name::new(b"/Size").unwrap(), integer(size as usize));
+
+                let mut bytes = table_bytes(&entries);
+                bytes.extend_from_slice(b"trailer\n");
+                bytes.extend_from_slice(trailer.as_bytes());
+                bytes.push(b'\n');
+
+                self.write_bytes(&bytes)?;
+            }
+            XrefStyle::Stream => {
+                entries.insert(size, XrefEntry::InUse { offset, generation: 0 });
+
+                let (widths, records) = stream_records(&entries);
+                let index = subsections(&entries).into_iter()
+                    .flat_map(|(first, count)| [integer(first as usize), integer(count as usize)])
+                    .collect();
+
+                let mut dicionary = Dicionary::new(vec![]);
+                dicionary.insert(Name::new(b"/Type").unwrap(), Object::Name(Name::new(b"/XRef").unwrap()));
+
+                for (key, value) in trailer.iter() {
+                    dicionary.insert(key.clone(), value.clone());
+                }
+
+                dicionary.insert(Name::new(b"/Size").unwrap(), integer(size as usize + 1));
+                dicionary.insert(Name::new(b"/Index").unwrap(), Object::Array(Array::new(index)));
+                dicionary.insert(Name::new(b"/W").unwrap(), Object::Array(Array::new(widths.into_iter().map(integer).collect())));
+                dicionary.insert(Name::new(b"/Filter").unwrap(), Object::Name(Name::new(b"/FlateDecode").unwrap()));
+
+                let stream = self.compress(dicionary, &records)?;
+
+                self.write_object(&IndirectObject::new(ObjectId::new(size, 0), Object::Stream(stream)))?;
+            }
+        }
+
+        self.write_end(offset)?;
+
+        Ok(offset)
+    }
+
+    /// Writes the header, the objects and the cross-reference data of a
+    /// whole file, as the options say.
+    ///
+    /// From PDF 1.5, with object streams enabled, objects that are not
+    /// streams are packed into object streams numbered after the last
+    /// object, and a cross-reference stream closes the file.
+    fn write_file(&mut self, version: Version, objects: Vec<(ObjectId, Object)>, trailer: Dicionary) -> std::io::Result<()> {
+
+        let batch = match self.options.object_stream_size() {
+            size if size > 0 && version.is_later_than(&Version::new(1, 4)) => Some(size),
+            _ => None,
+        };
+
+        let mut next = objects.iter().map(|(id, _)| id.number()).chain(self.entries.keys().copied()).max().unwrap_or(0) + 1;
+        let mut packed = Vec::new();
+
+        self.write_header(&Header::new(version))?;
+
+        for (id, object) in objects {
+
+            let object = self.prepare(object)?;
+
+            if batch.is_some() && id.generation() == 0 && !matches!(object, Object::Stream(_)) {
+                packed.push((id.number(), object));
+            } else {
+                self.write_object(&IndirectObject::new(id, object))?;
+            }
+        }
+
+        for objects in packed.chunks(batch.unwrap_or(1)) {
+            self.write_object_stream(next, objects)?;
+            next += 1;
+        }
+
+        let (entries, size) = self.full_entries();
+        let style = if batch.is_some() { XrefStyle::Stream } else { XrefStyle::Table };
+
+        self.write_xref(style, entries, size, trailer)?;
+
+        Ok(())
+    }
+
+    /// Writes an object stream (`/Type /ObjStm`) holding the given
+    /// objects, and records their entries in it.
+    fn write_object_stream(&mut self, number: u32, objects: &[(u32, Object)]) -> std::io::Result<()> {
+
+        let mut header = Vec::new();
+        let mut body = Vec::new();
+
+        for (member, object) in objects {
+            header.extend_from_slice(format!("{} {} ", member, body.len()).as_bytes());
+            body.extend_from_slice(object.as_bytes());
+            body.push(b'\n');
+        }
+
+        let integer = |value: usize| Object::Integer(Integer::from_value(value as i64));
+
+        let mut dicionary = Dicionary::new(vec![]);
+        dicionary.insert(Name::new(b"/Type").unwrap(), Object::Name(Name::new(b"/ObjStm").unwrap()));
+        dicionary.insert(Name::new(b"/N").unwrap(), integer(objects.len()));
+        dicionary.insert(Name::new(b"/First").unwrap(), integer(header.len()));
+        dicionary.insert(Name::new(b"/Filter").unwrap(), Object::Name(Name::new(b"/FlateDecode").unwrap()));
+
+        header.extend_from_slice(&body);
+        let stream = self.compress(dicionary, &header)?;
+
+        self.write_object(&IndirectObject::new(ObjectId::new(number, 0), Object::Stream(stream)))?;
+
+        for (index, (member, _)) in objects.iter().enumerate() {
+            self.entries.insert(*member, XrefEntry::Compressed { stream: number, index: index as u32 });
+        }
+
+        Ok(())
+    }
+
+    /// Returns an object as it is written: with the options' stream
+    /// compression, a stream without a filter is Flate-compressed when
+    /// that makes it smaller. Metadata streams are left readable.
+    fn prepare(&self, object: Object) -> std::io::Result<Object> {
+
+        let Object::Stream(stream) = &object else {
+            return Ok(object);
+        };
+
+        let dicionary = stream.dicionary();
+
+        if !self.options.compresses_streams()
+            || dicionary.contains_key(b"/Filter")
+            || dicionary.get(b"/Type").map(Object::as_bytes) == Some(b"/Metadata")
+        {
+            return Ok(object);
+        }
+
+        let mut dicionary = dicionary.clone();
+        dicionary.remove(b"/DecodeParms");
+        dicionary.insert(Name::new(b"/Filter").unwrap(), Object::Name(Name::new(b"/FlateDecode").unwrap()));
+
+        let compressed = self.compress(dicionary, stream.data().as_bytes())?;
+
+        if compressed.data().len() < stream.data().len() {
+            Ok(Object::Stream(compressed))
+        } else {
+            Ok(object)
+        }
+    }
+
+    /// Creates a stream of the data Flate-compressed at the options' level.
+    ///
+    /// The dictionary must name `/FlateDecode` as its filter.
+    fn compress(&self, dicionary: Dicionary, data: &[u8]) -> std::io::Result<Stream> {
+
+        let data = encode_flate_with_level(data, self.options.compression_level())
+            .map_err(|message| Error::new(ErrorKind::InvalidData, message))?;
+
+        Ok(Stream::new(dicionary, data))
+    }
+
+    /// Returns the entries of every object number up to the highest one
+    /// recorded, and their count.
+    ///
+    /// Object numbers without an entry are free, and free entries are
+    /// linked from object 0.
+    fn full_entries(&self) -> (BTreeMap<u32, XrefEntry>, u32) {
+
+        let size = self.entries.keys().next_back().map_or(1, |number| number + 1);
+
+        let mut entries = (0..size)
+            .map(|number| match self.entries.get(&number) {
+                Some(entry) if number > 0 => (number, *entry),
+                _ if number == 0 => (number, XrefEntry::Free { next: 0, generation: 65535 }),
+                _ => (number, XrefEntry::Free { next: 0, generation: 0 }),
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        // Each free entry points to the next one, and the last one to 0.
+        let mut next = 0;
+
+        for (number, entry) in entries.iter_mut().rev() {
+            if let XrefEntry::Free { next: link, .. } = entry {
+                *link = next;
+                next = *number;
+            }
+        }
+
+        (entries, size)
+    }
+
+    /// Returns the offset of the next byte from the `%PDF-` header.
+    fn offset(&self) -> usize {
+        self.position - self.base
+    }
+
+    /// Writes `startxref`, the offset of the cross-reference data and `%%EOF`.
+    fn write_end(&mut self, offset: usize) -> std::io::Result<()> {
+
+        self.write_bytes(format!("startxref\n{}\n", offset).as_bytes())?;
+        self.write_bytes(EOF::new().as_bytes())?;
+        self.write_bytes(b"\n")
+    }
+
+    /// Writes raw bytes and advances the position.
+    fn write_bytes(&mut self, bytes: &[u8]) -> std::io::Result<()> {
+
+        self.output.write_all(bytes)?;
+        self.position += bytes.len();
+
+        Ok(())
+    }
+}
+
+/// Returns `true` if the object is an object stream or a cross-reference
+/// stream, whose content is rebuilt when writing.
+pub(crate) fn is_cross_reference_container(object: &Object) -> bool {
+
+    let Object::Stream(stream) = object else {
+        return false;
+    };
+
+    matches!(stream.dicionary().get(b"/Type").map(Object::as_bytes), Some(b"/ObjStm" | b"/XRef"))
+}
+
+/// Returns the trailer entries that describe the document rather than
+/// the cross-reference data of the file.
+fn carried_trailer(trailer: &Dicionary) -> Dicionary {
+
+    let mut carried = Dicionary::new(vec![]);
+
+    for key in TRAILER_KEYS {
+        if let Some(value) = trailer.get(key) {
+            carried.insert(Name::new(key).unwrap(), value.clone());
+        }
+    }
+
+    carried
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::{WriteOptions, Writer, XrefStyle};
+    use crate::byte_source::ByteSource;
+    use crate::byte_source::memory::MemoryByteSource;
+    use crate::document::Document;
+    use crate::document::tests::build_pdf;
+    use crate::filter::decode_stream;
+    use crate::object::{Dicionary, Name, Object, ObjectId, Reference};
+    use crate::structure::xref::tests::{compressed_pdf, updated_pdf};
+    use crate::structure::xref::XrefEntry;
+    use crate::structure::{Header, IndirectObject, Version};
+
+    fn read(bytes: Vec<u8>) -> Document {
+        let source: Arc<dyn ByteSource> = Arc::new(MemoryByteSource::new(bytes));
+        Document::read(&source).unwrap()
+    }
+
+    #[test]
+    fn should_write_header_objects_and_xref_table() {
+        let mut writer = Writer::new(Vec::new());
+        writer.write_header(&Header::new(Version::new(1, 4))).unwrap();
+        writer.write_object(&IndirectObject::new(ObjectId::new(1, 0), Object::parse(b"<</Type /Catalog /Pages 3 0 R>>").unwrap())).unwrap();
+        writer.write_object(&IndirectObject::new(ObjectId::new(3, 0), Object::parse(b"<</Type /Pages /Kids [] /Count 0>>").unwrap())).unwrap();
+
+        let mut trailer = Dicionary::new(vec![]);
+        trailer.insert(Name::new(b"/Root").unwrap(), Object::Reference(Reference::new(ObjectId::new(1, 0))));
+
+        let offset = writer.write_xref_table(&trailer).unwrap();
+        let bytes = writer.into_inner();
+
+        assert_eq!(offset, 112);
+        assert_eq!(&bytes[..offset], b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n\
+            1 0 obj\n<</Type /Catalog /Pages 3 0 R>>\nendobj\n\
+            3 0 obj\n<</Type /Pages /Kids [] /Count 0>>\nendobj\n".as_slice());
+        assert_eq!(&bytes[offset..], b"xref\n0 4\n\
+            0000000002 65535 f\r\n0000000015 00000 n\r\n0000000000 00000 f\r\n0000000062 00000 n\r\n\
+            trailer\n<</Root 1 0 R /Size 4>>\nstartxref\n112\n%%EOF\n".as_slice());
+
+        let document = read(bytes);
+        assert!(document.repair_report().is_none());
+        assert_eq!(document.version(), Version::new(1, 4));
+    }
+
+    #[test]
+    fn should_write_document_with_changes() {
+        let mut document = Document::read(&build_pdf(&[
+            b"<</Type /Catalog /Pages 2 0 R>>",
+            b"<</Type /Pages /Kids [3 0 R] /Count 1>>",
+            b"<</Type /Page /Parent 2 0 R /MediaBox [0 0 200 100] /Contents 4 0 R>>",
+            b"<</Length 5>>\nstream\nBT ET\nendstream",
+        ])).unwrap();
+
+        let contents = document.add_object(Object::parse(b"(new)").unwrap());
+        document.set_object(ObjectId::new(4, 0), Object::parse(b"<</Length 3>>\nstream\nq Q\nendstream").unwrap());
+
+        let mut writer = Writer::new(Vec::new());
+        writer.write_document(&document).unwrap();
+
+        let written = read(writer.into_inner());
+        assert!(written.repair_report().is_none());
+        assert_eq!(written.pages().len(), 1);
+        assert_eq!(written.page(0).unwrap().size(), (200.0, 100.0));
+        assert_eq!(written.page_content(0).unwrap(), b"q Q");
+        assert_eq!(written.get(contents).unwrap(), Object::parse(b"(new)").unwrap());
+        assert_eq!(written.trailer().get(b"/Size").unwrap().as_i64(), Some(6));
+    }
+
+    #[test]
+    fn should_unpack_object_streams() {
+        let document = Document::read(&compressed_pdf(false)).unwrap();
+
+        let mut writer = Writer::new(Vec::new());
+        writer.write_document(&document).unwrap();
+
+        let written = read(writer.into_inner());
+        assert!(written.repair_report().is_none());
+        assert_eq!(written.version(), Version::new(1, 5));
+        assert_eq!(written.catalog(), document.catalog());
+        assert!(written.xref().entries().values().all(|entry| !matches!(entry, XrefEntry::Compressed { .. })));
+        assert_eq!(written.trailer().get(b"/Size").unwrap().as_i64(), Some(3));
+    }
+
+    #[test]
+    fn should_pack_objects_into_object_streams() {
+        let document = Document::read(&compressed_pdf(false)).unwrap();
+
+        let mut writer = Writer::with_options(Vec::new(), WriteOptions::compact().with_object_streams(1));
+        writer.write_document(&document).unwrap();
+
+        let written = read(writer.into_inner());
+        assert!(written.repair_report().is_none());
+        assert_eq!(XrefStyle::of(&written), XrefStyle::Stream);
+        assert_eq!(written.catalog(), document.catalog());
+        assert!(matches!(written.xref().entry(1), Some(XrefEntry::Compressed { stream: 3, index: 0 })));
+        assert!(matches!(written.xref().entry(2), Some(XrefEntry::Compressed { stream: 4, index: 0 })));
+        assert_eq!(written.trailer().get(b"/Size").unwrap().as_i64(), Some(6));
+    }
+
+    #[test]
+    fn should_compress_streams_without_filter() {
+        let content = "0 0 m 10 10 l S\n".repeat(20);
+        let source = build_pdf(&[
+            b"<</Type /Catalog /Pages 2 0 R /Metadata 4 0 R>>",
+            b"<</Type /Pages /Kids [] /Count 0>>",
+            format!("<</Length {}>>\nstream\n{}\nendstream", content.len(), content).as_bytes(),
+            format!("<</Type /Metadata /Subtype /XML /Length {}>>\nstream\n{}\nendstream", content.len(), content).as_bytes(),
+        ]);
+        let document = Document::read(&source).unwrap();
+
+        let mut plain = Writer::new(Vec::new());
+        plain.write_document(&document).unwrap();
+        let plain = plain.into_inner();
+
+        let mut writer = Writer::with_options(Vec::new(), WriteOptions::compact());
+        writer.write_document(&document).unwrap();
+        let compact = writer.into_inner();
+
+        assert!(compact.len() < plain.len());
+
+        let written = read(compact);
+        assert!(written.repair_report().is_none());
+        assert_eq!(written.version(), Version::new(1, 7));
+
+        let stream = written.get(ObjectId::new(3, 0)).unwrap();
+        assert_eq!(stream.as_stream().unwrap().dicionary().get(b"/Filter").unwrap().as_bytes(), b"/FlateDecode");
+        assert_eq!(decode_stream(stream.as_stream().unwrap()).unwrap(), content.as_bytes());
+
+        let metadata = written.get(ObjectId::new(4, 0)).unwrap();
+        assert!(metadata.as_stream().unwrap().dicionary().get(b"/Filter").is_none());
+        assert!(matches!(written.xref().entry(1), Some(XrefEntry::Compressed { stream: 5, index: 0 })));
+    }
+
+    #[test]
+    fn should_rewrite_updated_document_as_one_revision() {
+        let (source, _) = updated_pdf();
+        let document = Document::read(&source).unwrap();
+
+        let mut writer = Writer::new(Vec::new());
+        let report = writer.rewrite_document(&document).unwrap();
+
+        let written = read(writer.into_inner());
+        assert!(written.repair_report().is_none());
+        assert_eq!(written.xref().sections().len(), 1);
+        assert_eq!(written.xref().entries().len(), report.objects() + 1);
+        assert_eq!(written.page_content(0).unwrap(), document.page_content(0).unwrap());
+        assert_eq!(report.dropped_revisions(), 1);
+    }
+
+    #[test]
+    fn should_append_changes_as_incremental_update() {
+        let source = build_pdf(&[
+            b"<</Type /Catalog /Pages 2 0 R>>",
+            b"<</Type /Pages /Kids [] /Count 0>>",
+            b"<</Title (Draft)>>",
+        ]);
+        let original = source.slice(0..source.len()).to_vec();
+
+        let mut document = Document::read(&source).unwrap();
+        document.set_object(ObjectId::new(3, 0), Object::parse(b"<</Title (Final)>>").unwrap());
+        let outline = document.add_object(Object::parse(b"<</Count 0>>").unwrap());
+
+        let mut writer = Writer::new(Vec::new());
+        writer.update_document(&document, XrefStyle::Table).unwrap();
+        let bytes = writer.into_inner();
+
+        assert!(bytes.starts_with(&original));
+        assert!(String::from_utf8_lossy(&bytes[original.len()..]).contains("xref\n3 2\n"));
+
+        let updated = read(bytes);
+        assert!(updated.repair_report().is_none());
+        assert_eq!(updated.xref().sections().len(), 2);
+        assert_eq!(updated.trailer().get(b"/Prev").unwrap().as_i64(), Some(document.xref().startxref() as i64));
+        assert_eq!(updated.trailer().get(b"/Size").unwrap().as_i64(), Some(5));
+        assert_eq!(updated.get(ObjectId::new(3, 0)).unwrap().as_bytes(), b"<</Title (Final)>>");
+        assert_eq!(updated.get(outline).unwrap().as_bytes(), b"<</Count 0>>");
+    }
+
+    #[test]
+    fn should_append_update_with_xref_stream() {
+        let source = compressed_pdf(false);
+        let original = source.slice(0..source.len()).to_vec();
+
+        let mut document = Document::read(&source).unwrap();
+        assert!(Writer::new(Vec::new()).update_document(&document, XrefStyle::of(&document)).is_ok());
+
+        let mut catalog = document.catalog().clone();
+        catalog.insert(Name::new(b"/PageMode").unwrap(), Object::parse(b"/UseOutlines").unwrap());
+        document.set_object(ObjectId::new(1, 0), Object::Dicionary(catalog));
+
+        let mut writer = Writer::new(Vec::new());
+        writer.update_document(&document, XrefStyle::of(&document)).unwrap();
+        let bytes = writer.into_inner();
+
+        assert!(bytes.starts_with(&original));
+
+        let updated = read(bytes);
+        assert!(updated.repair_report().is_none());
+        assert_eq!(XrefStyle::of(&updated), XrefStyle::Stream);
+        assert_eq!(updated.xref().sections().len(), 2);
+        assert_eq!(updated.catalog().get(b"/PageMode").unwrap().as_bytes(), b"/UseOutlines");
+        assert_eq!(updated.pages().len(), 0);
+        assert!(matches!(updated.xref().entry(2), Some(XrefEntry::Compressed { stream: 3, index: 1 })));
+    }
+
+    #[test]
+    fn should_refuse_xref_streams_before_pdf_1_5() {
+        let source = build_pdf(&[b"<</Type /Catalog /Pages 2 0 R>>", b"<</Type /Pages /Kids [] /Count 0>>"]);
+        let mut document = read([b"%PDF-1.4".as_slice(), source.slice(8..source.len())].concat());
+        document.set_object(ObjectId::new(2, 0), Object::parse(b"<</Type /Pages /Kids [] /Count 0>>").unwrap());
+
+        let mut writer = Writer::new(Vec::new());
+        assert!(writer.update_document(&document, XrefStyle::Stream).is_err());
+    }
+
+    #[test]
+    fn should_refuse_encrypted_documents() {
+        let source = build_pdf(&[b"<</Type /Catalog /Pages 2 0 R>>", b"<</Type /Pages /Kids [] /Count 0>>"]);
+        let pdf = String::from_utf8(source.slice(0..source.len()).to_vec()).unwrap().replace("/Root 1 0 R", "/Root 1 0 R /Encrypt <<>>");
+
+        let mut writer = Writer::new(Vec::new());
+        assert!(writer.write_document(&read(pdf.into_bytes())).is_err());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,145 @@
+/// Default Flate compression level, as zlib's.
+const DEFAULT_COMPRESSION_LEVEL: u32 = 6;
+
+/// Number of objects packed in each object stream by `compact`.
+const COMPACT_OBJECT_STREAM_SIZE: usize = 100;
+
+/// Options of the files written by a `Writer`.
+///
+/// By default objects are written as they are, with a classic
+/// cross-reference table. `compact` makes the smallest files.
+///
+/// ```rs
+/// let options = WriteOptions::compact().with_compression_level(9);
+/// let mut writer = Writer::with_options(File::create("small.pdf")?, options);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WriteOptions {
+    /// Flate compression level, from 0 to 9.
+    compression_level: u32,
+    /// Number of objects packed in each object stream, or 0 for none.
+    object_stream_size: usize,
+    /// Whether streams without a filter are Flate-compressed.
+    compress_streams: bool,
+    /// Whether full rewrites are linearized.
+    linearize: bool,
+    /// Size of the hint data kept in the primary hint stream, or 0 for all of it.
+    primary_hint_limit: usize,
+}
+
+impl WriteOptions {
+
+    /// Creates the default options: no object streams and no stream compression.
+    pub fn new() -> Self {
+        Self {
+            compression_level: DEFAULT_COMPRESSION_LEVEL,
+            object_stream_size: 0,
+            compress_streams: false,
+            linearize: false,
+            primary_hint_limit: 0,
+        }
+    }
+
+    /// Creates options that pack objects into object streams and
+    /// compress streams without a filter.
+    pub fn compact() -> Self {
+        Self {
+            compression_level: DEFAULT_COMPRESSION_LEVEL,
+            object_stream_size: COMPACT_OBJECT_STREAM_SIZE,
+            compress_streams: true,
+            linearize: false,
+            primary_hint_limit: 0,
+        }
+    }
+
+    /// Sets the Flate compression level, from 0 (stored) to 9 (smallest).
+    ///
+    /// Higher levels are taken as 9.
+    pub fn with_compression_level(mut self, level: u32) -> Self {
+        self.compression_level = level.min(9);
+        self
+    }
+
+    /// Sets the number of objects packed in each object stream.
+    ///
+    /// Object streams, and the cross-reference stream they need, are
+    /// only written for PDF 1.5 and later; 0 writes none.
+    pub fn with_object_streams(mut self, size: usize) -> Self {
+        self.object_stream_size = size;
+        self
+    }
+
+    /// Sets whether streams without a filter are Flate-compressed.
+    pub fn with_stream_compression(mut self, compress: bool) -> Self {
+        self.compress_streams = compress;
+        self
+    }
+
+    /// Sets whether full rewrites are linearized ("Fast Web View"), so
+    /// that the first page can be shown before the file is fully read.
+    ///
+    /// Linearized files are written with cross-reference tables, and
+    /// without object streams.
+    pub fn with_linearization(mut self, linearize: bool) -> Self {
+        self.linearize = linearize;
+        self
+    }
+
+    /// Sets the size of the hint data kept in the primary hint stream of
+    /// linearized files; the rest goes to an overflow hint stream at the
+    /// end of the file. 0 keeps all of it in the primary hint stream.
+    pub fn with_primary_hint_limit(mut self, limit: usize) -> Self {
+        self.primary_hint_limit = limit;
+        self
+    }
+
+    /// Returns the Flate compression level.
+    pub fn compression_level(&self) -> u32 {
+        self.compression_level
+    }
+
+    /// Returns the number of objects packed in each object stream, or 0 for none.
+    pub fn object_stream_size(&self) -> usize {
+        self.object_stream_size
+    }
+
+    /// Returns `true` if streams without a filter are Flate-compressed.
+    pub fn compresses_streams(&self) -> bool {
+        self.compress_streams
+    }
+
+    /// Returns `true` if full rewrites are linearized.
+    pub fn linearizes(&self) -> bool {
+        self.linearize
+    }
+
+    /// Returns the size of the hint data kept in the primary hint stream, or 0 for all of it.
+    pub fn primary_hint_limit(&self) -> usize {
+        self.primary_hint_limit
+    }
+}
+
+impl Default for WriteOptions {
+
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WriteOptions;
+
+    #[test]
+    fn should_build_options() {
+        let options = WriteOptions::default();
+        assert_eq!((options.compression_level(), options.object_stream_size(), options.compresses_streams()), (6, 0, false));
+
+        let options = WriteOptions::compact().with_compression_level(12).with_object_streams(20);
+        assert_eq!((options.compression_level(), options.object_stream_size(), options.compresses_streams()), (9, 20, true));
+        assert!(!options.linearizes());
+
+        let options = options.with_linearization(true).with_primary_hint_limit(64);
+        assert_eq!((options.linearizes(), options.primary_hint_limit()), (true, 64));
+    }
+}
\ No newline at end of file
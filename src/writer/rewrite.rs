@@ -0,0 +1,282 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::document::Document;
+use crate::object::{Array, Dicionary, DicionaryEntry, Name, Null, Object, ObjectId, Reference, Stream};
+use crate::structure::IndirectObject;
+use crate::writer::is_cross_reference_container;
+
+/// Counts of what a full rewrite kept and discarded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RewriteReport {
+    /// Number of objects written.
+    objects: usize,
+    /// Number of objects in use that were not reachable.
+    dropped_objects: usize,
+    /// Size of the dropped objects, as they would have been written.
+    dropped_bytes: usize,
+    /// Number of earlier revisions discarded.
+    dropped_revisions: usize,
+}
+
+impl RewriteReport {
+
+    /// Returns the number of objects written.
+    pub fn objects(&self) -> usize {
+        self.objects
+    }
+
+    /// Returns the number of objects in use that were not reachable
+    /// from the trailer, and so were not written.
+    ///
+    /// Object and cross-reference streams are not counted: their
+    /// content is rebuilt rather than dropped.
+    pub fn dropped_objects(&self) -> usize {
+        self.dropped_objects
+    }
+
+    /// Returns the size of the dropped objects, as they would have been
+    /// written. Objects that cannot be read count for no bytes.
+    pub fn dropped_bytes(&self) -> usize {
+        self.dropped_bytes
+    }
+
+    /// Returns the number of earlier revisions (incremental updates)
+    /// whose cross-reference sections were discarded.
+    pub fn dropped_revisions(&self) -> usize {
+        self.dropped_revisions
+    }
+}
+
+/// The objects of a document reachable from its trailer, renumbered
+/// from 1 in the order they are found.
+pub(crate) struct Rewrite {
+    /// The objects, the first one numbered 1, with renumbered references.
+    pub(crate) objects: Vec<Object>,
+    /// The trailer entries of the rewritten file, without `/Size`.
+    pub(crate) trailer: Dicionary,
+    /// What was kept and discarded.
+    pub(crate) report: RewriteReport,
+    /// The new number of each object kept.
+    pub(crate) numbers: HashMap<ObjectId, u32>,
+}
+
+impl Rewrite {
+
+    /// Walks the document from the trailer's `/Root` and `/Info`.
+    ///
+    /// References to objects that are not in use are replaced by `null`,
+    /// as they would resolve to it.
+    pub(crate) fn new(document: &Document) -> Result<Self, String> {
+
+        let mut numbers = HashMap::new();
+        let mut found = Vec::new();
+        let mut queue = VecDeque::new();
+
+        for key in [b"/Root".as_slice(), b"/Info"] {
+            if let Some(id) = document.trailer().get(key).and_then(Object::as_reference) {
+                visit(document, id, &mut numbers, &mut queue);
+            }
+        }
+
+        while let Some(id) = queue.pop_front() {
+
+            let object = document.get(id)?;
+
+            let mut references = Vec::new();
+            collect_references(&object, &mut references);
+
+            for reference in references {
+                visit(document, reference, &mut numbers, &mut queue);
+            }
+
+            found.push(object);
+        }
+
+        let objects = found.iter().map(|object| renumber(object, &numbers)).collect::<Vec<_>>();
+
+        let mut trailer = Dicionary::new(vec![]);
+
+        for key in [b"/Root".as_slice(), b"/Info", b"/ID"] {
+            if let Some(value) = document.trailer().get(key) {
+                let value = renumber(value, &numbers);
+
+                if !matches!(value, Object::Null(_)) {
+                    trailer.insert(Name::new(key).unwrap(), value);
+                }
+            }
+        }
+
+        let mut report = RewriteReport {
+            objects: objects.len(),
+            dropped_revisions: document.xref().sections().len().saturating_sub(1),
+            ..RewriteReport::default()
+        };
+
+        for id in live_objects(document) {
+            if numbers.contains_key(&id) {
+                continue;
+            }
+
+            match document.get(id) {
+                Ok(object) if is_cross_reference_container(&object) => {}
+                Ok(object) => {
+                    report.dropped_objects += 1;
+                    report.dropped_bytes += IndirectObject::new(id, object).as_bytes().len();
+                }
+                Err(_) => report.dropped_objects += 1,
+            }
+        }
+
+        Ok(Self { objects, trailer, report, numbers })
+    }
+}
+
+/// Numbers an object the first time it is reached and queues it.
+fn visit(document: &Document, id: ObjectId, numbers: &mut HashMap<ObjectId, u32>, queue: &mut VecDeque<ObjectId>) {
+
+    if numbers.contains_key(&id) || !is_live(document, id) {
+        return;
+    }
+
+    // Object and cross-reference streams are never written as such.
+    if document.get(id).is_ok_and(|object| is_cross_reference_container(&object)) {
+        return;
+    }
+
+    numbers.insert(id, numbers.len() as u32 + 1);
+    queue.push_back(id);
+}
+
+/// Returns `true` if the object is in use, in the file or among the changes.
+fn is_live(document: &Document, id: ObjectId) -> bool {
+
+    document.changes().contains_key(&id)
+        || document.xref().entry(id.number())
+            .is_some_and(|entry| id.number() > 0 && entry.is_in_use() && entry.generation() == id.generation())
+}
+
+/// Returns the identifiers of every object in use, changes included.
+fn live_objects(document: &Document) -> Vec<ObjectId> {
+
+    let mut ids = document.xref().entries().iter()
+        .filter(|(number, entry)| **number > 0 && entry.is_in_use())
+        .map(|(number, entry)| ObjectId::new(*number, entry.generation()))
+        .collect::<Vec<_>>();
+
+    ids.extend(document.changes().keys().filter(|id| !ids.contains(id)).copied().collect::<Vec<_>>());
+
+    ids
+}
+
+/// Collects the references held by an object, in order.
+pub(crate) fn collect_references(object: &Object, references: &mut Vec<ObjectId>) {
+
+    match object {
+        Object::Reference(reference) => references.push(reference.id()),
+        Object::Array(array) => {
+            for object in array.as_objects() {
+                collect_references(object, references);
+            }
+        }
+        Object::Dicionary(dicionary) => {
+            for (_, object) in dicionary.iter() {
+                collect_references(object, references);
+            }
+        }
+        Object::Stream(stream) => {
+            for (_, object) in stream.dicionary().iter() {
+                collect_references(object, references);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Returns a copy of the object with its references renumbered, and
+/// references to objects not written replaced by `null`.
+pub(crate) fn renumber(object: &Object, numbers: &HashMap<ObjectId, u32>) -> Object {
+
+    match object {
+        Object::Reference(reference) => match numbers.get(&reference.id()) {
+            Some(number) => Object::Reference(Reference::new(ObjectId::new(*number, 0))),
+            None => Object::Null(Null::new()),
+        },
+        Object::Array(array) => {
+            Object::Array(Array::new(array.as_objects().iter().map(|object| renumber(object, numbers)).collect()))
+        }
+        Object::Dicionary(dicionary) => Object::Dicionary(renumber_dicionary(dicionary, numbers)),
+        Object::Stream(stream) => {
+            Object::Stream(Stream::with_data(renumber_dicionary(stream.dicionary(), numbers), stream.data().clone()))
+        }
+        _ => object.clone(),
+    }
+}
+
+/// Returns a copy of the dictionary with its references renumbered.
+pub(crate) fn renumber_dicionary(dicionary: &Dicionary, numbers: &HashMap<ObjectId, u32>) -> Dicionary {
+
+    Dicionary::new(dicionary.iter()
+        .map(|(key, value)| DicionaryEntry { key: key.clone(), value: renumber(value, numbers) })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::Rewrite;
+    use crate::byte_source::ByteSource;
+    use crate::byte_source::memory::MemoryByteSource;
+    use crate::document::Document;
+    use crate::document::tests::build_pdf;
+    use crate::object::{Object, ObjectId};
+    use crate::structure::xref::tests::{compressed_pdf, updated_pdf};
+
+    #[test]
+    fn should_keep_reachable_objects_renumbered() {
+        let source = build_pdf(&[
+            b"<</Type /Pages /Kids [] /Count 0>>",
+            b"(orphan)",
+            b"<</Type /Catalog /Pages 1 0 R /Outlines 9 0 R /Names 5 0 R>>",
+            b"<</Title (Report)>>",
+            b"[3 0 R 1 0 R]",
+        ]);
+        let pdf = String::from_utf8(source.slice(0..source.len()).to_vec()).unwrap()
+            .replace("/Root 1 0 R", "/Root 3 0 R /Info 4 0 R /ID [<01> <02>]");
+        let source: Arc<dyn ByteSource> = Arc::new(MemoryByteSource::new(pdf.into_bytes()));
+
+        let mut document = Document::read(&source).unwrap();
+        document.add_object(Object::parse(b"(added orphan)").unwrap());
+
+        let rewrite = Rewrite::new(&document).unwrap();
+
+        let objects = rewrite.objects.iter().map(|object| String::from_utf8_lossy(object.as_bytes()).into_owned()).collect::<Vec<_>>();
+        assert_eq!(objects, vec![
+            "<</Type /Catalog /Pages 3 0 R /Outlines null /Names 4 0 R>>",
+            "<</Title (Report)>>",
+            "<</Type /Pages /Kids [] /Count 0>>",
+            "[1 0 R 3 0 R]",
+        ]);
+        assert_eq!(rewrite.trailer.as_bytes(), b"<</Root 1 0 R /Info 2 0 R /ID [<01> <02>]>>");
+
+        assert_eq!(rewrite.report.objects(), 4);
+        assert_eq!(rewrite.report.dropped_objects(), 2);
+        assert_eq!(rewrite.report.dropped_bytes(), b"2 0 obj\n(orphan)\nendobj".len() + b"6 0 obj\n(added orphan)\nendobj".len());
+        assert_eq!(rewrite.report.dropped_revisions(), 0);
+    }
+
+    #[test]
+    fn should_drop_earlier_revisions_and_containers() {
+        let (source, _) = updated_pdf();
+        let rewrite = Rewrite::new(&Document::read(&source).unwrap()).unwrap();
+
+        assert_eq!(rewrite.report.dropped_revisions(), 1);
+        assert_eq!(rewrite.objects[0].as_dicionary().unwrap().get(b"/Type").unwrap().as_bytes(), b"/Catalog");
+
+        let rewrite = Rewrite::new(&Document::read(&compressed_pdf(false)).unwrap()).unwrap();
+
+        assert_eq!(rewrite.objects.len(), 2);
+        assert_eq!(rewrite.report.dropped_objects(), 0);
+        assert_eq!(rewrite.objects[0].as_dicionary().unwrap().get(b"/Pages").and_then(Object::as_reference), Some(ObjectId::new(2, 0)));
+    }
+}
\ No newline at end of file
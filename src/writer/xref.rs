@@ -0,0 +1,145 @@
+use std::collections::BTreeMap;
+
+use crate::document::Document;
+use crate::structure::xref::XrefEntry;
+
+/// The form of the cross-reference data that closes a file or an update.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum XrefStyle {
+    /// A classic `xref` table followed by a `trailer` dictionary.
+    Table,
+    /// A cross-reference stream (`/Type /XRef`), from PDF 1.5.
+    Stream,
+}
+
+impl XrefStyle {
+
+    /// Returns the style of the newest cross-reference section of a document.
+    ///
+    /// Hybrid-reference files, whose table points to a stream, are tables.
+    pub fn of(document: &Document) -> Self {
+
+        let source = document.source();
+        let position = document.xref().base() + document.xref().startxref();
+
+        match source.slice(position.min(source.len())..(position + 4).min(source.len())) {
+            b"xref" => XrefStyle::Table,
+            _ => XrefStyle::Stream,
+        }
+    }
+}
+
+/// Returns the runs of consecutive object numbers of the entries, as
+/// the first number and the count of each run.
+pub(crate) fn subsections(entries: &BTreeMap<u32, XrefEntry>) -> Vec<(u32, u32)> {
+
+    let mut runs: Vec<(u32, u32)> = Vec::new();
+
+    for number in entries.keys() {
+        match runs.last_mut() {
+            Some((first, count)) if *first + *count == *number => *count += 1,
+            _ => runs.push((*number, 1)),
+        }
+    }
+
+    runs
+}
+
+/// Returns a classic cross-reference table of the entries, from the
+/// `xref` keyword to the end of the last entry.
+pub(crate) fn table_bytes(entries: &BTreeMap<u32, XrefEntry>) -> Vec<u8> {
+
+    let mut bytes = b"xref\n".to_vec();
+    let mut values = entries.values();
+
+    for (first, count) in subsections(entries) {
+        bytes.extend_from_slice(format!("{} {}\n", first, count).as_bytes());
+
+        for entry in values.by_ref().take(count as usize) {
+            bytes.extend_from_slice(&entry.as_bytes());
+        }
+    }
+
+    bytes
+}
+
+/// Returns the field widths (`/W`) and the records of a cross-reference
+/// stream of the entries, in object number order.
+pub(crate) fn stream_records(entries: &BTreeMap<u32, XrefEntry>) -> ([usize; 3], Vec<u8>) {
+
+    let fields = entries.values()
+        .map(|entry| match *entry {
+            XrefEntry::Free { next, generation } => (0, next as u64, generation as u64),
+            XrefEntry::InUse { offset, generation } => (1, offset as u64, generation as u64),
+            XrefEntry::Compressed { stream, index } => (2, stream as u64, index as u64),
+        })
+        .collect::<Vec<_>>();
+
+    let width = |value: u64| (u64::BITS - value.leading_zeros()).div_ceil(8).max(1) as usize;
+
+    let widths = [
+        1,
+        width(fields.iter().map(|(_, second, _)| *second).max().unwrap_or(0)),
+        width(fields.iter().map(|(_, _, third)| *third).max().unwrap_or(0)),
+    ];
+
+    let mut records = Vec::with_capacity(fields.len() * widths.iter().sum::<usize>());
+
+    for (kind, second, third) in fields {
+        records.push(kind);
+        records.extend_from_slice(&second.to_be_bytes()[8 - widths[1]..]);
+        records.extend_from_slice(&third.to_be_bytes()[8 - widths[2]..]);
+    }
+
+    (widths, records)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{stream_records, subsections, table_bytes, XrefStyle};
+    use crate::document::Document;
+    use crate::document::tests::build_pdf;
+    use crate::structure::xref::XrefEntry;
+    use crate::structure::xref::tests::compressed_pdf;
+
+    fn entries() -> BTreeMap<u32, XrefEntry> {
+        BTreeMap::from([
+            (0, XrefEntry::Free { next: 0, generation: 65535 }),
+            (1, XrefEntry::InUse { offset: 15, generation: 0 }),
+            (4, XrefEntry::InUse { offset: 300, generation: 2 }),
+            (5, XrefEntry::Compressed { stream: 4, index: 1 }),
+        ])
+    }
+
+    #[test]
+    fn should_split_entries_into_subsections() {
+        assert_eq!(subsections(&entries()), vec![(0, 2), (4, 2)]);
+        assert_eq!(table_bytes(&entries()), b"xref\n0 2\n\
+            0000000000 65535 f\r\n0000000015 00000 n\r\n4 2\n\
+            0000000300 00002 n\r\n0000000000 00000 f\r\n".to_vec());
+    }
+
+    #[test]
+    fn should_encode_stream_records_with_minimal_widths() {
+        let (widths, records) = stream_records(&entries());
+
+        assert_eq!(widths, [1, 2, 2]);
+        assert_eq!(records, vec![
+            0, 0, 0, 0xFF, 0xFF,
+            1, 0, 15, 0, 0,
+            1, 0x01, 0x2C, 0, 2,
+            2, 0, 4, 0, 1,
+        ]);
+    }
+
+    #[test]
+    fn should_detect_xref_style() {
+        let table = build_pdf(&[b"<</Type /Catalog /Pages 2 0 R>>", b"<</Type /Pages /Kids [] /Count 0>>"]);
+
+        assert_eq!(XrefStyle::of(&Document::read(&table).unwrap()), XrefStyle::Table);
+        assert_eq!(XrefStyle::of(&Document::read(&compressed_pdf(false)).unwrap()), XrefStyle::Stream);
+        assert_eq!(XrefStyle::of(&Document::read(&compressed_pdf(true)).unwrap()), XrefStyle::Table);
+    }
+}
\ No newline at end of file